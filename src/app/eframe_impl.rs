@@ -1,13 +1,15 @@
 use std::sync::{Arc, Mutex};
 
 use super::App;
+use crate::disk::state::Xmrig;
 #[cfg(target_os = "windows")]
 use crate::errors::{ErrorButtons, ErrorFerris, process_running};
-use crate::helper::{Helper, ProcessName, ProcessState};
+use crate::helper::mqtt::MqttCommand;
+use crate::helper::{Helper, ProcessName, ProcessSignal, ProcessState};
 use crate::inits::init_text_styles;
 use crate::{NODE_MIDDLE, P2POOL_MIDDLE, SECOND, XMRIG_MIDDLE, XMRIG_PROXY_MIDDLE, XVB_MIDDLE};
 use derive_more::derive::{Deref, DerefMut};
-use log::debug;
+use log::{debug, info, warn};
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
@@ -50,6 +52,8 @@ impl eframe::App for App {
             self.quit_error_panel(ctx, &process_states, &key);
             return;
         }
+        // Run the commands received by the MQTT client.
+        self.mqtt_commands(&process_states);
         // Compare [og == state] & [node_vec/pool_vec] and enable diff if found.
         // The struct fields are compared directly because [Version]
         // contains Arc<Mutex>'s that cannot be compared easily.
@@ -76,7 +80,62 @@ impl eframe::App for App {
         p_xvb.alive = p_xvb.state != ProcessState::Dead;
         self.middle_panel(ctx, frame, key, &process_states);
     }
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        Helper::restore_remote_miners_without_xvb(&self.helper);
+    }
 }
+impl App {
+    // State used to start XMRig, the threads of a MQTT command are not saved
+    // and are used until the user changes the threads in the [XMRig] tab.
+    pub fn xmrig_state(&self) -> Xmrig {
+        let mut xmrig = self.state.xmrig.clone();
+        match self.mqtt_threads {
+            Some((threads, saved)) if saved == xmrig.current_threads => {
+                xmrig.current_threads = threads
+            }
+            _ => (),
+        }
+        xmrig
+    }
+    // Same as the buttons of the bottom panel, the state needed to start a process is here.
+    fn mqtt_commands(&mut self, states: &ProcessStatesGui) {
+        let commands = std::mem::take(&mut self.mqtt_api.lock().unwrap().commands);
+        for command in commands {
+            info!("MQTT | Running command {:?}", command);
+            match command {
+                MqttCommand::Signal(name, signal) => {
+                    let process = states.find(name);
+                    match signal {
+                        ProcessSignal::Start if !process.alive && !process.waiting => {
+                            match self.start_ready(process) {
+                                Ok(_) => self.start_process(name),
+                                Err(e) => warn!("MQTT | {} can not be started: {}", name, e),
+                            }
+                        }
+                        ProcessSignal::Stop if process.alive && !process.waiting => {
+                            process.stop(&self.helper)
+                        }
+                        ProcessSignal::Restart if process.alive && !process.waiting => {
+                            self.restart_process(name)
+                        }
+                        _ => debug!("MQTT | Command ignored in the current state of {}", name),
+                    }
+                }
+                MqttCommand::Threads(threads) => {
+                    self.mqtt_threads = Some((
+                        threads.clamp(1, self.max_threads),
+                        self.state.xmrig.current_threads,
+                    ));
+                    let xmrig = states.find(ProcessName::Xmrig);
+                    if xmrig.alive && !xmrig.waiting {
+                        self.restart_process(ProcessName::Xmrig);
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ProcessStateGui {
     pub name: ProcessName,
@@ -167,9 +167,11 @@ impl App {
         } else if key.is_c() && !wants_input {
             match self.tab {
                 Tab::Status => match self.state.status.submenu {
-                    Submenu::Processes => self.state.status.submenu = Submenu::Benchmarks,
+                    Submenu::Processes => self.state.status.submenu = Submenu::Fleet,
                     Submenu::P2pool => self.state.status.submenu = Submenu::Processes,
                     Submenu::Benchmarks => self.state.status.submenu = Submenu::P2pool,
+                    Submenu::Threads => self.state.status.submenu = Submenu::Benchmarks,
+                    Submenu::Fleet => self.state.status.submenu = Submenu::Threads,
                 },
                 Tab::Gupax => flip!(self.state.gupax.simple),
                 Tab::Node => flip!(self.state.node.simple),
@@ -185,7 +187,9 @@ impl App {
                 Tab::Status => match self.state.status.submenu {
                     Submenu::Processes => self.state.status.submenu = Submenu::P2pool,
                     Submenu::P2pool => self.state.status.submenu = Submenu::Benchmarks,
-                    Submenu::Benchmarks => self.state.status.submenu = Submenu::Processes,
+                    Submenu::Benchmarks => self.state.status.submenu = Submenu::Threads,
+                    Submenu::Threads => self.state.status.submenu = Submenu::Fleet,
+                    Submenu::Fleet => self.state.status.submenu = Submenu::Processes,
                 },
                 Tab::Gupax => flip!(self.state.gupax.simple),
                 Tab::P2pool => flip!(self.state.p2pool.simple),
@@ -16,6 +16,7 @@ use crate::components::gupax::FileWindow;
 use crate::components::node::Ping;
 use crate::components::node::REMOTE_NODES;
 use crate::components::node::RemoteNode;
+use crate::components::stratum::Probes;
 use crate::components::update::Update;
 use crate::disk::consts::NODE_TOML;
 use crate::disk::consts::POOL_TOML;
@@ -25,6 +26,9 @@ use crate::disk::gupax_p2pool_api::GupaxP2poolApi;
 use crate::disk::node::Node;
 use crate::disk::pool::Pool;
 use crate::disk::state::State;
+use crate::disk::xvb_history::{XvbHistory, get_xvb_history_path};
+use crate::disk::xvb_remote_pools::{XvbRemotePools, get_xvb_remote_pools_path};
+use crate::disk::xvb_samples::{XvbSamples, get_xvb_samples_path};
 use crate::errors::ErrorButtons;
 use crate::errors::ErrorFerris;
 use crate::errors::ErrorState;
@@ -32,20 +36,25 @@ use crate::helper::Helper;
 use crate::helper::Process;
 use crate::helper::ProcessName;
 use crate::helper::Sys;
+use crate::helper::fleet::PubFleetApi;
+use crate::helper::mqtt::PubMqttApi;
 use crate::helper::node::ImgNode;
 use crate::helper::node::PubNodeApi;
+use crate::helper::notify::PubNotifyApi;
 use crate::helper::p2pool::ImgP2pool;
 use crate::helper::p2pool::PubP2poolApi;
+use crate::helper::xrig::bench::PubBenchApi;
 use crate::helper::xrig::xmrig::ImgXmrig;
 use crate::helper::xrig::xmrig::PubXmrigApi;
 use crate::helper::xrig::xmrig_proxy::ImgProxy;
 use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
 use crate::helper::xvb::PubXvbApi;
+use crate::helper::xvb::endpoints::XvbEndpoints;
 use crate::helper::xvb::priv_stats::RuntimeMode;
 use crate::inits::init_text_styles;
-use crate::miscs::cmp_f64;
 use crate::miscs::get_exe;
 use crate::miscs::get_exe_dir;
+use crate::utils::benchmarks::{detect_benchmark_cpu, load_benchmarks};
 use crate::utils::constants::VISUALS;
 use crate::utils::macros::arc_mut;
 use crate::utils::sudo::SudoState;
@@ -100,11 +109,12 @@ pub struct App {
     pub update: Arc<Mutex<Update>>, // State for update data [update.rs]
     pub file_window: Arc<Mutex<FileWindow>>, // State for the path selector in [Gupax]
     pub ping: Arc<Mutex<Ping>>,     // Ping data found in [node.rs]
+    pub stratum_probes: Arc<Mutex<Probes>>, // Stratum probes of the [XMRig] pools found in [stratum.rs]
     pub og_node_vec: Vec<(String, PoolNode)>, // Manual Node database
-    pub node_vec: Vec<(String, PoolNode)>, // Manual Node database
+    pub node_vec: Vec<(String, PoolNode)>,  // Manual Node database
     pub og_pool_vec: Vec<(String, PoolNode)>, // Manual Pool database
-    pub pool_vec: Vec<(String, PoolNode)>, // Manual Pool database
-    pub diff: bool,                 // This bool indicates state changes
+    pub pool_vec: Vec<(String, PoolNode)>,  // Manual Pool database
+    pub diff: bool,                         // This bool indicates state changes
     // Restart state:
     // If Gupax updated itself, this represents that the
     // user should (but isn't required to) restart Gupax.
@@ -134,6 +144,11 @@ pub struct App {
     pub ip_local: Arc<Mutex<Option<IpAddr>>>,
     pub ip_public: Arc<Mutex<Option<Ipv4Addr>>>,
     pub proxy_port_reachable: Arc<Mutex<bool>>, // is the proxy port reachable from public ip ?
+    pub fleet_api: Arc<Mutex<PubFleetApi>>, // Remote instances and their stats, polled by the "helper"
+    pub notify_api: Arc<Mutex<PubNotifyApi>>, // Settings of the notifications, read by the "helper"
+    pub mqtt_api: Arc<Mutex<PubMqttApi>>, // Settings of the MQTT client and commands received by the "helper"
+    pub mqtt_threads: Option<(u16, u16)>, // XMRig threads given by a MQTT command and the threads of the state at this time
+    pub bench_api: Arc<Mutex<PubBenchApi>>, // Local benchmark of XMRig started from the [Status] tab
     // STDIN Buffer
     pub node_stdin: String, // The buffer between the node console and the [Helper]
     pub p2pool_stdin: String, // The buffer between the p2pool console and the [Helper]
@@ -149,6 +164,9 @@ pub struct App {
     // The below struct holds everything needed for it, the paths, the
     // actual stats, and all the functions needed to mutate them.
     pub gupax_p2pool_api: Arc<Mutex<GupaxP2poolApi>>,
+    pub xvb_history: Arc<Mutex<XvbHistory>>,
+    // Endpoints of XvB, shared with the Helper so that the pools refreshed by XvB are shown.
+    pub xvb_endpoints: Arc<Mutex<XvbEndpoints>>,
     // Static stuff
     pub benchmarks: Vec<Benchmark>,          // XMRig CPU benchmarks
     pub pid: sysinfo::Pid,                   // Gupax's PID
@@ -180,6 +198,7 @@ impl App {
             crate::miscs::clamp_scale(app.state.gupax.selected_scale),
         );
         cc.egui_ctx.set_visuals(VISUALS.clone());
+        app.mqtt_api.lock().unwrap().ctx = Some(cc.egui_ctx.clone());
         Self { resolution, ..app }
     }
 
@@ -239,6 +258,10 @@ impl App {
         let ip_local = arc_mut!(None);
         let ip_public = arc_mut!(None);
         let proxy_port_reachable = arc_mut!(false);
+        let fleet_api = arc_mut!(PubFleetApi::new());
+        let notify_api = arc_mut!(PubNotifyApi::new());
+        let mqtt_api = arc_mut!(PubMqttApi::new());
+        let bench_api = arc_mut!(PubBenchApi::new());
 
         info!("App Init | Sysinfo...");
         // We give this to the [Helper] thread.
@@ -261,11 +284,9 @@ impl App {
         // CPU Benchmark data initialization.
         info!("App Init | Initializing CPU benchmarks...");
         let benchmarks: Vec<Benchmark> = {
-            let cpu = sysinfo.cpus()[0].brand();
-            let mut json: Vec<Benchmark> =
-                serde_json::from_slice(include_bytes!("../../assets/cpu.json")).unwrap();
-            json.sort_by(|a, b| cmp_f64(strsim::jaro(&b.cpu, cpu), strsim::jaro(&a.cpu, cpu)));
-            json
+            let mut benchmarks = load_benchmarks(get_gupax_data_path().ok().as_deref());
+            detect_benchmark_cpu(&mut benchmarks, sysinfo.cpus()[0].brand());
+            benchmarks
         };
         info!("App Init | Assuming user's CPU is: {}", benchmarks[0].cpu);
 
@@ -273,6 +294,7 @@ impl App {
         let mut app = Self {
             tab: Tab::default(),
             ping: arc_mut!(Ping::new()),
+            stratum_probes: arc_mut!(Probes::new()),
             size: vec2(APP_DEFAULT_WIDTH, APP_DEFAULT_HEIGHT),
             must_resize: true,
             og: arc_mut!(State::new()),
@@ -313,6 +335,9 @@ impl App {
                 ip_local.clone(),
                 ip_public.clone(),
                 proxy_port_reachable.clone(),
+                fleet_api.clone(),
+                notify_api.clone(),
+                mqtt_api.clone(),
             )),
             node,
             p2pool,
@@ -335,6 +360,8 @@ impl App {
             alpha: 0,
             no_startup: false,
             gupax_p2pool_api: arc_mut!(GupaxP2poolApi::new()),
+            xvb_history: arc_mut!(XvbHistory::new()),
+            xvb_endpoints: arc_mut!(XvbEndpoints::default()),
             pub_sys,
             benchmarks,
             pid,
@@ -356,6 +383,11 @@ impl App {
             ip_local,
             ip_public,
             proxy_port_reachable,
+            fleet_api,
+            notify_api,
+            mqtt_api,
+            mqtt_threads: None,
+            bench_api,
             #[cfg(target_os = "windows")]
             xmrig_outside_warning_acknowledge: false,
         };
@@ -530,6 +562,38 @@ impl App {
         drop(gupax_p2pool_api);
         app.helper.lock().unwrap().gupax_p2pool_api = Arc::clone(&app.gupax_p2pool_api);
 
+        // Read the history of XvB rounds, it is not needed to run so an error is only logged.
+        info!("App Init | Reading XvB rounds history...");
+        let xvb_history_path = get_xvb_history_path(&app.os_data_path);
+        match XvbHistory::read(&xvb_history_path) {
+            Ok(history) => *app.xvb_history.lock().unwrap() = history,
+            Err(err) => {
+                error!("XvbHistory ... {}", err);
+                app.xvb_history.lock().unwrap().path = xvb_history_path;
+            }
+        }
+        app.helper.lock().unwrap().xvb_history = Arc::clone(&app.xvb_history);
+        app.helper.lock().unwrap().xvb_endpoints = Arc::clone(&app.xvb_endpoints);
+        // Read the hashrate sent by the XvB algorithm before the last exit, an invalid file is replaced.
+        info!("App Init | Reading XvB samples...");
+        let xvb_samples_path = get_xvb_samples_path(&app.os_data_path);
+        let xvb_samples = XvbSamples::read(&xvb_samples_path).unwrap_or_else(|err| {
+            error!("XvbSamples ... {}", err);
+            XvbSamples {
+                path: xvb_samples_path,
+                ..Default::default()
+            }
+        });
+        *app.helper.lock().unwrap().xvb_samples.lock().unwrap() = xvb_samples;
+        // Read the remote miners left on XvB by the last exit, an invalid file is replaced.
+        info!("App Init | Reading XvB remote miners...");
+        let xvb_remote_pools_path = get_xvb_remote_pools_path(&app.os_data_path);
+        app.xvb_api.lock().unwrap().remote_pools = XvbRemotePools::read(&xvb_remote_pools_path)
+            .unwrap_or_else(|err| {
+                error!("XvbRemotePools ... {}", err);
+                XvbRemotePools::new(&xvb_remote_pools_path)
+            });
+
         //----------------------------------------------------------------------------------------------------
         let mut og = app.og.lock().unwrap(); // Lock [og]
         // Handle max threads
@@ -540,6 +604,8 @@ impl App {
         if current > max {
             og.xmrig.current_threads = max;
         }
+        // the CPUs of the thread layout may not exist anymore
+        og.xmrig.cores.retain(|c| *c < max);
         // Handle [node_vec] overflow
         info!("App Init | Handling [node_vec] overflow");
         if og.p2pool.selected_node.index > app.og_node_vec.len() {
@@ -676,6 +742,15 @@ impl App {
         info!("Helper | Spawning helper thread...");
         Helper::spawn_helper(&app.helper, sysinfo, app.pid, app.max_threads);
         info!("Helper ... OK");
+        // Spawn the thread polling the remote instances.
+        app.fleet_api.lock().unwrap().instances = app.state.status.remotes.clone();
+        Helper::spawn_fleet_watchdog(&app.helper);
+        // Spawn the thread notifying the events of the processes.
+        app.notify_api.lock().unwrap().sync(&app.state.gupax);
+        Helper::spawn_notify_watchdog(&app.helper, &app.update.lock().unwrap().available);
+        // Spawn the thread publishing the state to the MQTT broker.
+        app.mqtt_api.lock().unwrap().sync(&app.state.gupax);
+        Helper::spawn_mqtt_watchdog(&app.helper);
 
         // Check for privilege. Should be Admin on [Windows] and NOT root on Unix.
         info!("App Init | Checking for privilege level...");
@@ -857,20 +932,39 @@ pub struct Benchmark {
 }
 #[cfg(test)]
 mod test {
-    use crate::miscs::cmp_f64;
+    use crate::utils::benchmarks::*;
 
     #[test]
     fn detect_benchmark_cpu() {
-        use crate::app::Benchmark;
-        let cpu = "AMD Ryzen 9 5950X 16-Core Processor";
-
-        let benchmarks: Vec<Benchmark> = {
-            let mut json: Vec<Benchmark> =
-                serde_json::from_slice(include_bytes!("../../assets/cpu.json")).unwrap();
-            json.sort_by(|a, b| cmp_f64(strsim::jaro(&b.cpu, cpu), strsim::jaro(&a.cpu, cpu)));
-            json
-        };
-
+        let mut benchmarks = parse_benchmarks(EMBEDDED_BENCHMARKS).unwrap();
+        crate::utils::benchmarks::detect_benchmark_cpu(
+            &mut benchmarks,
+            "AMD Ryzen 9 5950X 16-Core Processor",
+        );
+        assert!(benchmarks[0].cpu == "AMD Ryzen 9 5950X 16-Core Processor");
+        // name variants reported by the OS
+        crate::utils::benchmarks::detect_benchmark_cpu(&mut benchmarks, "AMD Ryzen 9 5950X");
         assert!(benchmarks[0].cpu == "AMD Ryzen 9 5950X 16-Core Processor");
     }
+
+    #[test]
+    fn benchmark_dataset_fallback() {
+        assert!(parse_benchmarks(b"[]").is_err());
+        assert!(parse_benchmarks(b"{\"cpu\": 1}").is_err());
+        let invalid = br#"[{"cpu": "X", "rank": 1, "percent": 100.0, "benchmarks": 1, "average": 10.0, "high": 5.0, "low": 20.0}]"#;
+        assert!(parse_benchmarks(invalid).is_err());
+        let valid = br#"[{"cpu": "X", "rank": 1, "percent": 100.0, "benchmarks": 1, "average": 10.0, "high": 20.0, "low": 5.0}]"#;
+        assert_eq!(parse_benchmarks(valid).unwrap().len(), 1);
+
+        let dir = std::env::temp_dir().join(format!("gupaxx_benchmarks_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let embedded = load_benchmarks(None).len();
+        assert_eq!(load_benchmarks(Some(&dir)).len(), embedded);
+        let path = dir.join(crate::disk::consts::CPU_BENCHMARKS_JSON);
+        std::fs::write(&path, invalid).unwrap();
+        assert_eq!(load_benchmarks(Some(&dir)).len(), embedded);
+        std::fs::write(&path, valid).unwrap();
+        assert_eq!(load_benchmarks(Some(&dir))[0].cpu, "X");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -247,60 +247,7 @@ impl crate::app::App {
                         .on_hover_text(restart_msg)
                         .clicked()
                 {
-                    let _ = self.og.lock().unwrap().update_absolute_path();
-                    let _ = self.state.update_absolute_path();
-                    // could improve this code with helper
-                    match process.name {
-                        ProcessName::Node => {
-                            Helper::restart_node(
-                                &self.helper,
-                                &self.state.node,
-                                &self.state.gupax.absolute_node_path,
-                            );
-                        }
-                        ProcessName::P2pool => {
-                            Helper::restart_p2pool(
-                                &self.helper,
-                                &self.state.p2pool,
-                                &self.state.node,
-                                &self.state.gupax.absolute_p2pool_path,
-                                self.backup_hosts.clone(),
-                                false,
-                            );
-                        }
-                        ProcessName::Xmrig => {
-                            if cfg!(windows) || !Helper::password_needed() {
-                                Helper::restart_xmrig(
-                                    &self.helper,
-                                    &self.state.xmrig,
-                                    &self.state.p2pool,
-                                    &self.state.xmrig_proxy,
-                                    &self.state.gupax.absolute_xmrig_path,
-                                    Arc::clone(&self.sudo),
-                                );
-                            } else {
-                                self.sudo.lock().unwrap().signal = ProcessSignal::Restart;
-                                self.error_state.ask_sudo(&self.sudo);
-                            }
-                        }
-                        ProcessName::XmrigProxy => {
-                            Helper::restart_xp(
-                                &self.helper,
-                                &self.state.xmrig_proxy,
-                                &self.state.p2pool,
-                                &self.state.gupax.absolute_xp_path,
-                            );
-                        }
-                        ProcessName::Xvb => {
-                            Helper::restart_xvb(
-                                &self.helper,
-                                &self.state.xvb,
-                                &self.state.p2pool,
-                                &self.state.xmrig,
-                                &self.state.xmrig_proxy,
-                            );
-                        }
-                    }
+                    self.restart_process(name);
                 }
             } else {
                 let text_err = self.start_ready(process).err().unwrap_or_default();
@@ -314,64 +261,7 @@ impl crate::app::App {
                             .on_disabled_hover_text(text_err)
                             .clicked()
                     {
-                        // check if process is running outside of Gupaxx, warn about it and do not start it.
-                        if process_running(name) {
-                            error!("Process already running outside: {}", name);
-                            self.error_state.set(
-                                PROCESS_OUTSIDE,
-                                ErrorFerris::Error,
-                                ErrorButtons::Okay,
-                            );
-                            return;
-                        }
-                        let _ = self.og.lock().unwrap().update_absolute_path();
-                        let _ = self.state.update_absolute_path();
-                        // start process
-                        match process.name {
-                            ProcessName::Node => Helper::start_node(
-                                &self.helper,
-                                &self.state.node,
-                                &self.state.gupax.absolute_node_path,
-                            ),
-                            ProcessName::P2pool => Helper::start_p2pool(
-                                &self.helper,
-                                &self.state.p2pool,
-                                &self.state.node,
-                                &self.state.gupax.absolute_p2pool_path,
-                                self.backup_hosts.clone(),
-                                false,
-                            ),
-
-                            ProcessName::Xmrig => {
-                                if cfg!(windows) || !Helper::password_needed() {
-                                    Helper::start_xmrig(
-                                        &self.helper,
-                                        &self.state.xmrig,
-                                        &self.state.p2pool,
-                                        &self.state.xmrig_proxy,
-                                        &self.state.gupax.absolute_xmrig_path,
-                                        Arc::clone(&self.sudo),
-                                    );
-                                } else {
-                                    self.sudo.lock().unwrap().signal = ProcessSignal::Start;
-                                    self.error_state.ask_sudo(&self.sudo);
-                                }
-                            }
-
-                            ProcessName::XmrigProxy => Helper::start_xp(
-                                &self.helper,
-                                &self.state.xmrig_proxy,
-                                &self.state.p2pool,
-                                &self.state.gupax.absolute_xp_path,
-                            ),
-                            ProcessName::Xvb => Helper::start_xvb(
-                                &self.helper,
-                                &self.state.xvb,
-                                &self.state.p2pool,
-                                &self.state.xmrig,
-                                &self.state.xmrig_proxy,
-                            ),
-                        }
+                        self.start_process(name);
                     }
                 });
                 ui.add_enabled_ui(false, |ui| {
@@ -385,6 +275,132 @@ impl crate::app::App {
             }
         });
     }
+    // Same as the restart button of the process.
+    pub fn restart_process(&mut self, name: ProcessName) {
+        let _ = self.og.lock().unwrap().update_absolute_path();
+        let _ = self.state.update_absolute_path();
+        // could improve this code with helper
+        match name {
+            ProcessName::Node => {
+                Helper::restart_node(
+                    &self.helper,
+                    &self.state.node,
+                    &self.state.gupax.absolute_node_path,
+                    &self.state.gupax.socks5,
+                );
+            }
+            ProcessName::P2pool => {
+                Helper::restart_p2pool(
+                    &self.helper,
+                    &self.state.p2pool,
+                    &self.state.node,
+                    &self.state.gupax.absolute_p2pool_path,
+                    self.backup_hosts.clone(),
+                    false,
+                    &self.state.gupax.socks5,
+                );
+            }
+            ProcessName::Xmrig => {
+                let xmrig = self.xmrig_state();
+                if !Helper::xmrig_password_needed(&xmrig, &self.state.gupax.absolute_xmrig_path) {
+                    Helper::restart_xmrig(
+                        &self.helper,
+                        &xmrig,
+                        &self.state.p2pool,
+                        &self.state.xmrig_proxy,
+                        &self.state.gupax.absolute_xmrig_path,
+                        Arc::clone(&self.sudo),
+                        &self.state.gupax.socks5,
+                    );
+                } else {
+                    self.sudo.lock().unwrap().signal = ProcessSignal::Restart;
+                    self.error_state.ask_sudo(&self.sudo);
+                }
+            }
+            ProcessName::XmrigProxy => {
+                Helper::restart_xp(
+                    &self.helper,
+                    &self.state.xmrig_proxy,
+                    &self.state.p2pool,
+                    &self.state.gupax.absolute_xp_path,
+                );
+            }
+            ProcessName::Xvb => {
+                Helper::restart_xvb(
+                    &self.helper,
+                    &self.state.xvb,
+                    &self.state.p2pool,
+                    &self.state.xmrig,
+                    &self.state.xmrig_proxy,
+                    &self.state.gupax.socks5,
+                );
+            }
+        }
+    }
+    // Same as the start button of the process, [start_ready] must be checked before.
+    pub fn start_process(&mut self, name: ProcessName) {
+        // check if process is running outside of Gupaxx, warn about it and do not start it.
+        // In attach mode, it is the process Gupaxx will monitor.
+        if !self.state.attached(name) && process_running(name) {
+            error!("Process already running outside: {}", name);
+            self.error_state
+                .set(PROCESS_OUTSIDE, ErrorFerris::Error, ErrorButtons::Okay);
+            return;
+        }
+        let _ = self.og.lock().unwrap().update_absolute_path();
+        let _ = self.state.update_absolute_path();
+        // start process
+        match name {
+            ProcessName::Node => Helper::start_node(
+                &self.helper,
+                &self.state.node,
+                &self.state.gupax.absolute_node_path,
+                &self.state.gupax.socks5,
+            ),
+            ProcessName::P2pool => Helper::start_p2pool(
+                &self.helper,
+                &self.state.p2pool,
+                &self.state.node,
+                &self.state.gupax.absolute_p2pool_path,
+                self.backup_hosts.clone(),
+                false,
+                &self.state.gupax.socks5,
+            ),
+
+            ProcessName::Xmrig => {
+                let xmrig = self.xmrig_state();
+                if !Helper::xmrig_password_needed(&xmrig, &self.state.gupax.absolute_xmrig_path) {
+                    Helper::start_xmrig(
+                        &self.helper,
+                        &xmrig,
+                        &self.state.p2pool,
+                        &self.state.xmrig_proxy,
+                        &self.state.gupax.absolute_xmrig_path,
+                        Arc::clone(&self.sudo),
+                        &self.state.gupax.socks5,
+                    );
+                } else {
+                    self.sudo.lock().unwrap().signal = ProcessSignal::Start;
+                    self.error_state.ask_sudo(&self.sudo);
+                }
+            }
+
+            ProcessName::XmrigProxy => Helper::start_xp(
+                &self.helper,
+                &self.state.xmrig_proxy,
+                &self.state.p2pool,
+                &self.state.gupax.absolute_xp_path,
+            ),
+            ProcessName::Xvb => Helper::start_xvb(
+                &self.helper,
+                &self.state.xvb,
+                &self.state.p2pool,
+                &self.state.xmrig,
+                &self.state.xmrig_proxy,
+                &self.state.gupax.socks5,
+            ),
+        }
+    }
     fn submenu(&mut self, ui: &mut Ui) {
         match self.tab {
             Tab::About => {}
@@ -439,7 +455,7 @@ impl crate::app::App {
         // ui.style_mut().wrap = Some(true);
         ui.group(|ui| {
             let spacing = spacing(ui);
-            let width = ((ui.available_width() / 1.5 / 3.0) - spacing).max(0.0);
+            let width = ((ui.available_width() / 1.5 / 5.0) - spacing).max(0.0);
             if ui
                 .add_sized(
                     [width, ui.available_height()],
@@ -478,6 +494,28 @@ impl crate::app::App {
             {
                 self.state.status.submenu = Submenu::Benchmarks;
             }
+            ui.separator();
+            if ui
+                .add_sized(
+                    [width, ui.available_height()],
+                    SelectableLabel::new(self.state.status.submenu == Submenu::Threads, "Threads"),
+                )
+                .on_hover_text(STATUS_SUBMENU_THREADS)
+                .clicked()
+            {
+                self.state.status.submenu = Submenu::Threads;
+            }
+            ui.separator();
+            if ui
+                .add_sized(
+                    [width, ui.available_height()],
+                    SelectableLabel::new(self.state.status.submenu == Submenu::Fleet, "Fleet"),
+                )
+                .on_hover_text(STATUS_SUBMENU_FLEET)
+                .clicked()
+            {
+                self.state.status.submenu = Submenu::Fleet;
+            }
         });
     }
     fn simple_advanced_submenu(ui: &mut Ui, simple: &mut bool, hover_text: (&str, &str)) {
@@ -583,11 +621,16 @@ impl crate::app::App {
         let path = match name {
             ProcessName::Node => {
                 // check path of DB valid, empty valid.
-                if !self.state.node.path_db.is_empty()
+                if !self.state.node.attached()
+                    && !self.state.node.path_db.is_empty()
                     && !Gupax::path_is_dir(&self.state.node.path_db)
                 {
                     return Err(format!("Error: {}", NODE_DB_DIR));
                 }
+                // the Node must not be started without the proxy it is asked to use
+                if !self.state.node.attached() && self.state.gupax.socks5.node_ip_invalid() {
+                    return Err(format!("Error: {}", GUPAX_SOCKS5_NODE_IP));
+                }
                 &self.state.gupax.node_path
             }
             ProcessName::P2pool => {
@@ -606,8 +649,8 @@ impl crate::app::App {
                 ""
             }
         };
-        // check path of binary except for XvB
-        if name != ProcessName::Xvb {
+        // check path of binary except for XvB and attached processes
+        if name != ProcessName::Xvb && !self.state.attached(name) {
             if path.is_empty() {
                 return Err(name.msg_binary_path_empty().to_string());
             }
@@ -19,4 +19,3 @@ pub mod console;
 pub mod header_tab;
 pub mod list_poolnode;
 pub mod state_edit_field;
-pub mod toggle;
@@ -5,7 +5,11 @@ use crate::components::gupax::*;
 use crate::components::update::Update;
 use crate::components::update::check_binary_path;
 use crate::disk::state::*;
+use crate::helper::mqtt::PubMqttApi;
+use crate::helper::notify::{NotifyEvent, PubNotifyApi};
 use crate::miscs::height_txt_before_button;
+use crate::utils::sudo::SudoState;
+use common::state_edit_field::StateTextEdit;
 use common::state_edit_field::slider_state_field;
 use log::debug;
 use std::path::Path;
@@ -24,6 +28,9 @@ impl Gupax {
         file_window: &Arc<Mutex<FileWindow>>,
         error_state: &mut ErrorState,
         restart: &Arc<Mutex<Restart>>,
+        sudo: &Arc<Mutex<SudoState>>,
+        notify_api: &Arc<Mutex<PubNotifyApi>>,
+        mqtt_api: &Arc<Mutex<PubMqttApi>>,
         _frame: &mut eframe::Frame,
         _ctx: &egui::Context,
         ui: &mut egui::Ui,
@@ -117,6 +124,34 @@ impl Gupax {
                 ui.separator();
                 self.horizontal_flex_auto_start(ui, AutoStart::ALL);
             });
+            debug!("Gupaxx Tab | Rendering notifications settings");
+            // the helper reads the settings of the state, also after a reset of the settings.
+            notify_api.lock().unwrap().sync(self);
+            ui.group(|ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add(Label::new(
+                        RichText::new("Notifications").underline().color(LIGHT_GRAY),
+                    ))
+                    .on_hover_text(GUPAX_NOTIFY);
+                });
+                ui.separator();
+                self.notifications.show(ui);
+            });
+            debug!("Gupaxx Tab | Rendering MQTT settings");
+            let mut mqtt = mqtt_api.lock().unwrap();
+            mqtt.sync(self);
+            let (connected, error) = (mqtt.connected, mqtt.error.clone());
+            drop(mqtt);
+            ui.group(|ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add(Label::new(
+                        RichText::new("MQTT").underline().color(LIGHT_GRAY),
+                    ))
+                    .on_hover_text(GUPAX_MQTT);
+                });
+                ui.separator();
+                self.mqtt.show(ui, connected, error.as_deref());
+            });
             if self.simple {
                 return;
             }
@@ -168,6 +203,33 @@ impl Gupax {
                 }
                 drop(guard);
             });
+            debug!("Gupaxx Tab | Rendering SOCKS5 proxy settings");
+            ui.group(|ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add(Label::new(
+                        RichText::new("SOCKS5 Proxy").underline().color(LIGHT_GRAY),
+                    ))
+                    .on_hover_text(GUPAX_SOCKS5);
+                });
+                ui.separator();
+                self.socks5.show(ui);
+            });
+            #[cfg(target_os = "linux")]
+            {
+                debug!("Gupaxx Tab | Rendering XMRig privileges");
+                ui.group(|ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.add(Label::new(
+                            RichText::new("XMRig privileges")
+                                .underline()
+                                .color(LIGHT_GRAY),
+                        ))
+                        .on_hover_text(GUPAX_XMRIG_PRIVILEGED);
+                    });
+                    ui.separator();
+                    self.xmrig_privileges(ui, sudo, error_state);
+                });
+            }
             // Saved [Tab]
             debug!("Gupaxx Tab | Rendering [Tab] selector");
             ui.group(|ui| {
@@ -421,6 +483,63 @@ impl Gupax {
                 });
             });
     }
+    /// status of the privileged setup and button to run it after a warning
+    #[cfg(target_os = "linux")]
+    fn xmrig_privileges(
+        &self,
+        ui: &mut Ui,
+        sudo: &Arc<Mutex<SudoState>>,
+        error_state: &mut ErrorState,
+    ) {
+        let mut guard = sudo.lock().unwrap();
+        // only checked again if the path of XMRig changed.
+        let privileged = match &guard.privileged {
+            Some((path, privileged)) if path == &self.absolute_xmrig_path => *privileged,
+            _ => {
+                let privileged = SudoState::xmrig_privileged(&self.absolute_xmrig_path);
+                guard.privileged = Some((self.absolute_xmrig_path.clone(), privileged));
+                privileged
+            }
+        };
+        ui.horizontal(|ui| {
+            if privileged {
+                ui.label(RichText::new(GUPAX_XMRIG_PRIVILEGED_YES).color(GREEN));
+            } else {
+                ui.label(RichText::new(GUPAX_XMRIG_PRIVILEGED_NO).color(LIGHT_GRAY));
+            }
+            if ui
+                .add_enabled(
+                    !guard.testing && !guard.setup_warning,
+                    Button::new("Run privileged setup"),
+                )
+                .on_hover_text(GUPAX_XMRIG_PRIVILEGED)
+                .clicked()
+            {
+                guard.setup_warning = true;
+            }
+        });
+        // nothing runs as root before the user read what the setup does.
+        if guard.setup_warning {
+            ui.label(RichText::new(GUPAX_XMRIG_PRIVILEGED_WARNING).color(RED));
+            ui.horizontal(|ui| {
+                if ui.button("Continue").clicked() {
+                    guard.setup_warning = false;
+                    drop(guard);
+                    error_state.ask_sudo(sudo);
+                    error_state.msg = GUPAX_XMRIG_PRIVILEGED_SETUP.to_string();
+                    let mut guard = sudo.lock().unwrap();
+                    guard.privileged_setup = true;
+                    guard.setup_msg.clear();
+                } else if ui.button("Cancel").clicked() {
+                    guard.setup_warning = false;
+                }
+            });
+            return;
+        }
+        if !guard.setup_msg.is_empty() {
+            ui.label(&guard.setup_msg);
+        }
+    }
 }
 fn path_binary(
     path: &mut String,
@@ -490,3 +609,142 @@ fn path_binary(
         });
     });
 }
+
+impl Notifications {
+    fn show(&mut self, ui: &mut Ui) {
+        ui.horizontal_wrapped(|ui| {
+            for event in NotifyEvent::iter() {
+                let mut enabled = self.events.contains(&event);
+                if ui
+                    .checkbox(&mut enabled, event.to_string())
+                    .on_hover_text(event.help_msg())
+                    .clicked()
+                {
+                    self.enable(event, enabled);
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            #[cfg(target_os = "linux")]
+            {
+                ui.checkbox(&mut self.desktop, "Desktop")
+                    .on_hover_text(GUPAX_NOTIFY_DESKTOP);
+                ui.separator();
+            }
+            ui.checkbox(&mut self.webhook, "Webhook")
+                .on_hover_text(GUPAX_NOTIFY_WEBHOOK);
+            ui.add_enabled_ui(self.webhook, |ui| {
+                StateTextEdit::new(ui)
+                    .description("URL ")
+                    .max_ch(255)
+                    .help_msg(GUPAX_NOTIFY_WEBHOOK)
+                    .validations(&[|x| x.starts_with("http://") || x.starts_with("https://")])
+                    .build(ui, &mut self.webhook_url);
+            });
+        });
+    }
+}
+
+impl Mqtt {
+    fn show(&mut self, ui: &mut Ui, connected: bool, error: Option<&str>) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.enabled, "Enable")
+                .on_hover_text(GUPAX_MQTT);
+            ui.separator();
+            if !self.enabled {
+                ui.label(RichText::new("Disabled").color(LIGHT_GRAY));
+            } else if connected {
+                ui.label(RichText::new("Connected").color(GREEN));
+            } else {
+                ui.label(RichText::new("Not connected").color(RED))
+                    .on_hover_text(error.unwrap_or_default());
+            }
+        });
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.horizontal(|ui| {
+                StateTextEdit::new(ui)
+                    .description("HOST ")
+                    .max_ch(255)
+                    .help_msg(GUPAX_MQTT_HOST)
+                    .validations(&[|x| REGEXES.ipv4.is_match(x) || REGEXES.domain.is_match(x)])
+                    .build(ui, &mut self.host);
+            });
+            ui.horizontal(|ui| {
+                StateTextEdit::new(ui)
+                    .description("PORT ")
+                    .max_ch(5)
+                    .help_msg(GUPAX_MQTT_PORT)
+                    .validations(&[|x| REGEXES.port.is_match(x)])
+                    .build(ui, &mut self.port);
+            });
+            ui.horizontal(|ui| {
+                StateTextEdit::new(ui)
+                    .description("USER ")
+                    .max_ch(255)
+                    .help_msg(GUPAX_MQTT_USERNAME)
+                    .build(ui, &mut self.username);
+            });
+            ui.horizontal(|ui| {
+                ui.label("PASS ");
+                ui.add(TextEdit::singleline(&mut self.password).password(true))
+                    .on_hover_text(GUPAX_MQTT_PASSWORD);
+            });
+            ui.horizontal(|ui| {
+                StateTextEdit::new(ui)
+                    .description("PREFIX ")
+                    .max_ch(255)
+                    .help_msg(GUPAX_MQTT_PREFIX)
+                    .validations(&[|x| !x.contains(['+', '#']) && !x.ends_with('/')])
+                    .build(ui, &mut self.prefix);
+            });
+            ui.checkbox(&mut self.commands, "Accept commands")
+                .on_hover_text(GUPAX_MQTT_COMMANDS);
+        });
+    }
+}
+
+impl Socks5 {
+    fn show(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.enabled, "Use a SOCKS5 proxy")
+            .on_hover_text(GUPAX_SOCKS5);
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.horizontal(|ui| {
+                StateTextEdit::new(ui)
+                    .description("IP   ")
+                    .max_ch(255)
+                    .help_msg(GUPAX_SOCKS5_IP)
+                    .validations(&[|x| REGEXES.ipv4.is_match(x) || REGEXES.domain.is_match(x)])
+                    .build(ui, &mut self.ip);
+            });
+            ui.horizontal(|ui| {
+                StateTextEdit::new(ui)
+                    .description("PORT ")
+                    .max_ch(5)
+                    .help_msg(GUPAX_SOCKS5_PORT)
+                    .validations(&[|x| REGEXES.port.is_match(x)])
+                    .build(ui, &mut self.port);
+            });
+            ui.checkbox(&mut self.tor, "Tor")
+                .on_hover_text(GUPAX_SOCKS5_TOR);
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.use_node, "Node")
+                    .on_hover_text(GUPAX_SOCKS5_NODE);
+                ui.separator();
+                ui.checkbox(&mut self.use_p2pool, "P2Pool")
+                    .on_hover_text(GUPAX_SOCKS5_P2POOL);
+                ui.separator();
+                ui.checkbox(&mut self.use_xmrig, "XMRig")
+                    .on_hover_text(GUPAX_SOCKS5_XMRIG);
+                ui.separator();
+                ui.checkbox(&mut self.use_gupaxx, "Gupaxx")
+                    .on_hover_text(GUPAX_SOCKS5_GUPAXX);
+            });
+        });
+        if self.enabled {
+            ui.label(RichText::new(GUPAX_SOCKS5_LATENCY).color(ORANGE));
+        }
+        if self.node_ip_invalid() {
+            ui.label(RichText::new(GUPAX_SOCKS5_NODE_IP).color(RED));
+        }
+    }
+}
@@ -69,6 +69,9 @@ impl crate::app::App {
                         self.max_threads,
                         &self.gupax_p2pool_api,
                         &self.benchmarks,
+                        &self.bench_api,
+                        &self.state.gupax.absolute_xmrig_path,
+                        &self.fleet_api,
                         ctx,
                         ui,
                     );
@@ -83,6 +86,9 @@ impl crate::app::App {
                         &self.file_window,
                         &mut self.error_state,
                         &self.restart,
+                        &self.sudo,
+                        &self.notify_api,
+                        &self.mqtt_api,
                         frame,
                         ctx,
                         ui,
@@ -121,15 +127,19 @@ impl crate::app::App {
                 }
                 Tab::Xmrig => {
                     debug!("App | Entering [XMRig] Tab");
+                    // the pools are probed through the proxy of Gupaxx
+                    self.stratum_probes.lock().unwrap().socks5 = self.state.gupax.socks5.gupaxx();
                     crate::disk::state::Xmrig::show(
                         &mut self.state.xmrig,
                         &mut self.pool_vec,
                         &self.xmrig,
                         &self.xmrig_api,
+                        &self.stratum_probes,
                         &mut self.xmrig_stdin,
                         ctx,
                         ui,
                         self.state.p2pool.stratum_port(),
+                        &self.pub_sys,
                     );
                 }
                 Tab::XmrigProxy => {
@@ -158,6 +168,9 @@ impl crate::app::App {
                         &self.xvb_api,
                         &self.xmrig_api,
                         &self.xmrig_proxy_api,
+                        &self.xvb_history,
+                        &self.xvb_endpoints,
+                        &self.state.status.remotes,
                         states.is_alive(ProcessName::Xvb),
                     );
                 }
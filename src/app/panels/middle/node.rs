@@ -17,19 +17,24 @@
 
 use crate::app::panels::middle::common::console::{console, input_args_field, start_options_field};
 use crate::app::panels::middle::common::header_tab::header_tab;
-use crate::app::panels::middle::common::state_edit_field::{path_db_field, slider_state_field};
+use crate::app::panels::middle::common::state_edit_field::{
+    StateTextEdit, path_db_field, slider_state_field,
+};
 use crate::app::panels::middle::{rpc_bind_field, rpc_port_field, zmq_bind_field, zmq_port_field};
+use crate::regex::REGEXES;
 use crate::{
-    NODE_DNS_BLOCKLIST, NODE_DNS_CHECKPOINT, NODE_FULL_MEM, NODE_INPUT, NODE_PRUNNING, NODE_URL,
+    NODE_ATTACH, NODE_DNS_BLOCKLIST, NODE_DNS_CHECKPOINT, NODE_FULL_MEM, NODE_I2P,
+    NODE_I2P_INBOUND, NODE_I2P_INBOUND_ADDRESS, NODE_I2P_INBOUND_MAX, NODE_I2P_INBOUND_PORT,
+    NODE_I2P_IP, NODE_I2P_P2POOL, NODE_I2P_PORT, NODE_INPUT, NODE_PRUNNING, NODE_URL,
     START_OPTIONS_HOVER,
 };
-use egui::TextStyle;
+use egui::{TextStyle, Ui};
 use std::sync::{Arc, Mutex};
 
 use log::debug;
 
 use crate::components::gupax::FileWindow;
-use crate::disk::state::{Node, StartOptionsMode};
+use crate::disk::state::{I2p, Node, StartOptionsMode};
 use crate::helper::node::PubNodeApi;
 use crate::helper::{Process, ProcessName};
 use crate::{P2POOL_IN, P2POOL_LOG, P2POOL_OUT, SPACE};
@@ -83,6 +88,9 @@ impl Node {
                     Self::process_name().start_options_hint(),
                     START_OPTIONS_HOVER,
                 );
+                ui.add_space(SPACE);
+                ui.checkbox(&mut self.attach, "Attach to an external instance")
+                    .on_hover_text(NODE_ATTACH);
                 //---------------------------------------------------------------------------------------------------- Prunned checkbox
                 if !self.arguments.is_empty() {
                     ui.disable();
@@ -166,8 +174,69 @@ impl Node {
                         guard.picked_nodedb = false;
                     }
                 });
+                //---------------------------------------------------------------------------------------------------- I2P
+                debug!("Node Tab | Rendering [I2P]");
+                ui.add_space(SPACE);
+                ui.group(|ui| {
+                    self.i2p.show(ui);
+                });
                 ui.add_space(SPACE);
             }
         });
     }
 }
+
+impl I2p {
+    fn show(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.enabled, "Use I2P")
+            .on_hover_text(NODE_I2P);
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.horizontal(|ui| {
+                StateTextEdit::new(ui)
+                    .description("IP   ")
+                    .max_ch(255)
+                    .help_msg(NODE_I2P_IP)
+                    .validations(&[|x| REGEXES.ipv4.is_match(x) || REGEXES.domain.is_match(x)])
+                    .build(ui, &mut self.ip);
+            });
+            ui.horizontal(|ui| {
+                StateTextEdit::new(ui)
+                    .description("PORT ")
+                    .max_ch(5)
+                    .help_msg(NODE_I2P_PORT)
+                    .validations(&[|x| REGEXES.port.is_match(x)])
+                    .build(ui, &mut self.port);
+            });
+            ui.checkbox(&mut self.use_p2pool, "Route P2Pool through I2P")
+                .on_hover_text(NODE_I2P_P2POOL);
+            ui.separator();
+            ui.checkbox(&mut self.inbound, "Anonymous inbound")
+                .on_hover_text(NODE_I2P_INBOUND);
+            ui.add_enabled_ui(self.inbound, |ui| {
+                ui.horizontal(|ui| {
+                    StateTextEdit::new(ui)
+                        .description("ADDRESS ")
+                        .max_ch(60)
+                        .help_msg(NODE_I2P_INBOUND_ADDRESS)
+                        .validations(&[|x| x.ends_with(".b32.i2p")])
+                        .build(ui, &mut self.inbound_address);
+                });
+                ui.horizontal(|ui| {
+                    StateTextEdit::new(ui)
+                        .description("PORT    ")
+                        .max_ch(5)
+                        .help_msg(NODE_I2P_INBOUND_PORT)
+                        .validations(&[|x| REGEXES.port.is_match(x)])
+                        .build(ui, &mut self.inbound_port);
+                });
+                slider_state_field(
+                    ui,
+                    "Max connections [1-128]:",
+                    NODE_I2P_INBOUND_MAX,
+                    &mut self.inbound_max,
+                    1..=128,
+                );
+            });
+        });
+    }
+}
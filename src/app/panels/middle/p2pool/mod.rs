@@ -1,4 +1,5 @@
 use crate::app::panels::middle::common::console::{console, input_args_field, start_options_field};
+use crate::app::panels::middle::common::state_edit_field::StateTextEdit;
 use crate::disk::state::{P2pool, StartOptionsMode, State};
 use crate::helper::p2pool::PubP2poolApi;
 // Gupaxx - Fork of Gupax
@@ -35,7 +36,7 @@ impl P2pool {
     pub fn show(
         &mut self,
         node_vec: &mut Vec<(String, PoolNode)>,
-        _og: &Arc<Mutex<State>>,
+        og: &Arc<Mutex<State>>,
         ping: &Arc<Mutex<Ping>>,
         process: &Arc<Mutex<Process>>,
         api: &Arc<Mutex<PubP2poolApi>>,
@@ -96,6 +97,16 @@ impl P2pool {
                     Self::process_name().start_options_hint(),
                     START_OPTIONS_HOVER,
                 );
+                ui.checkbox(&mut self.attach, "Attach to an external instance")
+                    .on_hover_text(P2POOL_ATTACH);
+                if self.attach {
+                    StateTextEdit::new(ui)
+                        .description("Data API directory ")
+                        .max_ch(255)
+                        .help_msg(P2POOL_ATTACH_DATA_API)
+                        .validations(&[|x| Path::new(x).is_dir()])
+                        .build(ui, &mut self.attach_data_api);
+                }
             }
             debug!("P2Pool Tab | Rendering [Address]");
             crate::app::panels::middle::common::state_edit_field::monero_address_field(
@@ -105,7 +116,7 @@ impl P2pool {
             );
 
             if self.simple {
-                self.simple(ui, ping, &mut api_lock);
+                self.simple(ui, ping, og, &mut api_lock);
             } else {
                 if !self.arguments.is_empty() {
                     ui.disable();
@@ -24,6 +24,7 @@ use crate::components::node::RemoteNode;
 use crate::components::node::format_ip_location;
 use crate::components::node::format_ms;
 use crate::disk::state::P2pool;
+use crate::disk::state::State;
 use crate::miscs::height_txt_before_button;
 use egui::Button;
 use egui::Checkbox;
@@ -39,7 +40,13 @@ use log::*;
 use super::p2pool::PubP2poolApi;
 
 impl P2pool {
-    pub(super) fn simple(&mut self, ui: &mut Ui, ping: &Arc<Mutex<Ping>>, api: &mut PubP2poolApi) {
+    pub(super) fn simple(
+        &mut self,
+        ui: &mut Ui,
+        ping: &Arc<Mutex<Ping>>,
+        og: &Arc<Mutex<State>>,
+        api: &mut PubP2poolApi,
+    ) {
         ui.vertical_centered(|ui|{
             ui.add_space(SPACE);
             ui.checkbox(&mut self.local_node, "Start with a local node").on_hover_text("If checked (recommended), p2pool will start trying to use the local node.\nCheck the Node tab to start a local node.\nIf unchecked, p2pool will attempt to use a remote node.");
@@ -131,7 +138,10 @@ impl P2pool {
                                     .on_hover_text(P2POOL_PING)
                                     .clicked()
                                 {
-                                    Ping::spawn_thread(ping);
+                                    Ping::spawn_thread(
+                                        ping,
+                                        og.lock().unwrap().gupax.socks5.gupaxx(),
+                                    );
                                 }
                             });
                             // [Last <-]
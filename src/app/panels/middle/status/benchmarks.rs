@@ -17,21 +17,31 @@
 
 use std::sync::{Arc, Mutex};
 
-use crate::{app::Benchmark, disk::state::Status, helper::xrig::xmrig::PubXmrigApi};
-use egui::{ProgressBar, ScrollArea, TextWrapMode};
+use crate::{
+    app::{Benchmark, panels::middle::common::state_edit_field::slider_state_field},
+    disk::{state::Status, status::BenchSize},
+    helper::xrig::{bench::PubBenchApi, xmrig::PubXmrigApi},
+};
+use egui::{Button, ComboBox, ProgressBar, ScrollArea, TextWrapMode};
 use egui_extras::{Column, TableBuilder};
 use readable::num::{Float, Percent, Unsigned};
+use std::path::Path;
+use strum::IntoEnumIterator;
 
 use crate::constants::*;
 use egui::{Label, RichText};
 use log::*;
 impl Status {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn benchmarks(
         &mut self,
         ui: &mut egui::Ui,
         benchmarks: &[Benchmark],
         xmrig_alive: bool,
         xmrig_api: &Arc<Mutex<PubXmrigApi>>,
+        bench_api: &Arc<Mutex<PubBenchApi>>,
+        xmrig_path: &Path,
+        max_threads: u16,
     ) {
         debug!("Status Tab | Rendering [Benchmarks]");
         let text = ui.text_style_height(&egui::TextStyle::Body);
@@ -74,7 +84,11 @@ impl Status {
             })
         });
 
-        // User's CPU hashrate comparison (if XMRig is alive).
+        // Benchmarks run on this computer, compared to the reference of the CPU.
+        ui.group(|ui| {
+            self.local_benchmarks(ui, cpu, xmrig_alive, bench_api, xmrig_path, max_threads);
+        });
+
         // User's CPU hashrate comparison (if XMRig is alive).
         ui.vertical_centered(|ui| {
             ui.add_space(SPACE);
@@ -219,4 +233,168 @@ impl Status {
                 });
         });
     }
+    fn local_benchmarks(
+        &mut self,
+        ui: &mut egui::Ui,
+        cpu: &Benchmark,
+        xmrig_alive: bool,
+        bench_api: &Arc<Mutex<PubBenchApi>>,
+        xmrig_path: &Path,
+        max_threads: u16,
+    ) {
+        let mut bench = bench_api.lock().unwrap();
+        if let Some(result) = bench.result.take() {
+            self.local_benchmarks.insert(0, result);
+        }
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new("Local Benchmarks").underline().color(BONE))
+                .on_hover_text(STATUS_BENCH_LOCAL);
+        });
+        let mut run = false;
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(!bench.running, |ui| {
+                ComboBox::from_id_salt("bench_size")
+                    .selected_text(format!("{} hashes", self.bench_size))
+                    .show_ui(ui, |ui| {
+                        for size in BenchSize::iter() {
+                            ui.selectable_value(
+                                &mut self.bench_size,
+                                size,
+                                format!("{size} hashes"),
+                            );
+                        }
+                    })
+                    .response
+                    .on_hover_text(STATUS_BENCH_SIZE);
+                ui.checkbox(&mut self.bench_hugepages, "Huge pages")
+                    .on_hover_text(STATUS_BENCH_HUGEPAGES);
+            });
+            if bench.running {
+                if ui.button("Stop").on_hover_text(STATUS_BENCH_STOP).clicked() {
+                    bench.cancel = true;
+                }
+                ui.spinner();
+            } else {
+                let ready = !xmrig_alive && xmrig_path.is_file();
+                if ui
+                    .add_enabled(ready, Button::new("Run benchmark"))
+                    .on_hover_text(STATUS_BENCH_RUN)
+                    .on_disabled_hover_text(STATUS_BENCH_DISABLED)
+                    .clicked()
+                {
+                    run = true;
+                }
+            }
+        });
+        if run {
+            drop(bench);
+            PubBenchApi::start(
+                bench_api,
+                xmrig_path,
+                self.bench_size,
+                self.bench_threads,
+                self.bench_hugepages,
+            );
+            bench = bench_api.lock().unwrap();
+        }
+        ui.add_enabled_ui(!bench.running, |ui| {
+            self.bench_threads = self.bench_threads.clamp(1, max_threads.max(1));
+            slider_state_field(
+                ui,
+                &format!("Threads [1-{max_threads}]:"),
+                STATUS_BENCH_THREADS,
+                &mut self.bench_threads,
+                1..=max_threads.max(1),
+            );
+        });
+        if !bench.progress.is_empty() {
+            ui.label(RichText::new(bench.progress.as_str()).color(LIGHT_GRAY));
+        }
+        drop(bench);
+        // the reference of the detected CPU first, then the local runs from the newest
+        let text = ui.text_style_height(&egui::TextStyle::Body);
+        let mut delete = None;
+        ui.push_id("local_benchmarks", |ui| {
+            TableBuilder::new(ui)
+                .columns(Column::auto(), 8)
+                .header(text * 1.5, |mut header| {
+                    for title in [
+                        "Date",
+                        "Hashes",
+                        "Threads",
+                        "Huge pages",
+                        "Time",
+                        "Hashrate",
+                        "Reference",
+                        "",
+                    ] {
+                        header.col(|ui| {
+                            ui.label(RichText::new(title).underline().color(BONE));
+                        });
+                    }
+                })
+                .body(|mut body| {
+                    body.row(text, |mut row| {
+                        row.col(|ui| {
+                            ui.label(RichText::new("xmrig.com").color(LIGHT_GRAY))
+                                .on_hover_text(STATUS_SUBMENU_OTHER_CPUS);
+                        });
+                        for _ in 0..4 {
+                            row.col(|_| {});
+                        }
+                        row.col(|ui| {
+                            ui.label(format!("{} H/s", Float::from_0(cpu.average.into())));
+                        });
+                        row.col(|ui| {
+                            ui.label("100%");
+                        });
+                        row.col(|_| {});
+                    });
+                    body.rows(text, self.local_benchmarks.len(), |mut row| {
+                        let i = row.index();
+                        let local = &self.local_benchmarks[i];
+                        row.col(|ui| {
+                            ui.label(local.date.as_str());
+                        });
+                        row.col(|ui| {
+                            ui.label(local.size.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(local.threads.to_string());
+                        });
+                        row.col(|ui| {
+                            if local.hugepages {
+                                ui.label(RichText::new("Yes").color(GREEN));
+                            } else {
+                                ui.label(RichText::new("No").color(RED));
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{:.1}s", local.seconds));
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{} H/s", Float::from_0(local.hashrate.into())));
+                        });
+                        row.col(|ui| {
+                            // compared to the average of the same CPU on xmrig.com
+                            let percent = local.hashrate / cpu.average * 100.0;
+                            let color = if percent >= 100.0 { GREEN } else { ORANGE };
+                            ui.label(RichText::new(Percent::from(percent).as_str()).color(color));
+                        });
+                        row.col(|ui| {
+                            if ui
+                                .button("Delete")
+                                .on_hover_text(STATUS_BENCH_DELETE)
+                                .clicked()
+                            {
+                                delete = Some(i);
+                            }
+                        });
+                    });
+                });
+        });
+        if let Some(i) = delete {
+            self.local_benchmarks.remove(i);
+        }
+    }
 }
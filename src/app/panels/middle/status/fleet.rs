@@ -0,0 +1,183 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex};
+
+use egui::{Button, ComboBox, Label, RichText, ScrollArea, TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+use log::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    app::panels::middle::common::state_edit_field::StateTextEdit,
+    constants::*,
+    disk::{
+        state::Status,
+        status::{RemoteInstance, RemoteKind},
+    },
+    helper::fleet::{PubFleetApi, RemoteApi, RemoteStats},
+    regex::REGEXES,
+};
+
+impl Status {
+    pub(super) fn fleet(&mut self, ui: &mut Ui, fleet_api: &Arc<Mutex<PubFleetApi>>) {
+        debug!("Status Tab | Rendering [Fleet]");
+        // the helper polls the instances of the state, also after a reset of the settings.
+        {
+            let mut fleet = fleet_api.lock().unwrap();
+            if fleet.instances != self.remotes {
+                fleet.instances.clone_from(&self.remotes);
+            }
+        }
+        ui.group(|ui| {
+            ui.horizontal_wrapped(|ui| {
+                let mut valid = StateTextEdit::new(ui)
+                    .description("Name ")
+                    .max_ch(30)
+                    .help_msg(STATUS_FLEET_NAME)
+                    .validations(&[|x| REGEXES.name.is_match(x)])
+                    .build(ui, &mut self.remote.name);
+                let kind = self.remote.kind;
+                ComboBox::from_id_salt("fleet_kind")
+                    .selected_text(self.remote.kind.to_string())
+                    .show_ui(ui, |ui| {
+                        for k in RemoteKind::iter() {
+                            ui.selectable_value(&mut self.remote.kind, k, k.to_string());
+                        }
+                    })
+                    .response
+                    .on_hover_text(STATUS_FLEET_KIND);
+                // follow the default port of the selected software if it was not modified.
+                if kind != self.remote.kind && self.remote.port == kind.default_port().to_string() {
+                    self.remote.port = self.remote.kind.default_port().to_string();
+                }
+                valid &= StateTextEdit::new(ui)
+                    .description("Host ")
+                    .max_ch(255)
+                    .help_msg(STATUS_FLEET_HOST)
+                    .validations(&[|x| REGEXES.ipv4.is_match(x) || REGEXES.domain.is_match(x)])
+                    .build(ui, &mut self.remote.host);
+                valid &= StateTextEdit::new(ui)
+                    .description("Port ")
+                    .max_ch(5)
+                    .help_msg(STATUS_FLEET_PORT)
+                    .validations(&[|x| REGEXES.port.is_match(x)])
+                    .build(ui, &mut self.remote.port);
+                StateTextEdit::new(ui)
+                    .description("Token ")
+                    .max_ch(255)
+                    .help_msg(STATUS_FLEET_TOKEN)
+                    .build(ui, &mut self.remote.token);
+                let exists = self.remotes.iter().any(|r| r.name == self.remote.name);
+                if ui
+                    .add_enabled(valid && !exists, Button::new("Add"))
+                    .on_hover_text(STATUS_FLEET_ADD)
+                    .clicked()
+                {
+                    self.remotes.push(self.remote.clone());
+                    self.remote = RemoteInstance::default();
+                }
+            });
+        });
+        if self.remotes.is_empty() {
+            ui.vertical_centered(|ui| {
+                ui.label("No remote instance, add one above to monitor it.");
+            });
+            return;
+        }
+        let text = ui.text_style_height(&egui::TextStyle::Body);
+        let double = text * 2.0;
+        let fleet = fleet_api.lock().unwrap();
+        let mut delete = None;
+        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+        ScrollArea::horizontal().show(ui, |ui| {
+            TableBuilder::new(ui)
+                .columns(Column::auto(), 6)
+                .header(double, |mut header| {
+                    for title in ["Name", "Software", "Address", "Status", "Stats", ""] {
+                        header.col(|ui| {
+                            ui.label(RichText::new(title).underline().color(BONE));
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(text, self.remotes.len(), |mut row| {
+                        let i = row.index();
+                        let remote = &self.remotes[i];
+                        let stats = fleet.find(remote);
+                        row.col(|ui| {
+                            ui.label(&remote.name);
+                        });
+                        row.col(|ui| {
+                            ui.label(remote.kind.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{}:{}", remote.host, remote.port));
+                        });
+                        row.col(|ui| match stats.map(|s| &s.error) {
+                            None => {
+                                ui.label(RichText::new("Waiting").color(LIGHT_GRAY));
+                            }
+                            Some(Some(e)) => {
+                                ui.label(RichText::new("Offline").color(RED))
+                                    .on_hover_text(e);
+                            }
+                            Some(None) => {
+                                ui.label(RichText::new("Online").color(GREEN));
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.add(Label::new(stats.map(fleet_stats).unwrap_or_default()));
+                        });
+                        row.col(|ui| {
+                            if ui
+                                .button("Delete")
+                                .on_hover_text(STATUS_FLEET_DELETE)
+                                .clicked()
+                            {
+                                delete = Some(i);
+                            }
+                        });
+                    });
+                });
+        });
+        if let Some(i) = delete {
+            self.remotes.remove(i);
+        }
+    }
+}
+
+// summary of the stats of an instance that responded
+fn fleet_stats(stats: &RemoteStats) -> String {
+    if stats.error.is_some() {
+        return String::new();
+    }
+    match &stats.api {
+        RemoteApi::Xmrig(api) => format!(
+            "Hashrate: {} | Accepted: {} | Rejected: {}",
+            api.hashrate, api.accepted, api.rejected
+        ),
+        RemoteApi::XmrigProxy(api) => format!(
+            "Hashrate: {} | Miners: {} | Accepted: {} | Rejected: {}",
+            api.hashrate, api.miners, api.accepted, api.rejected
+        ),
+        RemoteApi::Node(api) => format!(
+            "Height: {} | Synchronized: {} | Peers: {} out, {} in",
+            api.blockheight, api.synchronized, api.outgoing_connections, api.incoming_connections
+        ),
+    }
+}
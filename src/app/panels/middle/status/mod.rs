@@ -20,20 +20,25 @@ use crate::{
     disk::{gupax_p2pool_api::GupaxP2poolApi, state::Status, status::*},
     helper::{
         ProcessName, ProcessState, Sys,
+        fleet::PubFleetApi,
         node::PubNodeApi,
         p2pool::{ImgP2pool, PubP2poolApi},
         xrig::{
+            bench::PubBenchApi,
             xmrig::{ImgXmrig, PubXmrigApi},
             xmrig_proxy::PubXmrigProxyApi,
         },
         xvb::PubXvbApi,
     },
 };
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 mod benchmarks;
+mod fleet;
 mod p2pool;
 mod processes;
+mod threads;
 
 impl Status {
     #[inline(always)] // called once
@@ -53,6 +58,9 @@ impl Status {
         max_threads: u16,
         gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
         benchmarks: &[Benchmark],
+        bench_api: &Arc<Mutex<PubBenchApi>>,
+        xmrig_path: &Path,
+        fleet_api: &Arc<Mutex<PubFleetApi>>,
         _ctx: &egui::Context,
         ui: &mut egui::Ui,
     ) {
@@ -87,7 +95,16 @@ impl Status {
                 benchmarks,
                 states.is_alive(ProcessName::Xmrig),
                 xmrig_api,
+                bench_api,
+                xmrig_path,
+                max_threads,
             )
+        //---------------------------------------------------------------------------------------------------- [Threads]
+        } else if self.submenu == Submenu::Threads {
+            self.threads(ui, states.is_alive(ProcessName::Xmrig), xmrig_api);
+        //---------------------------------------------------------------------------------------------------- [Fleet]
+        } else if self.submenu == Submenu::Fleet {
+            self.fleet(ui, fleet_api);
         }
     }
 }
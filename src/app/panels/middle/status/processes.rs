@@ -148,6 +148,13 @@ fn gupax(ui: &mut Ui, sys: &Arc<Mutex<Sys>>) {
     ui.label(RichText::new("System CPU").underline().color(BONE))
         .on_hover_text(STATUS_GUPAX_SYSTEM_CPU_USAGE);
     ui.label(sys.system_cpu_usage.to_string());
+    ui.label(
+        RichText::new("System CPU Temperature")
+            .underline()
+            .color(BONE),
+    )
+    .on_hover_text(STATUS_GUPAX_SYSTEM_CPU_TEMP);
+    ui.label(sys.system_cpu_temp.to_string());
     ui.label(RichText::new("System Memory").underline().color(BONE))
         .on_hover_text(STATUS_GUPAX_SYSTEM_MEMORY);
     ui.label(sys.system_memory.to_string());
@@ -283,6 +290,9 @@ fn xmrig_proxy(
         ui.label(RichText::new("Miners Connected").underline().color(BONE))
             .on_hover_text(STATUS_PROXY_CONNECTIONS);
         ui.label(api.miners.to_string());
+        ui.label(RichText::new("Workers").underline().color(BONE))
+            .on_hover_text(STATUS_XMRIG_PROXY_WORKERS);
+        ui.label(api.workers_summary());
 
         ui.label(RichText::new("Pool").underline().color(BONE))
             .on_hover_text(STATUS_XMRIG_PROXY_POOL);
@@ -474,6 +484,9 @@ fn node(ui: &mut Ui, node_alive: bool, node_api: &Arc<Mutex<PubNodeApi>>) {
         ui.label(RichText::new("Incoming peers").underline().color(BONE))
             .on_hover_text(STATUS_NODE_IN);
         ui.label(api.incoming_connections.to_string());
+        ui.label(RichText::new("Anonymous peers").underline().color(BONE))
+            .on_hover_text(STATUS_NODE_ANONYMOUS);
+        ui.label(api.anonymous_connections.to_string());
         ui.label(RichText::new("Synchronized").underline().color(BONE))
             .on_hover_text(STATUS_NODE_SYNC);
         ui.label(api.synchronized.to_string());
@@ -0,0 +1,178 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex};
+
+use egui::{RichText, ScrollArea, TextWrapMode, Ui};
+use egui_extras::{Column, TableBuilder};
+use log::*;
+
+use crate::{
+    constants::*,
+    disk::state::Status,
+    helper::xrig::xmrig::{PubXmrigApi, ThreadStatus, XmrigBackend},
+    utils::topology::CPU_TOPOLOGY,
+};
+
+impl Status {
+    pub(super) fn threads(
+        &mut self,
+        ui: &mut Ui,
+        xmrig_alive: bool,
+        xmrig_api: &Arc<Mutex<PubXmrigApi>>,
+    ) {
+        debug!("Status Tab | Rendering [Threads]");
+        let api = xmrig_api.lock().unwrap();
+        let backends = api
+            .backends
+            .iter()
+            .filter(|b| b.enabled)
+            .collect::<Vec<_>>();
+        if !xmrig_alive || backends.is_empty() {
+            ui.vertical_centered(|ui| {
+                if xmrig_alive {
+                    ui.label("Waiting for the backends API of XMRig...");
+                    ui.spinner();
+                } else {
+                    ui.label("XMRig is offline. The threads cannot be shown.");
+                }
+            });
+            return;
+        }
+        let dataset = match &api.hugepages.dataset {
+            Some(a) => format!("huge pages {}% ({}/{})", a.percent, a.allocated, a.total),
+            None => format!("huge pages {UNKNOWN_DATA}"),
+        };
+        let randomx = format!(
+            "RandomX mode: {} | Dataset: {}",
+            api.randomx_mode.as_deref().unwrap_or(UNKNOWN_DATA),
+            dataset
+        );
+        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+        ScrollArea::vertical().show(ui, |ui| {
+            for (index, backend) in backends.into_iter().enumerate() {
+                ui.group(|ui| {
+                    backend_summary(ui, backend, &randomx);
+                    ui.separator();
+                    thread_table(ui, index, backend);
+                });
+            }
+        });
+    }
+}
+
+fn backend_summary(ui: &mut Ui, backend: &XmrigBackend, randomx: &str) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label(
+            RichText::new(backend.kind.to_uppercase())
+                .underline()
+                .color(BONE),
+        );
+        ui.label(format!(
+            "{} | profile {} | {} MB | {}",
+            backend.algo,
+            backend.profile,
+            backend.memory_mb,
+            hashrates(&backend.hashrate)
+        ))
+        .on_hover_text(STATUS_THREADS_BACKEND);
+        ui.label(format!("| huge pages {}", backend.hugepages))
+            .on_hover_text(STATUS_THREADS_HUGEPAGES);
+        if backend.algo.starts_with("rx") {
+            ui.label(format!("| {randomx}"))
+                .on_hover_text(STATUS_THREADS_DATASET);
+        }
+    });
+    let issues = backend.threads.iter().filter(|t| !t.status.is_ok()).count();
+    if issues > 0 {
+        ui.label(
+            RichText::new(format!(
+                "{issues} thread(s) out of {} need attention",
+                backend.threads.len()
+            ))
+            .color(ORANGE),
+        );
+    }
+}
+
+fn thread_table(ui: &mut Ui, index: usize, backend: &XmrigBackend) {
+    let text = ui.text_style_height(&egui::TextStyle::Body);
+    ui.push_id(index, |ui| {
+        TableBuilder::new(ui)
+            .columns(Column::auto(), 4)
+            .header(text * 1.5, |mut header| {
+                for (title, hover) in [
+                    ("Thread", STATUS_THREADS_INDEX),
+                    ("Affinity", STATUS_THREADS_AFFINITY),
+                    ("Hashrate", STATUS_THREADS_HASHRATE),
+                    ("Status", STATUS_THREADS_STATUS),
+                ] {
+                    header.col(|ui| {
+                        ui.label(RichText::new(title).underline().color(BONE))
+                            .on_hover_text(hover);
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(text, backend.threads.len(), |mut row| {
+                    let i = row.index();
+                    let thread = &backend.threads[i];
+                    row.col(|ui| {
+                        ui.label(i.to_string());
+                    });
+                    row.col(|ui| {
+                        ui.label(affinity(thread.affinity));
+                    });
+                    row.col(|ui| {
+                        ui.label(hashrates(&thread.hashrate));
+                    });
+                    row.col(|ui| {
+                        let color = match thread.status {
+                            ThreadStatus::Ok => GREEN,
+                            ThreadStatus::Stalled => RED,
+                            _ => ORANGE,
+                        };
+                        ui.label(RichText::new(thread.status.to_string()).color(color));
+                    });
+                });
+            });
+    });
+}
+
+// "CPU 3 (core 1, node 0)"
+fn affinity(cpu: Option<u16>) -> String {
+    let Some(cpu) = cpu else {
+        return "Not pinned".to_string();
+    };
+    match CPU_TOPOLOGY.cpus.iter().find(|c| c.id == cpu) {
+        // core ids of other packages are offset, only the id inside the package is shown
+        Some(c) => format!("CPU {cpu} (core {}, node {})", c.core & 0xffff, c.node),
+        None => format!("CPU {cpu}"),
+    }
+}
+
+// "[1234.5 H/s] [1200.1 H/s] [??? H/s]"
+fn hashrates(hashrate: &[Option<f32>; 3]) -> String {
+    hashrate
+        .iter()
+        .map(|h| match h {
+            Some(h) => format!("[{h:.1} H/s]"),
+            None => format!("[{UNKNOWN_DATA} H/s]"),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -21,14 +21,21 @@ use crate::app::panels::middle::common::list_poolnode::list_poolnode;
 use crate::app::panels::middle::common::state_edit_field::{
     monero_address_field, slider_state_field,
 };
+use crate::components::stratum::{Probes, StratumProbe};
 use crate::constants::*;
-use crate::disk::state::{StartOptionsMode, Xmrig};
+use crate::disk::state::{FailoverPool, RandomxMode, StartOptionsMode, Xmrig};
 use crate::helper::xrig::xmrig::PubXmrigApi;
-use crate::helper::{Process, ProcessName};
+use crate::helper::{Process, ProcessName, Sys};
 use crate::miscs::height_txt_before_button;
 use crate::regex::REGEXES;
-use egui::{Checkbox, Image, Ui, vec2};
+#[cfg(target_os = "linux")]
+use crate::utils::hugepages::{CPU_LAYOUT, HugepagesPlan, MemoryState};
+use crate::utils::topology::{CPU_TOPOLOGY, MAX_AFFINITY_CPU};
+use egui::{
+    Button, Checkbox, ComboBox, Grid, Image, RichText, SelectableLabel, TextEdit, Ui, vec2,
+};
 use log::*;
+use strum::IntoEnumIterator;
 
 use std::sync::{Arc, Mutex};
 
@@ -43,10 +50,12 @@ impl Xmrig {
         pool_vec: &mut Vec<(String, PoolNode)>,
         process: &Arc<Mutex<Process>>,
         api: &Arc<Mutex<PubXmrigApi>>,
+        probes: &Arc<Mutex<Probes>>,
         buffer: &mut String,
         _ctx: &egui::Context,
         ui: &mut egui::Ui,
         p2pool_stratum_port: u16,
+        sys: &Arc<Mutex<Sys>>,
     ) {
         let logo = Some(Image::from_bytes("bytes:/xmrig.png", BYTES_XMRIG));
         header_tab(
@@ -86,6 +95,12 @@ impl Xmrig {
                     Self::process_name().start_options_hint(),
                     START_OPTIONS_HOVER,
                 );
+                ui.checkbox(&mut self.attach, "Attach to an external instance")
+                    .on_hover_text(XMRIG_ATTACH);
+                ui.add_enabled_ui(self.arguments.is_empty() && !self.attach, |ui| {
+                    ui.checkbox(&mut self.config_file, "Use a config file")
+                        .on_hover_text(XMRIG_CONFIG_FILE);
+                });
                 ui.add_enabled_ui(self.arguments.is_empty(), |ui| {
                     debug!("XMRig Tab | Rendering [Address]");
                     monero_address_field(&mut self.address, ui, XMRIG_ADDRESS);
@@ -97,13 +112,17 @@ impl Xmrig {
             debug!("XMRig Tab | Rendering [Threads]");
             ui.vertical_centered(|ui| {
                 ui.set_max_width(ui.available_width() * 0.75);
-                slider_state_field(
-                    ui,
-                    &format!("Threads [1-{}]:", self.max_threads),
-                    XMRIG_THREADS,
-                    &mut self.current_threads,
-                    1..=self.max_threads,
-                );
+                // the thread layout decides the threads if it pins them
+                let pinned = !self.simple && self.layout && !self.cores.is_empty();
+                ui.add_enabled_ui(!pinned, |ui| {
+                    slider_state_field(
+                        ui,
+                        &format!("Threads [1-{}]:", self.max_threads),
+                        XMRIG_THREADS,
+                        &mut self.current_threads,
+                        1..=self.max_threads,
+                    );
+                });
                 #[cfg(not(target_os = "linux"))] // Pause on active isn't supported on Linux
                 slider_state_field(
                     ui,
@@ -113,6 +132,22 @@ impl Xmrig {
                     0..=255,
                 );
             });
+            #[cfg(target_os = "linux")]
+            {
+                debug!("XMRig Tab | Rendering [Huge pages]");
+                ui.group(|ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.label(RichText::new("Huge pages").underline().color(LIGHT_GRAY))
+                            .on_hover_text(XMRIG_HUGEPAGES);
+                    });
+                    ui.separator();
+                    self.hugepages(ui, api);
+                });
+            }
+            debug!("XMRig Tab | Rendering [Temperature throttling]");
+            ui.group(|ui| {
+                self.throttle(ui, sys);
+            });
             if !self.simple {
                 if !self.arguments.is_empty() {
                     ui.disable();
@@ -155,6 +190,11 @@ impl Xmrig {
                             });
                         });
                         ui.add_space(5.0);
+                        debug!("XMRig Tab | Rendering [Failover] pools");
+                        ui.group(|ui| {
+                            self.failover(ui, api, probes);
+                        });
+                        ui.add_space(5.0);
                         debug!("XMRig Tab | Rendering [API] TextEdits");
                         // [HTTP API IP/Port]
                         ui.group(|ui| {
@@ -190,8 +230,356 @@ impl Xmrig {
                             });
                         });
                     });
+                debug!("XMRig Tab | Rendering [Thread layout]");
+                ui.group(|ui| {
+                    self.thread_layout(ui);
+                });
+            }
+        });
+    }
+    // ordered list of the failover pools, the pool in use is highlighted.
+    // The pools can be probed over stratum and sorted by the time to get a job.
+    fn failover(
+        &mut self,
+        ui: &mut Ui,
+        api: &Arc<Mutex<PubXmrigApi>>,
+        probes: &Arc<Mutex<Probes>>,
+    ) {
+        let active = api
+            .lock()
+            .unwrap()
+            .pool
+            .as_ref()
+            .map(|p| format!("{}:{}", p.url(), p.port()));
+        let primary = FailoverPool {
+            name: self.selected_pool.name.clone(),
+            ip: self.ip.clone(),
+            port: self.port.clone(),
+            user: String::new(),
+            rig: self.rig.clone(),
+            tls: self.tls,
+            keepalive: self.keepalive,
+        };
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new("Failover pools")
+                    .underline()
+                    .color(LIGHT_GRAY),
+            )
+            .on_hover_text(XMRIG_FAILOVER);
+            if ui
+                .button("Add the selected pool")
+                .on_hover_text(XMRIG_FAILOVER_ADD)
+                .clicked()
+            {
+                self.failover.push(FailoverPool {
+                    name: self.name.clone(),
+                    ..primary.clone()
+                });
+            }
+            ui.separator();
+            let probing = probes.lock().unwrap().probing;
+            if ui
+                .add_enabled(!probing, Button::new("Probe pools"))
+                .on_hover_text(XMRIG_FAILOVER_PROBE)
+                .clicked()
+            {
+                let pools = std::iter::once(&primary)
+                    .chain(self.failover.iter())
+                    .map(|p| {
+                        let (ip, port) = p
+                            .url()
+                            .rsplit_once(':')
+                            .map(|(i, p)| (i.to_string(), p.to_string()))
+                            .unwrap_or_default();
+                        (ip, port, p.tls)
+                    })
+                    .collect();
+                Probes::spawn_thread(probes, pools);
+            }
+            if ui
+                .add_enabled(!probing, Button::new("Sort by latency"))
+                .on_hover_text(XMRIG_FAILOVER_SORT)
+                .clicked()
+            {
+                let probes = probes.lock().unwrap();
+                // stable sort, pools not probed or unreachable are kept last in their order
+                self.failover.sort_by_key(|p| {
+                    probes
+                        .get(&p.url())
+                        .and_then(|r| r.as_ref().ok())
+                        .map_or(u128::MAX, |r| r.score())
+                });
             }
         });
+        let probes = probes.lock().unwrap();
+        let probe = |url: &str| match probes.get(url) {
+            Some(r @ Ok(_)) => RichText::new(StratumProbe::summary(r)).color(GREEN),
+            Some(r @ Err(_)) => RichText::new(StratumProbe::summary(r)).color(RED),
+            None => RichText::new("not probed").color(LIGHT_GRAY),
+        };
+        let color = |url: &str| {
+            if active.as_deref() == Some(url) {
+                GREEN
+            } else {
+                LIGHT_GRAY
+            }
+        };
+        let mut action = None;
+        Grid::new("xmrig_failover").striped(true).show(ui, |ui| {
+            ui.label(RichText::new("Primary").color(color(&primary.url())));
+            ui.label(&primary.name);
+            ui.label(primary.url());
+            ui.label(probe(&primary.url()))
+                .on_hover_text(XMRIG_FAILOVER_PROBE_RESULT);
+            ui.end_row();
+            let len = self.failover.len();
+            for (i, pool) in self.failover.iter_mut().enumerate() {
+                ui.label(RichText::new(format!("{}", i + 1)).color(color(&pool.url())));
+                ui.label(&pool.name);
+                ui.label(pool.url());
+                ui.label(probe(&pool.url()))
+                    .on_hover_text(XMRIG_FAILOVER_PROBE_RESULT);
+                ui.add(
+                    TextEdit::singleline(&mut pool.user)
+                        .hint_text("Payout address")
+                        .desired_width(150.0),
+                )
+                .on_hover_text(XMRIG_FAILOVER_USER);
+                ui.checkbox(&mut pool.tls, "TLS").on_hover_text(XMRIG_TLS);
+                ui.checkbox(&mut pool.keepalive, "Keepalive")
+                    .on_hover_text(XMRIG_KEEPALIVE);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(i > 0, Button::new("⬆"))
+                        .on_hover_text(XMRIG_FAILOVER_UP)
+                        .clicked()
+                    {
+                        action = Some((i, i - 1));
+                    }
+                    if ui
+                        .add_enabled(i + 1 < len, Button::new("⬇"))
+                        .on_hover_text(XMRIG_FAILOVER_DOWN)
+                        .clicked()
+                    {
+                        action = Some((i, i + 1));
+                    }
+                    if ui
+                        .button("Remove")
+                        .on_hover_text(XMRIG_FAILOVER_REMOVE)
+                        .clicked()
+                    {
+                        action = Some((i, i));
+                    }
+                });
+                ui.end_row();
+            }
+        });
+        match action {
+            Some((i, j)) if i == j => {
+                self.failover.remove(i);
+            }
+            Some((i, j)) => self.failover.swap(i, j),
+            None => (),
+        }
+    }
+    // CPUs grouped by NUMA node and L3 cache, priority and RandomX mode.
+    fn thread_layout(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.layout, "Thread layout")
+            .on_hover_text(XMRIG_LAYOUT);
+        if !self.layout {
+            return;
+        }
+        ui.separator();
+        let topology = &*CPU_TOPOLOGY;
+        ui.horizontal_wrapped(|ui| {
+            if ui.button("All").on_hover_text(XMRIG_LAYOUT_ALL).clicked() {
+                self.cores = topology
+                    .cpus
+                    .iter()
+                    .map(|c| c.id)
+                    .filter(|c| *c < MAX_AFFINITY_CPU)
+                    .collect();
+            }
+            if ui.button("None").on_hover_text(XMRIG_LAYOUT_NONE).clicked() {
+                self.cores.clear();
+            }
+            if ui
+                .button("L3 cache aware")
+                .on_hover_text(XMRIG_LAYOUT_L3)
+                .clicked()
+            {
+                self.cores = topology.l3_aware_cpus();
+            }
+            let nodes = topology.nodes();
+            if nodes.len() > 1 {
+                for node in nodes {
+                    if ui
+                        .button(format!("NUMA node {node}"))
+                        .on_hover_text(XMRIG_LAYOUT_NODE)
+                        .clicked()
+                    {
+                        self.cores = topology.node_cpus(node);
+                    }
+                }
+            }
+        });
+        for (index, cache) in topology.l3_caches.iter().enumerate() {
+            let cpus = topology
+                .cpus
+                .iter()
+                .filter(|c| c.l3 == index)
+                .collect::<Vec<_>>();
+            let Some(node) = cpus.first().map(|c| c.node) else {
+                continue;
+            };
+            ui.horizontal_wrapped(|ui| {
+                if cache.size_kb == 0 {
+                    ui.label(format!("Node {node}:"));
+                } else {
+                    ui.label(format!("Node {node} | L3 {} MB:", cache.size_kb / 1024));
+                }
+                for cpu in cpus {
+                    let selected = self.cores.contains(&cpu.id);
+                    if ui
+                        .add_enabled(
+                            cpu.id < MAX_AFFINITY_CPU,
+                            SelectableLabel::new(selected, cpu.id.to_string()),
+                        )
+                        .on_disabled_hover_text(XMRIG_LAYOUT_MAX_CPU)
+                        .clicked()
+                    {
+                        if selected {
+                            self.cores.retain(|c| *c != cpu.id);
+                        } else {
+                            self.cores.push(cpu.id);
+                            self.cores.sort_unstable();
+                        }
+                    }
+                }
+            });
+        }
+        slider_state_field(
+            ui,
+            "CPU priority [0-5]:",
+            XMRIG_CPU_PRIORITY,
+            &mut self.cpu_priority,
+            0..=5,
+        );
+        ui.horizontal(|ui| {
+            ui.label("RandomX mode:");
+            ComboBox::from_id_salt("randomx_mode")
+                .selected_text(self.randomx_mode.to_string())
+                .show_ui(ui, |ui| {
+                    for mode in RandomxMode::iter() {
+                        ui.selectable_value(&mut self.randomx_mode, mode, mode.to_string());
+                    }
+                })
+                .response
+                .on_hover_text(XMRIG_RANDOMX_MODE);
+        });
+        // effective arguments of the layout
+        ui.label(
+            RichText::new(format!(
+                "--threads {} {}",
+                self.threads(),
+                self.layout_args().join(" ")
+            ))
+            .color(LIGHT_GRAY),
+        );
+    }
+    // thresholds of the temperature throttling, applied at the next start of XMRig.
+    fn throttle(&mut self, ui: &mut Ui, sys: &Arc<Mutex<Sys>>) {
+        ui.checkbox(&mut self.throttle, "Temperature throttling")
+            .on_hover_text(XMRIG_THROTTLE);
+        if !self.throttle {
+            return;
+        }
+        ui.separator();
+        let sys = sys.lock().unwrap();
+        match sys.cpu_temp {
+            Some(_) => {
+                ui.label(format!("CPU temperature: {}", sys.system_cpu_temp));
+            }
+            None => {
+                ui.label(RichText::new(XMRIG_THROTTLE_NO_SENSOR).color(ORANGE));
+            }
+        }
+        drop(sys);
+        ui.vertical_centered(|ui| {
+            ui.set_max_width(ui.available_width() * 0.75);
+            slider_state_field(
+                ui,
+                "Soft threshold [40-100]°C:",
+                XMRIG_TEMP_SOFT,
+                &mut self.temp_soft,
+                40..=100,
+            );
+            // the hard threshold is always above the soft one
+            slider_state_field(
+                ui,
+                &format!("Hard threshold [{}-105]°C:", self.temp_soft + 1),
+                XMRIG_TEMP_HARD,
+                &mut self.temp_hard,
+                self.temp_soft + 1..=105,
+            );
+            self.temp_hard = self.temp_hard.max(self.temp_soft + 1);
+            slider_state_field(
+                ui,
+                "Cooldown [10-600] seconds:",
+                XMRIG_THROTTLE_COOLDOWN,
+                &mut self.throttle_cooldown,
+                10..=600,
+            );
+        });
+    }
+    // layout of the memory, pages needed and what XMRig reported at startup.
+    #[cfg(target_os = "linux")]
+    fn hugepages(&self, ui: &mut Ui, api: &Arc<Mutex<PubXmrigApi>>) {
+        let report = api.lock().unwrap().hugepages.clone();
+        let memory = MemoryState::detect();
+        // a Node started by Gupaxx also uses huge pages for its RandomX caches.
+        let plan = HugepagesPlan::new(&CPU_LAYOUT, &memory, self.current_threads as u64, true);
+        ui.label(format!(
+            "1GB pages supported by the CPU: {} | NUMA nodes: {}",
+            CPU_LAYOUT.pdpe1gb, CPU_LAYOUT.numa_nodes
+        ));
+        let color = if plan.applied(&memory) { GREEN } else { RED };
+        ui.label(
+            RichText::new(format!(
+                "Reserved: {} x 2MB, {} x 1GB | Needed: {} x 2MB, {} x 1GB",
+                memory.pages_2mb, memory.pages_1gb, plan.pages_2mb, plan.pages_1gb
+            ))
+            .color(color),
+        );
+        if report.is_empty() {
+            ui.label(
+                RichText::new("Start XMRig to verify the allocation of the huge pages")
+                    .color(LIGHT_GRAY),
+            );
+        } else {
+            ui.horizontal_wrapped(|ui| {
+                for (name, allocation) in [("Dataset", report.dataset), ("Threads", report.threads)]
+                {
+                    if let Some(a) = allocation {
+                        let color = if a.complete() { GREEN } else { RED };
+                        ui.label(
+                            RichText::new(format!(
+                                "{name}: {}% {}/{}",
+                                a.percent, a.allocated, a.total
+                            ))
+                            .color(color),
+                        );
+                    }
+                }
+                if let Some(pages_1gb) = &report.pages_1gb {
+                    ui.label(format!("1GB pages: {pages_1gb}"));
+                }
+            });
+        }
+        if let Some(remediation) = report.remediation(&plan, &memory) {
+            ui.label(RichText::new(remediation).color(ORANGE));
+        }
     }
     fn name_field(&mut self, ui: &mut Ui) -> bool {
         StateTextEdit::new(ui)
@@ -15,7 +15,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use egui::{Checkbox, TextStyle, Ui, vec2};
+use egui::{Checkbox, RichText, TextStyle, Ui, vec2};
+use egui_extras::{Column, TableBuilder};
 use std::net::{IpAddr, Ipv4Addr};
 use std::sync::{Arc, Mutex};
 
@@ -25,15 +26,19 @@ use crate::app::panels::middle::common::console::{console, input_args_field, sta
 use crate::app::panels::middle::common::header_tab::header_tab;
 use crate::app::panels::middle::common::list_poolnode::list_poolnode;
 use crate::disk::state::{StartOptionsMode, XmrigProxy};
-use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
+use crate::helper::xrig::xmrig_proxy::{ProxyWorker, PubXmrigProxyApi, WorkerStatus};
 use crate::helper::{Helper, Process, ProcessName};
+use crate::human::{HumanNumber, HumanTime};
 use crate::miscs::height_txt_before_button;
 use crate::regex::REGEXES;
-use crate::utils::constants::IP_NOT_FOUND;
+use crate::utils::constants::{
+    BONE, GREEN, IP_NOT_FOUND, LIGHT_GRAY, ORANGE, RED, XMRIG_PROXY_WORKERS,
+    XMRIG_PROXY_WORKERS_HASHRATE, XMRIG_PROXY_WORKERS_LAST_SHARE, XMRIG_PROXY_WORKERS_SHARES,
+};
 use crate::{
     SPACE, START_OPTIONS_HOVER, XMRIG_API_IP, XMRIG_API_PORT, XMRIG_IP, XMRIG_KEEPALIVE,
-    XMRIG_NAME, XMRIG_PORT, XMRIG_PROXY_INPUT, XMRIG_PROXY_REDIRECT, XMRIG_PROXY_URL, XMRIG_RIG,
-    XMRIG_TLS,
+    XMRIG_NAME, XMRIG_PORT, XMRIG_PROXY_ATTACH, XMRIG_PROXY_INPUT, XMRIG_PROXY_REDIRECT,
+    XMRIG_PROXY_URL, XMRIG_RIG, XMRIG_TLS,
 };
 
 use super::common::list_poolnode::PoolNode;
@@ -81,6 +86,14 @@ impl XmrigProxy {
                     );
                 }
             });
+            //---------------------------------------------------------------------------------------------------- Workers
+            debug!("XMRig-Proxy Tab | Rendering [Workers]");
+            ui.add_space(SPACE);
+            ui.group(|ui| {
+                ui.label(RichText::new("Workers").underline().color(BONE))
+                    .on_hover_text(XMRIG_PROXY_WORKERS);
+                workers(ui, &api.lock().unwrap().workers);
+            });
             if !self.simple {
                 //---------------------------------------------------------------------------------------------------- Arguments
                 debug!("XMRig-Proxy Tab | Rendering [Arguments]");
@@ -96,6 +109,9 @@ impl XmrigProxy {
                     Self::process_name().start_options_hint(),
                     START_OPTIONS_HOVER,
                 );
+                ui.add_space(SPACE);
+                ui.checkbox(&mut self.attach, "Attach to an external instance")
+                    .on_hover_text(XMRIG_PROXY_ATTACH);
                 if !self.arguments.is_empty() {
                     ui.disable();
                 }
@@ -319,3 +335,80 @@ impl XmrigProxy {
         valid
     }
 }
+
+fn workers(ui: &mut Ui, workers: &[ProxyWorker]) {
+    if workers.is_empty() {
+        ui.label("No worker is connected to XMRig-Proxy");
+        return;
+    }
+    let text = ui.text_style_height(&TextStyle::Body);
+    egui::ScrollArea::horizontal()
+        .id_salt("proxy_workers")
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .columns(Column::auto(), 6)
+                .header(text * 1.5, |mut header| {
+                    for (title, hover) in [
+                        ("Name", ""),
+                        ("IP", ""),
+                        ("Hashrate (1m/10m/1h)", XMRIG_PROXY_WORKERS_HASHRATE),
+                        ("Accepted/Invalid", XMRIG_PROXY_WORKERS_SHARES),
+                        ("Last share", XMRIG_PROXY_WORKERS_LAST_SHARE),
+                        ("Status", ""),
+                    ] {
+                        header.col(|ui| {
+                            let label = ui.label(RichText::new(title).underline().color(BONE));
+                            if !hover.is_empty() {
+                                label.on_hover_text(hover);
+                            }
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(text, workers.len(), |mut row| {
+                        let worker = &workers[row.index()];
+                        row.col(|ui| {
+                            let label = ui.label(&worker.name);
+                            if let Some(agent) = &worker.agent {
+                                label.on_hover_text(agent);
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(&worker.ip);
+                        });
+                        row.col(|ui| {
+                            let hashrate = worker.hashrate[..3]
+                                .iter()
+                                .map(|h| Some(*h as u64))
+                                .collect::<Vec<_>>();
+                            ui.label(
+                                HumanNumber::from_hashrate(&hashrate)
+                                    .to_string()
+                                    .replace('\n', " "),
+                            );
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{}/{}", worker.accepted, worker.invalid));
+                        });
+                        row.col(|ui| {
+                            ui.label(match worker.last_seen {
+                                Some(secs) => {
+                                    HumanTime::into_human(std::time::Duration::from_secs(secs))
+                                        .display(false)
+                                }
+                                None => "Never".to_string(),
+                            });
+                        });
+                        row.col(|ui| {
+                            let color = match worker.status {
+                                WorkerStatus::Ok => GREEN,
+                                WorkerStatus::Slow => ORANGE,
+                                WorkerStatus::Missing => RED,
+                                WorkerStatus::Offline => LIGHT_GRAY,
+                            };
+                            ui.label(RichText::new(worker.status.to_string()).color(color));
+                        });
+                    });
+                });
+        });
+}
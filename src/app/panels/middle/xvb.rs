@@ -17,7 +17,8 @@
 
 use std::sync::{Arc, Mutex};
 
-use egui::{Align, Image, Label, RichText, ScrollArea, TextStyle, Ui};
+use egui::{Align, ComboBox, Image, RichText, ScrollArea, TextStyle, Ui};
+use egui_extras::{Column, TableBuilder};
 use log::debug;
 use readable::num::Float;
 use readable::up::Uptime;
@@ -26,20 +27,27 @@ use strum::EnumCount;
 use crate::app::panels::middle::common::console::console;
 use crate::app::panels::middle::common::header_tab::header_tab;
 use crate::app::panels::middle::common::state_edit_field::StateTextEdit;
-use crate::app::panels::middle::common::toggle::toggle_ui_compact;
+use crate::components::stratum::ProbeResult;
 use crate::disk::state::{ManualDonationLevel, ManualDonationMetric, XvbMode};
+use crate::disk::status::RemoteInstance;
+use crate::disk::xvb_history::XvbHistory;
 use crate::helper::ProcessName;
 use crate::helper::xrig::xmrig::PubXmrigApi;
 use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
 use crate::helper::xvb::PubXvbApi;
+use crate::helper::xvb::endpoints::XvbEndpoints;
+use crate::helper::xvb::miners::is_controllable;
+use crate::helper::xvb::planner::plan_tiers;
 use crate::helper::xvb::priv_stats::RuntimeMode;
 use crate::miscs::height_txt_before_button;
 use crate::utils::constants::{
-    ORANGE, XVB_DONATED_1H_FIELD, XVB_DONATED_24H_FIELD, XVB_DONATION_LEVEL_DONOR_HELP,
-    XVB_DONATION_LEVEL_MEGA_DONOR_HELP, XVB_DONATION_LEVEL_VIP_DONOR_HELP,
-    XVB_DONATION_LEVEL_WHALE_DONOR_HELP, XVB_FAILURE_FIELD, XVB_HELP, XVB_HERO_SELECT,
+    BONE, GREEN, ORANGE, RED, XVB_CONTROLLED_MINER, XVB_CONTROLLED_MINERS, XVB_DONATED_1H_FIELD,
+    XVB_DONATED_24H_FIELD, XVB_DONATION_LEVEL_DONOR_HELP, XVB_DONATION_LEVEL_MEGA_DONOR_HELP,
+    XVB_DONATION_LEVEL_VIP_DONOR_HELP, XVB_DONATION_LEVEL_WHALE_DONOR_HELP, XVB_FAILURE_FIELD,
+    XVB_HELP, XVB_HERO_SELECT, XVB_HISTORY_HELP, XVB_HISTORY_LAST_ROUNDS, XVB_HISTORY_ROUNDS,
     XVB_MANUAL_POOL, XVB_MANUAL_SLIDER_MANUAL_P2POOL_HELP, XVB_MANUAL_SLIDER_MANUAL_XVB_HELP,
     XVB_MODE_MANUAL_DONATION_LEVEL_HELP, XVB_MODE_MANUAL_P2POOL_HELP, XVB_MODE_MANUAL_XVB_HELP,
+    XVB_PLANNER_COLUMNS, XVB_PLANNER_HELP, XVB_PROBES_COLUMNS, XVB_PROBES_HELP,
     XVB_ROUND_TYPE_FIELD, XVB_TOKEN_LEN, XVB_URL_RULES, XVB_WINNER_FIELD,
 };
 use crate::utils::regex::Regexes;
@@ -60,6 +68,9 @@ impl crate::disk::state::Xvb {
         api: &Arc<Mutex<PubXvbApi>>,
         gui_api_xmrig: &Arc<Mutex<PubXmrigApi>>,
         gui_api_xp: &Arc<Mutex<PubXmrigProxyApi>>,
+        history: &Arc<Mutex<XvbHistory>>,
+        endpoints: &Arc<Mutex<XvbEndpoints>>,
+        remotes: &[RemoteInstance],
         is_alive: bool,
     ) {
         // logo and website link
@@ -265,15 +276,18 @@ impl crate::disk::state::Xvb {
         // Allow user to choose XvB pool manually
         // checkbox to enable
         ui.checkbox(&mut self.manual_pool_enabled, "Manual selection of the XvB pool").on_hover_text(XVB_MANUAL_POOL);
-        // slider for EU or NA
-            ui.horizontal(|ui|{
+        // list of the XvB pools
         ui.add_enabled_ui(self.manual_pool_enabled, |ui|{
-                ui.style_mut().override_text_style = Some(TextStyle::Heading);
-                ui.add_sized([0.0, text_height], Label::new(" [ NA"));
-                toggle_ui_compact(&mut self.manual_pool_eu, ui);
-                ui.add_sized([0.0, text_height], Label::new("EU ]"));
-            });
+            ComboBox::from_id_salt("xvb_manual_pool")
+                .selected_text(&self.manual_pool)
+                .show_ui(ui, |ui| {
+                    for node in endpoints.lock().unwrap().nodes.clone() {
+                        ui.selectable_value(&mut self.manual_pool, node.name.clone(), node.name);
+                    }
+                });
         });
+        // remote miners of the fleet controlled with the local one
+        self.controlled_miners(ui, remotes);
 
         }
 
@@ -357,8 +371,48 @@ if priv_stats.win_current {
                     })
                 });
                     // currently mining on
+            // hashrate needed for each round type
+            ui.add_space(SPACE);
+            round_planner(ui, &api.lock().unwrap());
+            // history of the rounds
+            ui.add_space(SPACE);
+            round_history(ui, &history.lock().unwrap());
+            // stratum probes of the XvB pools
+            ui.add_space(SPACE);
+            pool_probes(ui, &api.lock().unwrap().probes);
                 });
     }
+    fn controlled_miners(&mut self, ui: &mut Ui, remotes: &[RemoteInstance]) {
+        ui.label(RichText::new("Remote miners").underline().color(BONE))
+            .on_hover_text(XVB_CONTROLLED_MINERS);
+        let miners = remotes
+            .iter()
+            .filter(|r| is_controllable(r.kind))
+            .collect::<Vec<_>>();
+        if miners.is_empty() {
+            ui.label("Add XMRig or XMRig-Proxy instances in the [Fleet] submenu of the [Status] tab to control them.");
+            return;
+        }
+        // instances removed from the fleet are not controlled anymore
+        self.controlled
+            .retain(|name| miners.iter().any(|m| &m.name == name));
+        ui.horizontal_wrapped(|ui| {
+            for miner in miners {
+                let mut controlled = self.controlled.contains(&miner.name);
+                if ui
+                    .checkbox(&mut controlled, &miner.name)
+                    .on_hover_text(XVB_CONTROLLED_MINER)
+                    .changed()
+                {
+                    if controlled {
+                        self.controlled.push(miner.name.clone());
+                    } else {
+                        self.controlled.retain(|name| name != &miner.name);
+                    }
+                }
+            }
+        });
+    }
     fn field_token(&mut self, ui: &mut Ui) {
         StateTextEdit::new(ui)
             .help_msg(XVB_HELP)
@@ -387,3 +441,260 @@ fn stat_box(ui: &mut Ui, title: &str, value: &str, column_height: f32) {
         });
     });
 }
+
+// Results of the last stratum probes of the XvB pools, in the order used to choose the pool.
+fn pool_probes(ui: &mut Ui, probes: &[(String, ProbeResult)]) {
+    ui.group(|ui| {
+        ui.label(RichText::new("Pools latency").underline().color(BONE))
+            .on_hover_text(XVB_PROBES_HELP);
+        if probes.is_empty() {
+            ui.label("The XvB pools were not probed yet. They are probed when XvB starts and when the pool in use fails.");
+            return;
+        }
+        let text = ui.text_style_height(&TextStyle::Body);
+        ui.push_id("xvb_probes", |ui| {
+            TableBuilder::new(ui)
+                .columns(Column::auto(), 4)
+                .header(text * 1.5, |mut header| {
+                    for title in ["Pool", "Connection", "Login", "Job"] {
+                        header.col(|ui| {
+                            ui.label(RichText::new(title).underline().color(BONE))
+                                .on_hover_text(XVB_PROBES_COLUMNS);
+                        });
+                    }
+                })
+                .body(|mut body| {
+                    for (name, result) in probes {
+                        body.row(text, |mut row| {
+                            row.col(|ui| {
+                                ui.label(name);
+                            });
+                            match result {
+                                Ok(p) => {
+                                    for ms in [Some(p.connect_ms), Some(p.login_ms), p.job_ms] {
+                                        row.col(|ui| {
+                                            match ms {
+                                                Some(ms) => ui.label(RichText::new(format!("{ms}ms")).color(GREEN)),
+                                                None => ui.label(RichText::new("none").color(ORANGE)),
+                                            };
+                                        });
+                                    }
+                                }
+                                Err(e) => {
+                                    row.col(|ui| {
+                                        ui.label(RichText::new(e).color(RED));
+                                    });
+                                }
+                            }
+                        });
+                    }
+                });
+        });
+    });
+}
+
+// Donation needed for each round type with the hashrate of the last cycle of the algorithm.
+fn round_planner(ui: &mut Ui, api: &PubXvbApi) {
+    ui.group(|ui| {
+        ui.label(RichText::new("Round planner").underline().color(BONE))
+            .on_hover_text(XVB_PLANNER_HELP);
+        let Some(inputs) = &api.planner else {
+            ui.label("The planner is available once the algorithm of XvB ran a cycle.");
+            return;
+        };
+        let plans = plan_tiers(
+            inputs,
+            api.stats_priv.donor_1hr_avg * 1000.0,
+            api.stats_priv.donor_24hr_avg * 1000.0,
+        );
+        let text = ui.text_style_height(&TextStyle::Body);
+        ui.push_id("xvb_planner", |ui| {
+            TableBuilder::new(ui)
+                .columns(Column::auto(), 5)
+                .header(text * 1.5, |mut header| {
+                    for title in [
+                        "Round",
+                        "Donation",
+                        "Of hashrate",
+                        "Share coverage",
+                        "Time to reach",
+                    ] {
+                        header.col(|ui| {
+                            ui.label(RichText::new(title).underline().color(BONE))
+                                .on_hover_text(XVB_PLANNER_COLUMNS);
+                        });
+                    }
+                })
+                .body(|mut body| {
+                    for plan in plans {
+                        let color = if plan.keeps_share() { GREEN } else { RED };
+                        body.row(text, |mut row| {
+                            row.col(|ui| {
+                                ui.label(format!(
+                                    "{} ({} kH/s)",
+                                    plan.round,
+                                    Float::from_0((plan.tier_hashrate / 1000.0) as f64)
+                                ));
+                            });
+                            row.col(|ui| {
+                                ui.label(format!(
+                                    "{} kH/s",
+                                    Float::from_3((plan.donation / 1000.0) as f64)
+                                ));
+                            });
+                            row.col(|ui| {
+                                match plan.fraction {
+                                    Some(f) => ui.label(
+                                        RichText::new(format!("{:.1}%", f * 100.0)).color(color),
+                                    ),
+                                    None => ui.label("No hashrate"),
+                                };
+                            });
+                            row.col(|ui| {
+                                match plan.coverage {
+                                    Some(c) => ui.label(
+                                        RichText::new(format!("{:.0}%", c * 100.0)).color(color),
+                                    ),
+                                    None => ui.label(RichText::new("Not needed").color(GREEN)),
+                                };
+                            });
+                            row.col(|ui| {
+                                match plan.hours {
+                                    Some(h) if h <= 0.0 => {
+                                        ui.label(RichText::new("Reached").color(GREEN))
+                                    }
+                                    Some(h) => {
+                                        ui.label(Uptime::from((h * 3600.0) as u64).to_string())
+                                    }
+                                    None => ui.label(RichText::new("Out of reach").color(ORANGE)),
+                                };
+                            });
+                        });
+                    }
+                });
+        });
+    });
+}
+
+// Participation, wins and donation of the rounds logged on disk.
+fn round_history(ui: &mut Ui, history: &XvbHistory) {
+    ui.group(|ui| {
+        ui.label(RichText::new("Rounds history").underline().color(BONE))
+            .on_hover_text(XVB_HISTORY_HELP);
+        if history.rounds.is_empty() {
+            ui.label("No round was logged yet. Rounds are logged while XvB is running.");
+            return;
+        }
+        let now = chrono::Utc::now().timestamp();
+        let text = ui.text_style_height(&TextStyle::Body);
+        ui.push_id("xvb_history_summary", |ui| {
+            TableBuilder::new(ui)
+                .columns(Column::auto(), 5)
+                .header(text * 1.5, |mut header| {
+                    for title in ["Period", "Rounds", "Participation", "Wins", "Donated"] {
+                        header.col(|ui| {
+                            ui.label(RichText::new(title).underline().color(BONE))
+                                .on_hover_text(XVB_HISTORY_ROUNDS);
+                        });
+                    }
+                })
+                .body(|mut body| {
+                    for (period, secs) in [
+                        ("Last 24 hours", Some(86_400)),
+                        ("Last 7 days", Some(7 * 86_400)),
+                        ("Last 30 days", Some(30 * 86_400)),
+                        ("All", None),
+                    ] {
+                        let summary = history.summary(secs.map_or(i64::MIN, |s| now - s));
+                        body.row(text, |mut row| {
+                            row.col(|ui| {
+                                ui.label(period);
+                            });
+                            row.col(|ui| {
+                                ui.label(summary.rounds.to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(format!(
+                                    "{} ({:.1}%)",
+                                    summary.participated,
+                                    summary.participation()
+                                ));
+                            });
+                            row.col(|ui| {
+                                ui.label(summary.wins.to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(format!(
+                                    "{} kH/s*h",
+                                    Float::from_3(summary.donated_hr_hours / 1000.0)
+                                ));
+                            });
+                        });
+                    }
+                });
+        });
+        ui.separator();
+        ui.label(RichText::new("Last rounds").underline().color(BONE))
+            .on_hover_text(XVB_HISTORY_LAST_ROUNDS);
+        ui.push_id("xvb_history_rounds", |ui| {
+            TableBuilder::new(ui)
+                .columns(Column::auto(), 6)
+                .header(text * 1.5, |mut header| {
+                    for title in [
+                        "Date",
+                        "Round",
+                        "Donated 1h/24h",
+                        "Time on XvB",
+                        "Winner",
+                        "",
+                    ] {
+                        header.col(|ui| {
+                            ui.label(RichText::new(title).underline().color(BONE));
+                        });
+                    }
+                })
+                .body(|mut body| {
+                    for round in history.rounds.iter().rev().take(10) {
+                        body.row(text, |mut row| {
+                            row.col(|ui| {
+                                let date = chrono::DateTime::from_timestamp(round.timestamp, 0)
+                                    .map(|d| {
+                                        d.with_timezone(&chrono::Local)
+                                            .format("%Y-%m-%d %H:%M")
+                                            .to_string()
+                                    })
+                                    .unwrap_or_default();
+                                ui.label(date);
+                            });
+                            row.col(|ui| {
+                                ui.label(
+                                    round
+                                        .round
+                                        .as_ref()
+                                        .map_or("None".to_string(), |r| r.to_string()),
+                                );
+                            });
+                            row.col(|ui| {
+                                ui.label(format!(
+                                    "{}/{} kH/s",
+                                    Float::from_3(round.donor_1hr_avg as f64),
+                                    Float::from_3(round.donor_24hr_avg as f64)
+                                ));
+                            });
+                            row.col(|ui| {
+                                ui.label(Uptime::from(round.donated_secs).to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(&round.winner);
+                            });
+                            row.col(|ui| {
+                                if round.won {
+                                    ui.label(RichText::new("Won").color(GREEN));
+                                }
+                            });
+                        });
+                    }
+                });
+        });
+    });
+}
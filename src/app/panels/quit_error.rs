@@ -323,10 +323,11 @@ impl crate::app::App {
                                     SudoState::test_sudo(
                                         self.sudo.clone(),
                                         &self.helper.clone(),
-                                        &self.state.xmrig,
+                                        &self.xmrig_state(),
                                         &self.state.p2pool,
                                         &self.state.xmrig_proxy,
                                         &self.state.gupax.absolute_xmrig_path,
+                                        &self.state.gupax.socks5,
                                     );
                                 }
                             }
@@ -20,7 +20,7 @@ use crate::{constants::*, macros::*};
 use egui::Color32;
 use log::*;
 use rand::{Rng, rng};
-use reqwest::{Client, RequestBuilder};
+use reqwest::RequestBuilder;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -299,12 +299,12 @@ impl Ping {
     #[cold]
     #[inline(never)]
     // Intermediate function for spawning thread
-    pub fn spawn_thread(ping: &Arc<Mutex<Self>>) {
+    pub fn spawn_thread(ping: &Arc<Mutex<Self>>, socks5: Option<String>) {
         info!("Spawning ping thread...");
         let ping = Arc::clone(ping);
         std::thread::spawn(move || {
             let now = Instant::now();
-            match Self::ping(&ping) {
+            match Self::ping(&ping, socks5.as_deref()) {
                 Ok(msg) => {
                     info!("Ping ... OK");
                     ping.lock().unwrap().msg = msg;
@@ -343,7 +343,10 @@ impl Ping {
     #[cold]
     #[inline(never)]
     #[tokio::main]
-    pub async fn ping(ping: &Arc<Mutex<Self>>) -> Result<String, anyhow::Error> {
+    pub async fn ping(
+        ping: &Arc<Mutex<Self>>,
+        socks5: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
         // Start ping
         let ping = Arc::clone(ping);
         ping.lock().unwrap().pinging = true;
@@ -353,7 +356,7 @@ impl Ping {
         // Create HTTP client
         let info = "Creating HTTP Client".to_string();
         ping.lock().unwrap().msg = info;
-        let client = Client::new();
+        let client = crate::miscs::http_client(socks5);
 
         // Random User Agent
         let rand_user_agent = get_user_agent();
@@ -0,0 +1,309 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Stratum probe of a pool, to know how it really behaves for a miner.
+// A connection is opened like XMRig does (with TLS if the pool uses it), a throwaway worker
+// logs in and the time to get the answer to the login and the first job is measured.
+// The connection goes through the SOCKS5 proxy of Gupaxx if it is enabled, except for a local pool.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow, bail};
+use log::{error, info};
+use serde_json::{Value, json};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    time::timeout,
+};
+use tokio_rustls::{
+    TlsConnector,
+    rustls::{
+        ClientConfig, DigitallySignedStruct, SignatureScheme,
+        client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        crypto::{CryptoProvider, ring, verify_tls12_signature, verify_tls13_signature},
+        pki_types::{CertificateDer, ServerName, UnixTime},
+    },
+};
+use tokio_socks::tcp::Socks5Stream;
+
+use crate::GUPAX_VERSION_UNDERSCORE;
+
+// Time given to each step of the probe: connection, answer to the login and first job.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+// Login of the throwaway worker.
+pub const PROBE_WORKER: &str = "gupaxx_probe";
+// Added to the login round-trip of a pool that did not give a job.
+pub const PROBE_NO_JOB_PENALTY: u128 = 1000;
+
+pub type ProbeResult = Result<StratumProbe, String>;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StratumProbe {
+    // TCP connection and TLS handshake
+    pub connect_ms: u128,
+    // from the login request to its answer
+    pub login_ms: u128,
+    // from the login request to the first job, None if the pool did not give one
+    pub job_ms: Option<u128>,
+    // the pool refused the login of the throwaway worker, no job can be measured
+    pub refused: bool,
+}
+
+impl StratumProbe {
+    // Latency used to rank the pools, a pool without job is ranked after the others.
+    // If the login was refused, only the connection tells how fast the pool is.
+    pub fn score(&self) -> u128 {
+        if self.refused {
+            return self.connect_ms;
+        }
+        self.job_ms.unwrap_or(self.login_ms + PROBE_NO_JOB_PENALTY)
+    }
+    // short description for the UI
+    pub fn summary(result: &ProbeResult) -> String {
+        match result {
+            Ok(p) if p.refused => format!("login refused, connect {}ms", p.connect_ms),
+            Ok(p) => format!(
+                "login {}ms, job {}",
+                p.login_ms,
+                p.job_ms.map_or("none".to_string(), |ms| format!("{ms}ms"))
+            ),
+            Err(e) => e.clone(),
+        }
+    }
+}
+
+// Connect to the pool, log in and wait for a job.
+pub async fn probe_stratum(
+    host: &str,
+    port: &str,
+    tls: bool,
+    socks5: Option<&str>,
+) -> Result<StratumProbe> {
+    // an IPv6 address can be written with brackets like in an URL
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = port.parse::<u16>()?;
+    let start = Instant::now();
+    let tcp = timeout(PROBE_TIMEOUT, connect(host, port, socks5))
+        .await
+        .map_err(|_| anyhow!("connection timed out"))??;
+    if tls {
+        let connector = TlsConnector::from(Arc::new(tls_config()?));
+        let name = ServerName::try_from(host.to_string())?;
+        let stream = timeout(PROBE_TIMEOUT, connector.connect(name, tcp))
+            .await
+            .map_err(|_| anyhow!("TLS handshake timed out"))??;
+        login(stream, start).await
+    } else {
+        login(tcp, start).await
+    }
+}
+
+// Connect directly or through the SOCKS5 proxy, the host is resolved by the proxy.
+async fn connect(host: &str, port: u16, socks5: Option<&str>) -> Result<TcpStream> {
+    match socks5 {
+        Some(proxy) if !is_local(host) => Ok(Socks5Stream::connect(proxy, (host, port))
+            .await?
+            .into_inner()),
+        _ => Ok(TcpStream::connect((host, port)).await?),
+    }
+}
+
+// same exceptions as the HTTP client of Gupaxx
+fn is_local(host: &str) -> bool {
+    matches!(host, "127.0.0.1" | "localhost" | "::1")
+}
+
+async fn login<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: S,
+    start: Instant,
+) -> Result<StratumProbe> {
+    let mut probe = StratumProbe {
+        connect_ms: start.elapsed().as_millis(),
+        ..Default::default()
+    };
+    let (read, mut write) = tokio::io::split(stream);
+    let mut lines = BufReader::new(read).lines();
+    let request = json!({
+        "id": 1,
+        "jsonrpc": "2.0",
+        "method": "login",
+        "params": {
+            "login": PROBE_WORKER,
+            "pass": "x",
+            "agent": GUPAX_VERSION_UNDERSCORE,
+            "algo": ["rx/0"],
+        },
+    });
+    let sent = Instant::now();
+    write.write_all(format!("{request}\n").as_bytes()).await?;
+    let answer = timeout(PROBE_TIMEOUT, next_message(&mut lines))
+        .await
+        .map_err(|_| anyhow!("no answer to the login"))??;
+    probe.login_ms = sent.elapsed().as_millis();
+    // the login of the throwaway worker can be refused, the connection time is used instead.
+    if !answer["error"].is_null() {
+        info!("Stratum probe | login refused: {}", answer["error"]);
+        probe.refused = true;
+        return Ok(probe);
+    }
+    if answer["result"]["job"].is_object() {
+        probe.job_ms = Some(probe.login_ms);
+        return Ok(probe);
+    }
+    // the job can also be sent after the answer of the login
+    let job = async {
+        loop {
+            if next_message(&mut lines).await?["method"] == "job" {
+                return Ok::<_, anyhow::Error>(());
+            }
+        }
+    };
+    if let Ok(Ok(())) = timeout(PROBE_TIMEOUT, job).await {
+        probe.job_ms = Some(sent.elapsed().as_millis());
+    }
+    Ok(probe)
+}
+
+async fn next_message<R: AsyncRead + Unpin>(
+    lines: &mut tokio::io::Lines<BufReader<R>>,
+) -> Result<Value> {
+    match lines.next_line().await? {
+        Some(line) => Ok(serde_json::from_str(&line)?),
+        None => bail!("connection closed by the pool"),
+    }
+}
+
+// Like XMRig, the certificate of the pool is not verified, pools often use a self-signed one.
+fn tls_config() -> Result<ClientConfig> {
+    let provider = Arc::new(ring::default_provider());
+    Ok(ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AnyCertificate(provider)))
+        .with_no_client_auth())
+}
+
+#[derive(Debug)]
+struct AnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, tokio_rustls::rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Probes of the [XMRig] tab
+#[derive(Debug, Default)]
+pub struct Probes {
+    // by address host:port
+    pub results: HashMap<String, ProbeResult>,
+    pub probing: bool,
+    // SOCKS5 proxy of Gupaxx, kept in sync with the state by the GUI.
+    pub socks5: Option<String>,
+}
+
+impl Probes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // Probe the pools (host, port, tls) in parallel on a new thread.
+    pub fn spawn_thread(probes: &Arc<Mutex<Self>>, pools: Vec<(String, String, bool)>) {
+        info!("Spawning stratum probe thread...");
+        let socks5 = {
+            let mut probes = probes.lock().unwrap();
+            probes.probing = true;
+            probes.socks5.clone()
+        };
+        let probes = Arc::clone(probes);
+        std::thread::spawn(move || {
+            let results = Self::probe(pools, socks5);
+            let mut probes = probes.lock().unwrap();
+            probes.results.extend(results);
+            probes.probing = false;
+        });
+    }
+    #[tokio::main]
+    async fn probe(
+        pools: Vec<(String, String, bool)>,
+        socks5: Option<String>,
+    ) -> Vec<(String, ProbeResult)> {
+        let handles: Vec<_> = pools
+            .into_iter()
+            .map(|(host, port, tls)| {
+                let socks5 = socks5.clone();
+                tokio::spawn(async move {
+                    let result = probe_stratum(&host, &port, tls, socks5.as_deref())
+                        .await
+                        .map_err(|e| e.to_string());
+                    (format!("{host}:{port}"), result)
+                })
+            })
+            .collect();
+        let mut results = Vec::with_capacity(handles.len());
+        for handle in handles {
+            match handle.await {
+                Ok(result) => results.push(result),
+                Err(e) => error!("Stratum probe | {e}"),
+            }
+        }
+        results
+    }
+    pub fn get(&self, address: &str) -> Option<&ProbeResult> {
+        self.results.get(address)
+    }
+}
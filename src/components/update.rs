@@ -57,6 +57,8 @@ use zip::ZipArchive;
 //
 
 const GUPAX_METADATA: &str = "https://api.github.com/repos/Cyrix126/gupaxx/releases/latest";
+const CPU_BENCHMARKS: &str =
+    "https://raw.githubusercontent.com/Cyrix126/gupaxx/main/assets/cpu.json";
 
 cfg_if::cfg_if! {
      if #[cfg(target_family = "unix")] {
@@ -124,6 +126,7 @@ const MSG_START: &str = "Starting update";
 const MSG_TMP: &str = "Creating temporary directory";
 const MSG_HTTPS: &str = "Creating HTTPS client";
 const MSG_METADATA: &str = "Fetching package metadata";
+const MSG_BENCHMARKS: &str = "Fetching CPU benchmarks";
 const MSG_COMPARE: &str = "Compare package versions";
 const MSG_UP_TO_DATE: &str = "All packages already up-to-date";
 const MSG_DOWNLOAD: &str = "Downloading packages";
@@ -133,6 +136,7 @@ pub const MSG_FAILED: &str = "Update failed";
 pub const MSG_FAILED_HELP: &str = "Consider manually replacing your executable from github releases: https://github.com/Cyrix126/gupaxx/releases";
 const INIT: &str = "------------------- Init -------------------";
 const METADATA: &str = "----------------- Metadata -----------------";
+const BENCHMARKS: &str = "---------------- Benchmarks ----------------";
 const COMPARE: &str = "----------------- Compare ------------------";
 const DOWNLOAD: &str = "----------------- Download -----------------";
 const EXTRACT: &str = "----------------- Extract ------------------";
@@ -168,14 +172,15 @@ pub fn check_binary_path(path: &str, process: ProcessName) -> bool {
 
 #[derive(Clone)]
 pub struct Update {
-    pub path_gupax: String,         // Full path to current gupax
-    pub path_p2pool: String,        // Full path to current p2pool
-    pub path_xmrig: String,         // Full path to current xmrig
-    pub path_xp: String,            // Full path to current xmrig-proxy
-    pub path_node: String,          // Full path to current node
-    pub updating: Arc<Mutex<bool>>, // Is an update in progress?
-    pub prog: Arc<Mutex<f32>>,      // Holds the 0-100% progress bar number
-    pub msg: Arc<Mutex<String>>,    // Message to display on [Gupax] tab while updating
+    pub path_gupax: String,                    // Full path to current gupax
+    pub path_p2pool: String,                   // Full path to current p2pool
+    pub path_xmrig: String,                    // Full path to current xmrig
+    pub path_xp: String,                       // Full path to current xmrig-proxy
+    pub path_node: String,                     // Full path to current node
+    pub updating: Arc<Mutex<bool>>,            // Is an update in progress?
+    pub prog: Arc<Mutex<f32>>,                 // Holds the 0-100% progress bar number
+    pub msg: Arc<Mutex<String>>,               // Message to display on [Gupax] tab while updating
+    pub available: Arc<Mutex<Option<String>>>, // Newer version found by the last check, read by the notify thread
 }
 
 impl Update {
@@ -196,6 +201,7 @@ impl Update {
             updating: arc_mut!(false),
             prog: arc_mut!(0.0),
             msg: arc_mut!(MSG_NONE.to_string()),
+            available: arc_mut!(None),
         }
     }
 
@@ -397,7 +403,8 @@ impl Update {
         info!("Update | {}", msg);
         *lock.msg.lock().unwrap() = msg;
         drop(lock);
-        let client = Client::new();
+        let socks5 = og.lock().unwrap().gupax.socks5.gupaxx();
+        let client = crate::miscs::http_client(socks5.as_deref());
         *update.lock().unwrap().prog.lock().unwrap() += 5.0;
         info!(
             "Update | Init ... OK ... {}%",
@@ -422,6 +429,15 @@ impl Update {
         *update.lock().unwrap().prog.lock().unwrap() += 10.0;
         info!("Update | Gupaxx {} ... OK", new_ver);
 
+        //---------------------------------------------------------------------------------------------------- Benchmarks
+        // The dataset is refreshed even if Gupaxx is up-to-date, a failure does not stop the update.
+        *update.lock().unwrap().msg.lock().unwrap() = MSG_BENCHMARKS.to_string();
+        info!("Update | {}", BENCHMARKS);
+        match Self::update_benchmarks(&client, user_agent).await {
+            Ok(len) => info!("Update | CPU benchmarks ({} CPUs) ... OK", len),
+            Err(e) => warn!("Update | CPU benchmarks ... FAIL ... {}", e),
+        }
+
         //---------------------------------------------------------------------------------------------------- Compare
         *update.lock().unwrap().msg.lock().unwrap() = MSG_COMPARE.to_string();
         info!("Update | {}", COMPARE);
@@ -431,6 +447,7 @@ impl Update {
                 "Update | Gupaxx {} != {} ... ADDING",
                 GUPAX_VERSION, new_ver
             );
+            *update.lock().unwrap().available.lock().unwrap() = Some(new_ver.clone());
         } else {
             info!(
                 "Update | Gupaxx {} == {} ... SKIPPING",
@@ -613,6 +630,21 @@ impl Update {
         *update.lock().unwrap().prog.lock().unwrap() = 100.0;
         Ok(())
     }
+
+    // Download the CPU benchmarks dataset into the data directory, it will be used at the next start.
+    // Returns the amount of CPUs in the dataset.
+    async fn update_benchmarks(
+        client: &Client,
+        user_agent: &'static str,
+    ) -> Result<usize, anyhow::Error> {
+        let bytes = get_bytes(client, CPU_BENCHMARKS.to_string(), user_agent).await?;
+        let len = crate::utils::benchmarks::parse_benchmarks(&bytes)?.len();
+        let path = get_gupax_data_path()
+            .map_err(|e| anyhow!(e.to_string()))?
+            .join(consts::CPU_BENCHMARKS_JSON);
+        std::fs::write(path, bytes)?;
+        Ok(len)
+    }
 }
 
 //---------------------------------------------------------------------------------------------------- Pkg functions
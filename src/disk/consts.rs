@@ -14,6 +14,11 @@ pub const DIRECTORY: &str = "gupaxx/";
 pub const STATE_TOML: &str = "state.toml";
 pub const NODE_TOML: &str = "node.toml";
 pub const POOL_TOML: &str = "pool.toml";
+pub const XMRIG_CONFIG_JSON: &str = "xmrig.json";
+pub const CPU_BENCHMARKS_JSON: &str = "cpu.json";
+pub const XVB_ROUNDS_LOG: &str = "xvb_rounds.jsonl";
+pub const XVB_SAMPLES_JSON: &str = "xvb_samples.json";
+pub const XVB_REMOTE_POOLS_JSON: &str = "xvb_remote_pools.json";
 
 // P2Pool API
 // Lives within the Gupax OS data directory.
@@ -58,6 +58,10 @@ pub mod pool;
 pub mod state;
 pub mod status;
 pub mod tests;
+pub mod xmrig_config;
+pub mod xvb_history;
+pub mod xvb_remote_pools;
+pub mod xvb_samples;
 //---------------------------------------------------------------------------------------------------- General functions for all [File]'s
 // get_file_path()      | Return absolute path to OS data path + filename
 // read_to_string()     | Convert the file at a given path into a [String]
@@ -192,6 +196,16 @@ pub enum File {
     Node,  // node.toml    | P2Pool manual node selector
     Pool,  // pool.toml    | XMRig manual pool selector
 
+    // XMRig config generated from the state
+    XmrigConfig, // xmrig.json
+
+    // History of the XvB rounds
+    XvbRounds, // xvb_rounds.jsonl
+    // Hashrate sent by the XvB algorithm during the last hour
+    XvbSamples, // xvb_samples.json
+    // Remote miners sent to XvB by the algorithm
+    XvbRemotePools, // xvb_remote_pools.json
+
     // Gupax-P2Pool API
     Log,    // log    | Raw log lines of P2Pool payouts received
     Payout, // payout | Single [u64] representing total payouts
@@ -1,14 +1,18 @@
 use anyhow::Result;
 use derive_more::Display;
 use rand::{Rng, distr::Alphanumeric, rng};
-use strum::{EnumCount, EnumIter};
+use strum::{EnumCount, EnumIter, IntoEnumIterator};
 
 use super::*;
 use crate::{
     app::panels::middle::common::list_poolnode::PoolNode,
     components::node::RemoteNode,
     disk::status::*,
-    helper::{Helper, ProcessName, node::ImgNode, p2pool::ImgP2pool, xrig::xmrig_proxy::ImgProxy},
+    helper::{
+        Helper, ProcessName, node::ImgNode, notify::NotifyEvent, p2pool::ImgP2pool,
+        xrig::xmrig_proxy::ImgProxy,
+    },
+    utils::topology::affinity_mask,
 };
 //---------------------------------------------------------------------------------------------------- [State] Impl
 impl Default for State {
@@ -40,6 +44,16 @@ impl State {
         self.gupax.absolute_node_path = into_absolute_path(self.gupax.node_path.clone())?;
         Ok(())
     }
+    /// Is the process set to be attached to an instance started outside of Gupaxx
+    pub fn attached(&self, name: ProcessName) -> bool {
+        match name {
+            ProcessName::Node => self.node.attached(),
+            ProcessName::P2pool => self.p2pool.attached(),
+            ProcessName::Xmrig => self.xmrig.attached(),
+            ProcessName::XmrigProxy => self.xmrig_proxy.attached(),
+            ProcessName::Xvb => false,
+        }
+    }
 
     // Convert [&str] to [State]
     pub fn from_str(string: &str) -> Result<Self, TomlError> {
@@ -143,7 +157,7 @@ impl State {
     // leaving behind old keys+values and updating [default] with old valid ones.
     pub fn merge(old: &str) -> Result<Self, TomlError> {
         let default = toml::ser::to_string(&Self::new()).unwrap();
-        let new: Self = match Figment::from(Toml::string(&default))
+        let mut new: Self = match Figment::from(Toml::string(&default))
             .merge(Toml::string(old))
             .extract()
         {
@@ -156,6 +170,15 @@ impl State {
                 return Err(TomlError::Merge(err));
             }
         };
+        // [manual_pool_eu] was replaced by the name of the pool
+        let old = Figment::from(Toml::string(old));
+        if let (Ok(eu), Err(_)) = (
+            old.extract_inner::<bool>("xvb.manual_pool_eu"),
+            old.extract_inner::<String>("xvb.manual_pool"),
+        ) {
+            info!("State | Migrating [manual_pool_eu] to [manual_pool]");
+            new.xvb.manual_pool = if eu { "European" } else { "North America" }.to_string();
+        }
         Ok(new)
     }
 }
@@ -180,6 +203,14 @@ pub struct Status {
     pub manual_hash: bool,
     pub hashrate: f64,
     pub hash_metric: Hash,
+    // fields of the remote instance to add in the fleet view
+    pub remote: RemoteInstance,
+    pub remotes: Vec<RemoteInstance>,
+    // settings of the next local benchmark and the results of the previous ones
+    pub bench_size: BenchSize,
+    pub bench_threads: u16,
+    pub bench_hugepages: bool,
+    pub local_benchmarks: Vec<LocalBenchmark>,
 }
 
 #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
@@ -200,6 +231,92 @@ pub struct Gupax {
     pub tab: Tab,
     pub ratio: Ratio,
     pub show_processes: Vec<ProcessName>,
+    pub socks5: Socks5,
+    pub notifications: Notifications,
+    pub mqtt: Mqtt,
+}
+
+// SOCKS5 proxy (Tor daemon, i2pd, ...) used for the traffic leaving this machine.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Socks5 {
+    pub enabled: bool,
+    pub ip: String,
+    pub port: String,
+    // the proxy is the Tor daemon, the Node also broadcasts its transactions to onion peers
+    pub tor: bool,
+    pub use_node: bool,
+    pub use_p2pool: bool,
+    pub use_xmrig: bool,
+    pub use_gupaxx: bool,
+}
+
+impl Socks5 {
+    // address of the proxy, only if it is enabled and must be used by the caller.
+    fn address_if(&self, used: bool) -> Option<String> {
+        if self.enabled && used && !self.ip.is_empty() && !self.port.is_empty() {
+            let ip = if self.ip == "localhost" {
+                "127.0.0.1"
+            } else {
+                &self.ip
+            };
+            Some([ip, ":", &self.port].concat())
+        } else {
+            None
+        }
+    }
+    // monerod only accepts an IP for its proxy.
+    pub fn node(&self) -> Option<String> {
+        self.address_if(self.use_node && !self.node_ip_invalid())
+    }
+    // The proxy must be used by the Node but is not given by an IP.
+    pub fn node_ip_invalid(&self) -> bool {
+        self.enabled
+            && self.use_node
+            && self.ip != "localhost"
+            && self.ip.parse::<std::net::Ipv4Addr>().is_err()
+    }
+    pub fn p2pool(&self) -> Option<String> {
+        self.address_if(self.use_p2pool)
+    }
+    pub fn xmrig(&self) -> Option<String> {
+        self.address_if(self.use_xmrig)
+    }
+    // updates, XvB stats, remote node/XvB ping
+    pub fn gupaxx(&self) -> Option<String> {
+        self.address_if(self.use_gupaxx)
+    }
+}
+
+// Events notified outside of the window, on the desktop (Linux) and/or to a webhook.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Notifications {
+    pub desktop: bool,
+    pub webhook: bool,
+    pub webhook_url: String,
+    pub events: Vec<NotifyEvent>,
+}
+impl Notifications {
+    pub fn enable(&mut self, event: NotifyEvent, enable: bool) {
+        if !enable {
+            self.events.retain(|e| *e != event);
+        } else if !self.events.contains(&event) {
+            self.events.push(event);
+        }
+    }
+}
+
+// MQTT broker the state of the processes is published to, for home automation.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Mqtt {
+    pub enabled: bool,
+    pub host: String,
+    pub port: String,
+    pub username: String,
+    pub password: String,
+    // topics are [prefix/process/...]
+    pub prefix: String,
+    // accept commands to start/stop the processes and change the threads of XMRig
+    pub commands: bool,
 }
 
 #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
@@ -310,6 +427,9 @@ pub struct P2pool {
     pub selected_node: SelectedPoolNode,
     pub prefer_local_node: bool,
     pub console_height: u32,
+    // monitor a P2Pool started outside of Gupaxx through its data-api instead of starting one
+    pub attach: bool,
+    pub attach_data_api: String,
 }
 
 // compatible for P2Pool and Xmrig/Proxy
@@ -339,6 +459,72 @@ pub struct Node {
     pub path_db: String,
     pub full_memory: bool,
     pub console_height: u32,
+    // monitor a Node started outside of Gupaxx through its RPC instead of starting one
+    pub attach: bool,
+    pub i2p: I2p,
+}
+
+// I2P router (i2pd, Java I2P) used by the Node to relay transactions and accept anonymous peers.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct I2p {
+    pub enabled: bool,
+    pub ip: String,
+    pub port: String,
+    pub inbound: bool,
+    pub inbound_address: String,
+    pub inbound_port: String,
+    pub inbound_max: u16,
+    pub use_p2pool: bool,
+}
+
+impl I2p {
+    // SOCKS endpoint of the I2P router, only if it is enabled.
+    pub fn proxy(&self) -> Option<String> {
+        if self.enabled && !self.ip.is_empty() && !self.port.is_empty() {
+            let ip = if self.ip == "localhost" {
+                "127.0.0.1"
+            } else {
+                &self.ip
+            };
+            Some([ip, ":", &self.port].concat())
+        } else {
+            None
+        }
+    }
+    pub fn p2pool(&self) -> Option<String> {
+        self.proxy().filter(|_| self.use_p2pool)
+    }
+    // value of --anonymous-inbound: <b32 address>,<local bind>,<max connections>
+    // The I2P router must have a server tunnel pointing to the local bind.
+    pub fn inbound(&self) -> Option<String> {
+        if self.proxy().is_some()
+            && self.inbound
+            && !self.inbound_address.is_empty()
+            && !self.inbound_port.is_empty()
+        {
+            Some(format!(
+                "{},127.0.0.1:{},{}",
+                self.inbound_address, self.inbound_port, self.inbound_max
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for I2p {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ip: String::from("127.0.0.1"),
+            port: 4447.to_string(),
+            inbound: false,
+            inbound_address: String::new(),
+            inbound_port: 18085.to_string(),
+            inbound_max: 32,
+            use_p2pool: false,
+        }
+    }
 }
 
 impl Default for Node {
@@ -359,6 +545,8 @@ impl Default for Node {
             path_db: String::new(),
             full_memory: false,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            attach: false,
+            i2p: I2p::default(),
         }
     }
 }
@@ -383,6 +571,56 @@ pub struct Xmrig {
     pub selected_pool: SelectedPoolNode,
     pub token: String,
     pub console_height: u32,
+    // monitor a XMRig started outside of Gupaxx through its HTTP API instead of starting one
+    pub attach: bool,
+    // thread layout, only used with advanced settings
+    pub layout: bool,
+    pub cores: Vec<u16>, // CPUs the threads are pinned to, the threads slider is used if empty
+    pub cpu_priority: u16,
+    pub randomx_mode: RandomxMode,
+    // start with a config file generated from these settings instead of command line flags
+    pub config_file: bool,
+    // pause or stop half of the threads when the CPU is too hot, temperatures in °C
+    pub throttle: bool,
+    pub temp_soft: u16,
+    pub temp_hard: u16,
+    pub throttle_cooldown: u16, // seconds below a threshold before the threads are restored
+    // pools XMRig switches to, in this order, when the pool above is down
+    pub failover: Vec<FailoverPool>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct FailoverPool {
+    pub name: String,
+    pub ip: String,
+    pub port: String,
+    pub user: String,
+    pub rig: String,
+    pub tls: bool,
+    pub keepalive: bool,
+}
+
+impl FailoverPool {
+    /// address given to XMRig, which doesn't understand [localhost]
+    pub fn url(&self) -> String {
+        let ip = if self.ip == "localhost" || self.ip.is_empty() {
+            "127.0.0.1"
+        } else {
+            &self.ip
+        };
+        format!("{}:{}", ip, self.port)
+    }
+}
+
+#[derive(
+    Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize, Default, EnumIter, strum::Display,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum RandomxMode {
+    #[default]
+    Auto,
+    Fast,
+    Light,
 }
 
 // present for future.
@@ -406,6 +644,8 @@ pub struct XmrigProxy {
     pub token: String,
     pub redirect_local_xmrig: bool,
     pub console_height: u32,
+    // monitor a XMRig-Proxy started outside of Gupaxx through its HTTP API instead of starting one
+    pub attach: bool,
 }
 
 impl Gupax {
@@ -469,6 +709,7 @@ impl Default for XmrigProxy {
             tls: false,
             keepalive: false,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            attach: false,
         }
     }
 }
@@ -488,7 +729,10 @@ pub struct Xvb {
     pub console_height: u32,
     // user can choose a pool manually
     pub manual_pool_enabled: bool,
-    pub manual_pool_eu: bool,
+    // name of the pool in the list
+    pub manual_pool: String,
+    // names of the remote XMRig and XMRig-Proxy of the fleet also controlled by the algorithm
+    pub controlled: Vec<String>,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize, Default, EnumCount, EnumIter)]
@@ -588,6 +832,12 @@ impl Default for Status {
             manual_hash: false,
             hashrate: 1.0,
             hash_metric: Hash::default(),
+            remote: RemoteInstance::default(),
+            remotes: Vec::new(),
+            bench_size: BenchSize::default(),
+            bench_threads: 1,
+            bench_hugepages: true,
+            local_benchmarks: Vec::new(),
         }
     }
 }
@@ -611,6 +861,49 @@ impl Default for Gupax {
             ratio: Ratio::Width,
             tab: Tab::Xvb,
             show_processes: ProcessName::having_tab(),
+            socks5: Socks5::default(),
+            notifications: Notifications::default(),
+            mqtt: Mqtt::default(),
+        }
+    }
+}
+
+impl Default for Mqtt {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".to_string(),
+            port: "1883".to_string(),
+            username: String::new(),
+            password: String::new(),
+            prefix: "gupaxx".to_string(),
+            commands: false,
+        }
+    }
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            desktop: false,
+            webhook: false,
+            webhook_url: String::new(),
+            events: NotifyEvent::iter().collect(),
+        }
+    }
+}
+
+impl Default for Socks5 {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ip: "127.0.0.1".to_string(),
+            port: "9050".to_string(),
+            tor: true,
+            use_node: true,
+            use_p2pool: true,
+            use_xmrig: false,
+            use_gupaxx: true,
         }
     }
 }
@@ -644,6 +937,8 @@ impl Default for P2pool {
             },
             prefer_local_node: true,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            attach: false,
+            attach_data_api: String::new(),
         }
     }
 }
@@ -696,6 +991,17 @@ impl Default for Xmrig {
                 .map(char::from)
                 .collect(),
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            attach: false,
+            layout: false,
+            cores: Vec::new(),
+            cpu_priority: 2,
+            randomx_mode: RandomxMode::default(),
+            config_file: false,
+            throttle: false,
+            temp_soft: 80,
+            temp_hard: 90,
+            throttle_cooldown: 60,
+            failover: Vec::new(),
         }
     }
 }
@@ -715,7 +1021,8 @@ impl Default for Xvb {
             use_p2pool_sidechain_hr: false,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
             manual_pool_enabled: false,
-            manual_pool_eu: true,
+            manual_pool: String::from("European"),
+            controlled: Vec::new(),
         }
     }
 }
@@ -735,8 +1042,12 @@ impl Node {
     pub const fn process_name() -> ProcessName {
         ProcessName::Node
     }
+    /// attach mode is only available with advanced settings
+    pub fn attached(&self) -> bool {
+        self.attach && !self.simple
+    }
     pub fn start_options(&self, mode: StartOptionsMode) -> String {
-        Helper::build_node_args(self, mode).join(" ")
+        Helper::build_node_args(self, mode, None, false).join(" ")
     }
     /// Return rpc port, zmq port from state
     pub fn ports(&self) -> (u16, u16) {
@@ -791,6 +1102,10 @@ impl P2pool {
     pub const fn process_name() -> ProcessName {
         ProcessName::P2pool
     }
+    /// attach mode is only available with advanced settings
+    pub fn attached(&self) -> bool {
+        self.attach && !self.simple
+    }
     pub fn start_options(
         &self,
         path: &Path,
@@ -807,6 +1122,7 @@ impl P2pool {
             local_node_rpc_port,
             local_node_zmq_port,
             mode,
+            None,
         )
         .join(" ")
     }
@@ -846,17 +1162,72 @@ impl Xmrig {
     pub const fn process_name() -> ProcessName {
         ProcessName::Xmrig
     }
+    /// attach mode is only available with advanced settings
+    pub fn attached(&self) -> bool {
+        self.attach && !self.simple
+    }
     pub fn start_options(&self, mode: StartOptionsMode, p2pool_stratum_port: u16) -> String {
-        Helper::build_xmrig_args(self, mode, p2pool_stratum_port).join(" ")
+        Helper::build_xmrig_args(self, mode, p2pool_stratum_port, None).join(" ")
+    }
+    /// threads started with the advanced settings, one per selected CPU if the layout pins them
+    pub fn threads(&self) -> u16 {
+        if self.layout && !self.cores.is_empty() {
+            self.cores.len() as u16
+        } else {
+            self.current_threads
+        }
+    }
+    /// arguments of the thread layout, empty if it is disabled
+    pub fn layout_args(&self) -> Vec<String> {
+        if !self.layout {
+            return vec![];
+        }
+        let mut args = vec![
+            format!("--cpu-priority={}", self.cpu_priority),
+            format!("--randomx-mode={}", self.randomx_mode),
+        ];
+        if !self.cores.is_empty() {
+            args.push(format!("--cpu-affinity={:#x}", affinity_mask(&self.cores)));
+        }
+        args
     }
 }
 impl XmrigProxy {
     pub const fn process_name() -> ProcessName {
         ProcessName::XmrigProxy
     }
+    /// attach mode is only available with advanced settings
+    pub fn attached(&self) -> bool {
+        self.attach && !self.simple
+    }
     pub fn start_options(&self, mode: StartOptionsMode, p2pool_stratum_port: u16) -> String {
         Helper::build_xp_args(self, mode, p2pool_stratum_port).join(" ")
     }
+    /// get the API IP that would be used if xmrig-proxy was started with the current settings,
+    /// or the one of the attached xmrig-proxy.
+    pub fn api_ip(&self) -> String {
+        let ip = if self.simple {
+            ""
+        } else if !self.arguments.is_empty() {
+            let mut last = "";
+            let mut ip = "";
+            for arg in self.arguments.split_whitespace() {
+                if last == "--http-host" {
+                    ip = arg;
+                }
+                last = arg;
+            }
+            ip
+        } else {
+            &self.api_ip
+        };
+        // xmrig-proxy doesn't understand [localhost]
+        if ip == "localhost" || ip.is_empty() {
+            "127.0.0.1".to_string()
+        } else {
+            ip.to_string()
+        }
+    }
     /// get the API port that would be used if xmrig was started with the current settings
     pub fn api_port(&self) -> u16 {
         if self.simple {
@@ -864,8 +1235,8 @@ impl XmrigProxy {
         } else if !self.arguments.is_empty() {
             let mut last = "";
             for arg in self.arguments.split_whitespace() {
-                if last == "--http-host" {
-                    return last.parse().unwrap_or(PROXY_API_PORT_DEFAULT);
+                if last == "--http-port" {
+                    return arg.parse().unwrap_or(PROXY_API_PORT_DEFAULT);
                 }
                 last = arg;
             }
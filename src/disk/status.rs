@@ -25,6 +25,8 @@ pub enum Submenu {
     Processes,
     P2pool,
     Benchmarks,
+    Threads,
+    Fleet,
 }
 
 impl Default for Submenu {
@@ -43,6 +45,111 @@ impl Display for Submenu {
     }
 }
 
+//---------------------------------------------------------------------------------------------------- [RemoteInstance] for [Status/Fleet] tab
+// A XMRig, XMRig-Proxy or Node running on another host, monitored through its API.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct RemoteInstance {
+    pub name: String,
+    pub kind: RemoteKind,
+    pub host: String,
+    pub port: String,
+    // token of the HTTP API of XMRig and XMRig-Proxy, not used by the Node.
+    pub token: String,
+}
+
+impl Default for RemoteInstance {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            kind: RemoteKind::default(),
+            host: String::new(),
+            port: RemoteKind::default().default_port().to_string(),
+            token: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize, EnumIter)]
+pub enum RemoteKind {
+    Xmrig,
+    XmrigProxy,
+    Node,
+}
+
+impl Default for RemoteKind {
+    fn default() -> Self {
+        Self::Xmrig
+    }
+}
+
+impl RemoteKind {
+    pub const fn default_port(&self) -> u16 {
+        match self {
+            Self::Xmrig => XMRIG_API_PORT_DEFAULT,
+            Self::XmrigProxy => PROXY_API_PORT_DEFAULT,
+            Self::Node => NODE_RPC_PORT_DEFAULT,
+        }
+    }
+}
+
+impl Display for RemoteKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Xmrig => write!(f, "XMRig"),
+            Self::XmrigProxy => write!(f, "XMRig-Proxy"),
+            Self::Node => write!(f, "Node"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [LocalBenchmark] for [Status/Benchmarks] tab
+// Number of hashes computed by [xmrig --bench].
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize, EnumIter)]
+pub enum BenchSize {
+    OneMillion,
+    TenMillion,
+}
+
+impl Default for BenchSize {
+    fn default() -> Self {
+        Self::OneMillion
+    }
+}
+
+impl BenchSize {
+    pub const fn hashes(&self) -> u32 {
+        match self {
+            Self::OneMillion => 1_000_000,
+            Self::TenMillion => 10_000_000,
+        }
+    }
+    // value of [--bench]
+    pub const fn arg(&self) -> &str {
+        match self {
+            Self::OneMillion => "1M",
+            Self::TenMillion => "10M",
+        }
+    }
+}
+
+impl Display for BenchSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.arg())
+    }
+}
+
+// A benchmark run on this computer.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct LocalBenchmark {
+    pub date: String,
+    pub size: BenchSize,
+    pub threads: u16,
+    // every huge page needed by the dataset and the threads was allocated
+    pub hugepages: bool,
+    pub seconds: f32,
+    pub hashrate: f32,
+}
+
 //---------------------------------------------------------------------------------------------------- [PayoutView] enum for [Status/P2Pool] tab
 // The enum buttons for selecting which "view" to sort the payout log in.
 #[derive(
@@ -59,6 +59,31 @@ mod test {
             save_before_quit = true
             processes = []
 
+            [gupax.socks5]
+            enabled = false
+            ip = "127.0.0.1"
+            port = "9050"
+            tor = true
+            use_node = true
+            use_p2pool = true
+            use_xmrig = false
+            use_gupaxx = true
+
+            [gupax.notifications]
+            desktop = true
+            webhook = false
+            webhook_url = "http://127.0.0.1:8080/hook"
+            events = ["Payout", "Crash", "XvbWin"]
+
+            [gupax.mqtt]
+            enabled = true
+            host = "192.168.1.10"
+            port = "1883"
+            username = "gupaxx"
+            password = "secret"
+            prefix = "home/rig1"
+            commands = true
+
 			[status]
 			submenu = "P2pool"
 			payout_view = "Oldest"
@@ -66,6 +91,31 @@ mod test {
 			manual_hash = false
 			hashrate = 1241.23
 			hash_metric = "Hash"
+			bench_size = "TenMillion"
+			bench_threads = 4
+			bench_hugepages = false
+
+			[status.remote]
+			name = ""
+			kind = "Xmrig"
+			host = ""
+			port = "18088"
+			token = ""
+
+			[[status.remotes]]
+			name = "rig1"
+			kind = "XmrigProxy"
+			host = "192.168.1.2"
+			port = "18089"
+			token = "token"
+
+			[[status.local_benchmarks]]
+			date = "2025-01-01 12:00"
+			size = "OneMillion"
+			threads = 4
+			hugepages = true
+			seconds = 250.5
+			hashrate = 3992.0
 			
 
 			[p2pool]
@@ -88,6 +138,8 @@ mod test {
             prefer_local_node = true
             console_height = 360
             stratum_port = 3333
+            attach = false
+            attach_data_api = ""
 
             [p2pool.selected_node]
             index = 0
@@ -114,6 +166,16 @@ mod test {
 			port = "3333"
             token = "testtoken"
             console_height = 360
+            attach = false
+            layout = true
+            cores = [0, 2, 4, 6]
+            cpu_priority = 3
+            randomx_mode = "Fast"
+            config_file = false
+            throttle = true
+            temp_soft = 75
+            temp_hard = 88
+            throttle_cooldown = 120
 
 
             [xmrig.selected_pool]
@@ -123,6 +185,15 @@ mod test {
             rpc = "18081"
             zmq_rig = "18083"
 
+            [[xmrig.failover]]
+            name = "backup"
+            ip = "192.168.1.123"
+            port = "3333"
+            user = "linux"
+            rig = "Gupaxx"
+            tls = false
+            keepalive = true
+
 
             [xmrig_proxy]
             simple = true
@@ -142,6 +213,7 @@ mod test {
             token = "testtoken"
             redirect_local_xmrig = true
             console_height = 360
+            attach = false
 
             [xmrig_proxy.selected_pool]
             index = 0
@@ -165,7 +237,8 @@ mod test {
             use_p2pool_sidechain_hr = false
             console_height = 360
             manual_pool_enabled = false
-            manual_pool_eu = true
+            manual_pool = "European"
+            controlled = ["rig1"]
 
             [node]
             simple = false
@@ -183,6 +256,17 @@ mod test {
             path_db = ""
             full_memory = false
             console_height = 360
+            attach = false
+
+            [node.i2p]
+            enabled = false
+            ip = "127.0.0.1"
+            port = "4447"
+            inbound = false
+            inbound_address = ""
+            inbound_port = "18085"
+            inbound_max = 32
+            use_p2pool = false
 
 			[version]
 			gupax = "v1.3.0"
@@ -324,6 +408,33 @@ mod test {
         assert!(merged_state.contains("backup_host = true"));
     }
 
+    // A state saved before the list of XvB pools keeps the pool chosen manually.
+    #[test]
+    fn migrate_state_manual_pool_eu() {
+        let dir = std::env::temp_dir().join(format!("gupaxx_old_state_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.toml");
+        for (eu, pool) in [(false, "North America"), (true, "European")] {
+            let mut old_state = State::to_string(&State::new())
+                .unwrap()
+                .replace("manual_pool_enabled = false", "manual_pool_enabled = true");
+            old_state = old_state.replace(
+                r#"manual_pool = "European""#,
+                &format!("manual_pool_eu = {eu}"),
+            );
+            assert!(!old_state.contains("manual_pool ="));
+            std::fs::write(&path, old_state).unwrap();
+            let state = State::get(&path).unwrap();
+            assert!(state.xvb.manual_pool_enabled);
+            assert_eq!(state.xvb.manual_pool, pool);
+            // the migrated state is saved
+            let saved = std::fs::read_to_string(&path).unwrap();
+            assert!(saved.contains(&format!(r#"manual_pool = "{pool}""#)));
+            assert!(!saved.contains("manual_pool_eu"));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn create_and_serde_gupax_p2pool_api() {
         use crate::disk::gupax_p2pool_api::GupaxP2poolApi;
@@ -385,4 +496,225 @@ mod test {
         assert_eq!(Hash::convert(hash, Hash::Giga, Hash::Mega), 1_000.0);
         assert_eq!(Hash::convert(hash, Hash::Giga, Hash::Giga), 1.0);
     }
+
+    #[test]
+    fn xmrig_config_round_trip() {
+        use crate::disk::state::Xmrig;
+        use crate::disk::xmrig_config::xmrig_config;
+        use serde_json::json;
+        let state = Xmrig {
+            ip: "pool.example.com".to_string(),
+            port: "3333".to_string(),
+            api_port: "18089".to_string(),
+            token: "testtoken".to_string(),
+            current_threads: 2,
+            ..Default::default()
+        };
+        // keys unknown to Gupaxx and the other pools are kept
+        let existing = json!({
+            "donate-level": 0,
+            "http": {"port": 1, "ipv6": true},
+            "cpu": {"rx/wow": [0]},
+            "pools": [
+                {"url": "old:1", "coin": "monero"},
+                {"url": "backup:3333"}
+            ]
+        });
+        let config = xmrig_config(&state, existing, Some("127.0.0.1:9050"), true);
+        assert_eq!(config["donate-level"], 0);
+        assert_eq!(config["http"]["ipv6"], true);
+        assert_eq!(config["http"]["port"], 18089);
+        assert_eq!(config["http"]["access-token"], "testtoken");
+        assert_eq!(config["cpu"]["rx/wow"], json!([0]));
+        assert_eq!(config["cpu"]["rx"], json!([-1, -1]));
+        assert_eq!(config["randomx"]["1gb-pages"], true);
+        assert_eq!(config["pools"][0]["url"], "pool.example.com:3333");
+        assert_eq!(config["pools"][0]["coin"], "monero");
+        assert_eq!(config["pools"][0]["socks5"], "127.0.0.1:9050");
+        assert_eq!(config["pools"][1]["url"], "backup:3333");
+        // the keys needed by the XvB algorithm to switch the pool
+        for key in ["url", "user", "rig-id", "tls", "keepalive"] {
+            assert!(config.pointer(&format!("/pools/0/{key}")).is_some());
+        }
+        // pinned threads, local pool without proxy
+        let state = Xmrig {
+            ip: "localhost".to_string(),
+            layout: true,
+            cores: vec![1, 3],
+            ..state
+        };
+        let config = xmrig_config(&state, json!({}), Some("127.0.0.1:9050"), false);
+        assert_eq!(config["cpu"]["rx"], json!([1, 3]));
+        assert_eq!(config["cpu"]["priority"], 2);
+        assert_eq!(config["pools"][0]["socks5"], serde_json::Value::Null);
+        assert_eq!(config["pools"].as_array().unwrap().len(), 1);
+        // failover pools replace the other pools of the file
+        let state = Xmrig {
+            failover: vec![crate::disk::state::FailoverPool {
+                name: "backup".to_string(),
+                ip: "backup.example.com".to_string(),
+                port: "443".to_string(),
+                user: String::new(),
+                rig: "rig".to_string(),
+                tls: true,
+                keepalive: false,
+            }],
+            address: "4address".to_string(),
+            ..state
+        };
+        let existing = json!({"pools": [{}, {"coin": "monero"}, {"url": "old:1"}]});
+        let config = xmrig_config(&state, existing, Some("127.0.0.1:9050"), false);
+        assert_eq!(config["pools"].as_array().unwrap().len(), 2);
+        assert_eq!(config["pools"][1]["url"], "backup.example.com:443");
+        assert_eq!(config["pools"][1]["user"], "4address");
+        assert_eq!(config["pools"][1]["tls"], true);
+        assert_eq!(config["pools"][1]["coin"], "monero");
+        assert_eq!(config["pools"][1]["socks5"], "127.0.0.1:9050");
+    }
+
+    #[test]
+    fn xvb_history_append_and_read() {
+        use crate::disk::xvb_history::{XvbHistory, XvbRoundLog, get_xvb_history_path};
+        use crate::helper::xvb::rounds::XvbRound;
+
+        let dir = std::env::temp_dir().join(format!("gupaxx_xvb_history_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = get_xvb_history_path(&dir);
+        let mut history = XvbHistory::read(&path).unwrap();
+        assert!(history.rounds.is_empty());
+        let round = XvbRoundLog {
+            timestamp: 1_000,
+            block_height: 3_000_000,
+            round: Some(XvbRound::DonorVip),
+            donor_1hr_avg: 10.5,
+            donor_24hr_avg: 9.8,
+            donated_secs: 1_800,
+            donated_hr_hours: 5_000.0,
+            winner: "4AbCdEfG...12345678".to_string(),
+            won: false,
+        };
+        history.append(round.clone()).unwrap();
+        history
+            .append(XvbRoundLog {
+                timestamp: 5_000,
+                round: None,
+                won: true,
+                ..round.clone()
+            })
+            .unwrap();
+        // an invalid line is skipped
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut f| std::io::Write::write_all(&mut f, b"not json\n"))
+            .unwrap();
+        let history = XvbHistory::read(&path).unwrap();
+        assert_eq!(history.rounds.len(), 2);
+        assert_eq!(history.rounds[0], round);
+        let summary = history.summary(0);
+        assert_eq!(summary.rounds, 2);
+        assert_eq!(summary.participated, 1);
+        assert_eq!(summary.wins, 1);
+        assert_eq!(summary.participation(), 50.0);
+        assert_eq!(summary.donated_hr_hours, 10_000.0);
+        assert_eq!(history.summary(2_000).rounds, 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn xvb_samples_save_and_restore() {
+        use crate::disk::xvb_samples::{XvbCycle, XvbSample, XvbSamples, get_xvb_samples_path};
+
+        let dir = std::env::temp_dir().join(format!("gupaxx_xvb_samples_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = get_xvb_samples_path(&dir);
+        let mut samples = XvbSamples::read(&path).unwrap();
+        assert!(samples.samples.is_empty());
+        let now = 100_000;
+        // too old, dropped when the next sample is pushed
+        samples.push(XvbSample {
+            timestamp: now - 5_000,
+            p2pool: 1.0,
+            xvb: 1.0,
+        });
+        samples.push(XvbSample {
+            timestamp: now - 1_300,
+            p2pool: 800.0,
+            xvb: 200.0,
+        });
+        assert_eq!(samples.samples.len(), 1);
+        // Gupaxx exits after 300 seconds on P2Pool and 60 on XvB
+        samples.cycle = Some(XvbCycle {
+            started: now - 400,
+            updated: now - 40,
+            p2pool_secs: 300,
+            xvb_secs: 60,
+            hashrate: 1_000.0,
+        });
+        samples.save().unwrap();
+        // the temporary file is renamed over the samples
+        assert!(!path.with_extension("json.tmp").exists());
+        let mut samples = XvbSamples::read(&path).unwrap();
+        assert!(samples.cycle.is_some());
+        samples.close_cycle(now);
+        assert!(samples.cycle.is_none());
+        assert_eq!(samples.samples.len(), 2);
+        assert_eq!(
+            samples.slots(now),
+            vec![
+                (0.0, 0.0),
+                (0.0, 0.0),
+                (0.0, 0.0),
+                (800.0, 200.0),
+                (0.0, 0.0),
+                (500.0, 100.0)
+            ]
+        );
+        // an invalid file is an error, the caller starts a new one
+        std::fs::write(&path, "not json").unwrap();
+        assert!(XvbSamples::read(&path).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn xvb_remote_pools_save_and_restore() {
+        use crate::disk::xvb_remote_pools::{XvbRemotePools, get_xvb_remote_pools_path};
+        use crate::helper::xvb::{
+            endpoints::XvbNode,
+            miners::{ControlledMiner, RemotePool},
+            nodes::Pool,
+        };
+
+        let dir =
+            std::env::temp_dir().join(format!("gupaxx_xvb_remote_pools_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = get_xvb_remote_pools_path(&dir);
+        let mut pools = XvbRemotePools::read(&path).unwrap();
+        assert!(pools.is_empty());
+        let remote = |name: &str| RemotePool {
+            miner: ControlledMiner {
+                name: name.to_string(),
+                api_url: format!("http://{name}:18088/1/config"),
+                token: "token".to_string(),
+                hashrate: 1000.0,
+            },
+            previous: serde_json::json!({"url": "pool.example.com:3333", "socks5": "127.0.0.1:9050"}),
+            xvb: Pool::xvb(XvbNode::default()),
+        };
+        // every change is saved
+        pools.insert("rig1".to_string(), remote("rig1"));
+        pools.insert("rig2".to_string(), remote("rig2"));
+        pools.remove("rig2");
+        let mut pools = XvbRemotePools::read(&path).unwrap();
+        assert_eq!(pools.len(), 1);
+        let rig1 = pools.get("rig1").unwrap();
+        assert_eq!(rig1.miner, remote("rig1").miner);
+        assert_eq!(rig1.xvb, remote("rig1").xvb);
+        // the proxy of the rig is kept while it mines on XvB
+        assert_eq!(rig1.socks5(), Some("127.0.0.1:9050"));
+        assert_eq!(pools.take().len(), 1);
+        assert!(XvbRemotePools::read(&path).unwrap().is_empty());
+        // an invalid file is an error, the caller starts a new one
+        std::fs::write(&path, "not json").unwrap();
+        assert!(XvbRemotePools::read(&path).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
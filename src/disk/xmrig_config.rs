@@ -0,0 +1,174 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// The [config.json] of XMRig, generated from [state::Xmrig] when XMRig is started with the advanced settings.
+// Only the keys modeled by Gupaxx are written, every other key already present in the file is kept,
+// so users can add the options Gupaxx does not handle (donate-level, cpu profiles, other pools...).
+// The HTTP API is always enabled with the port and token of the state so that XvB can still switch the pool.
+
+use serde_json::{Map, Value, json};
+
+use super::*;
+use crate::disk::state::Xmrig;
+
+pub fn get_xmrig_config_path(os_data_path: &Path) -> PathBuf {
+    let mut path = os_data_path.to_path_buf();
+    path.push(XMRIG_CONFIG_JSON);
+    path
+}
+
+// Write the config in the file, keeping the keys not modeled by Gupaxx.
+pub fn write_xmrig_config(
+    path: &Path,
+    state: &Xmrig,
+    socks5: Option<&str>,
+    pages_1gb: bool,
+) -> Result<(), TomlError> {
+    let file = File::XmrigConfig;
+    // a file which is not valid JSON is replaced
+    let existing = fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .unwrap_or_else(|| Value::Object(Map::new()));
+    let config = xmrig_config(state, existing, socks5, pages_1gb);
+    let config = serde_json::to_string_pretty(&config)
+        .map_err(|_| TomlError::Parse("[xmrig.json] serialize"))?;
+    match fs::write(path, config) {
+        Ok(_) => {
+            info!("{:?} | Write ... OK", file);
+            Ok(())
+        }
+        Err(e) => {
+            error!("{:?} | Write ... FAIL ... {}", file, e);
+            Err(TomlError::Io(e))
+        }
+    }
+}
+
+// Merge the settings of the state into an existing config.
+pub fn xmrig_config(
+    state: &Xmrig,
+    mut config: Value,
+    socks5: Option<&str>,
+    pages_1gb: bool,
+) -> Value {
+    // XMRig doesn't understand [localhost]
+    let localhost = |ip: &str| {
+        if ip == "localhost" || ip.is_empty() {
+            "127.0.0.1".to_string()
+        } else {
+            ip.to_string()
+        }
+    };
+    let ip = localhost(&state.ip);
+    let api_port = state.api_port.parse().unwrap_or(XMRIG_API_PORT_DEFAULT);
+    // a local pool can not be reached through the proxy
+    let pool_socks5 = socks5.filter(|_| ip != "127.0.0.1");
+    let threads = state.threads();
+    let affinity = if state.layout && !state.cores.is_empty() {
+        state.cores.iter().map(|c| *c as i64).collect()
+    } else {
+        vec![-1; threads as usize]
+    };
+    if !config.is_object() {
+        config = Value::Object(Map::new());
+    }
+    merge(
+        &mut config,
+        json!({
+            "autosave": false,
+            "background": false,
+            "colors": false,
+            "pause-on-active": if state.pause == 0 { json!(false) } else { json!(state.pause) },
+            "http": {
+                "enabled": true,
+                "host": localhost(&state.api_ip),
+                "port": api_port,
+                "access-token": state.token,
+                "restricted": false,
+            },
+            "randomx": {
+                "mode": if state.layout { state.randomx_mode.to_string() } else { "auto".to_string() },
+                "1gb-pages": pages_1gb,
+            },
+            "cpu": {
+                "enabled": true,
+                "priority": if state.layout { json!(state.cpu_priority) } else { Value::Null },
+                "rx": affinity,
+            },
+        }),
+    );
+    // the first pool is the one of the state, followed by the failover pools.
+    // If there is no failover pool, the other pools of the file are kept as they are.
+    let pool = json!({
+        "url": format!("{}:{}", ip, state.port),
+        "user": state.address,
+        "rig-id": state.rig,
+        "tls": state.tls,
+        "keepalive": state.keepalive,
+        "enabled": true,
+        "socks5": pool_socks5,
+    });
+    let failover = state.failover.iter().map(|pool| {
+        let url = pool.url();
+        json!({
+            "url": url,
+            "user": if pool.user.is_empty() { &state.address } else { &pool.user },
+            "rig-id": pool.rig,
+            "tls": pool.tls,
+            "keepalive": pool.keepalive,
+            "enabled": true,
+            "socks5": socks5.filter(|_| !url.starts_with("127.0.0.1:")),
+        })
+    });
+    if !config.get("pools").is_some_and(Value::is_array) {
+        config["pools"] = json!([]);
+    }
+    if let Some(pools) = config.get_mut("pools").and_then(Value::as_array_mut) {
+        let modeled = std::iter::once(pool).chain(failover).collect::<Vec<_>>();
+        if modeled.len() > 1 {
+            pools.truncate(modeled.len());
+        }
+        // merged by position to keep the keys added by hand to a pool
+        for (i, pool) in modeled.into_iter().enumerate() {
+            match pools.get_mut(i) {
+                Some(existing) => merge(existing, pool),
+                None => pools.push(pool),
+            }
+        }
+    }
+    config
+}
+
+// Write every key of [new] in [config], recursively for objects.
+fn merge(config: &mut Value, new: Value) {
+    match (config, new) {
+        (Value::Object(config), Value::Object(new)) => {
+            for (key, value) in new {
+                match config.get_mut(&key) {
+                    Some(existing) if existing.is_object() && value.is_object() => {
+                        merge(existing, value)
+                    }
+                    _ => {
+                        config.insert(key, value);
+                    }
+                }
+            }
+        }
+        (config, new) => *config = new,
+    }
+}
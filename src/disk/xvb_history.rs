@@ -0,0 +1,137 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// History of the XvB rounds seen by the XvB process.
+// One JSON object per line is appended at the end of every round, so the file is never rewritten.
+
+use std::io::Write;
+
+use super::*;
+use crate::helper::xvb::rounds::XvbRound;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct XvbRoundLog {
+    // UNIX timestamp of the end of the round
+    pub timestamp: i64,
+    pub block_height: u64,
+    // round type we qualified for, None without share in the PPLNS window
+    pub round: Option<XvbRound>,
+    // donated hashrate in kH/s, as given by the private API
+    pub donor_1hr_avg: f32,
+    pub donor_24hr_avg: f32,
+    // time mined on XvB during the round and the hashrate sent in H/s * hours
+    pub donated_secs: u32,
+    pub donated_hr_hours: f64,
+    pub winner: String,
+    pub won: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct XvbHistory {
+    pub path: PathBuf,
+    pub rounds: Vec<XvbRoundLog>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct XvbHistorySummary {
+    pub rounds: usize,
+    pub participated: usize,
+    pub wins: usize,
+    pub donated_hr_hours: f64,
+}
+
+impl XvbHistorySummary {
+    pub fn participation(&self) -> f32 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        self.participated as f32 / self.rounds as f32 * 100.0
+    }
+}
+
+pub fn get_xvb_history_path(os_data_path: &Path) -> PathBuf {
+    let mut path = os_data_path.to_path_buf();
+    path.push(XVB_ROUNDS_LOG);
+    path
+}
+
+impl XvbHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // A missing file is an empty history, lines that can not be parsed are skipped.
+    pub fn read(path: &Path) -> Result<Self, TomlError> {
+        let file = File::XvbRounds;
+        let mut history = Self {
+            path: path.to_path_buf(),
+            rounds: Vec::new(),
+        };
+        if !path.exists() {
+            info!("{:?} | [{}] does not exist yet", file, path.display());
+            return Ok(history);
+        }
+        let string = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("{:?} | Read ... FAIL ... {}", file, e);
+                return Err(TomlError::Io(e));
+            }
+        };
+        for line in string.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(round) => history.rounds.push(round),
+                Err(e) => warn!("{:?} | Skipping invalid line ... {}", file, e),
+            }
+        }
+        info!("{:?} | Read {} rounds ... OK", file, history.rounds.len());
+        Ok(history)
+    }
+    pub fn append(&mut self, round: XvbRoundLog) -> Result<(), TomlError> {
+        let file = File::XvbRounds;
+        let line = serde_json::to_string(&round)
+            .map_err(|_| TomlError::Parse("[xvb_rounds] serialize"))?;
+        self.rounds.push(round);
+        let result = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut f| writeln!(f, "{line}"));
+        match result {
+            Ok(_) => {
+                info!("{:?} | Append ... OK", file);
+                Ok(())
+            }
+            Err(e) => {
+                error!("{:?} | Append ... FAIL ... {}", file, e);
+                Err(TomlError::Io(e))
+            }
+        }
+    }
+    // Summary of the rounds ended after [since] (UNIX timestamp).
+    pub fn summary(&self, since: i64) -> XvbHistorySummary {
+        self.rounds.iter().filter(|r| r.timestamp >= since).fold(
+            XvbHistorySummary::default(),
+            |mut s, r| {
+                s.rounds += 1;
+                s.participated += r.round.is_some() as usize;
+                s.wins += r.won as usize;
+                s.donated_hr_hours += r.donated_hr_hours;
+                s
+            },
+        )
+    }
+}
@@ -0,0 +1,141 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Remote miners of the fleet sent to XvB by the algorithm, with the pool to send them back to.
+// It is written at every change, so the miners are still sent back after a restart of Gupaxx.
+
+use std::collections::HashMap;
+
+use super::*;
+use crate::helper::xvb::{miners::RemotePool, nodes::Pool};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct XvbRemotePools {
+    // not saved if empty, as for the API used without files.
+    #[serde(skip)]
+    pub path: PathBuf,
+    pools: HashMap<String, RemotePool>,
+}
+
+pub fn get_xvb_remote_pools_path(os_data_path: &Path) -> PathBuf {
+    let mut path = os_data_path.to_path_buf();
+    path.push(XVB_REMOTE_POOLS_JSON);
+    path
+}
+
+impl XvbRemotePools {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            ..Default::default()
+        }
+    }
+    // A missing file is no remote miner on XvB.
+    pub fn read(path: &Path) -> Result<Self, TomlError> {
+        let file = File::XvbRemotePools;
+        if !path.exists() {
+            info!("{:?} | [{}] does not exist yet", file, path.display());
+            return Ok(Self::new(path));
+        }
+        let string = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("{:?} | Read ... FAIL ... {}", file, e);
+                return Err(TomlError::Io(e));
+            }
+        };
+        match serde_json::from_str::<Self>(&string) {
+            Ok(pools) => {
+                info!("{:?} | Read {} remote miners ... OK", file, pools.len());
+                Ok(Self {
+                    path: path.to_path_buf(),
+                    ..pools
+                })
+            }
+            Err(e) => {
+                error!("{:?} | Parse ... FAIL ... {}", file, e);
+                Err(TomlError::Parse("[xvb_remote_pools] deserialize"))
+            }
+        }
+    }
+    // Written to a temporary file renamed over the previous one,
+    // so that a crash while saving does not lose the pools of the miners.
+    pub fn save(&self) -> Result<(), TomlError> {
+        if self.path.as_os_str().is_empty() {
+            return Ok(());
+        }
+        let file = File::XvbRemotePools;
+        let string = serde_json::to_string(self)
+            .map_err(|_| TomlError::Parse("[xvb_remote_pools] serialize"))?;
+        let tmp = self.path.with_extension("json.tmp");
+        match fs::write(&tmp, string).and_then(|_| fs::rename(&tmp, &self.path)) {
+            Ok(_) => {
+                debug!("{:?} | Save ... OK", file);
+                Ok(())
+            }
+            Err(e) => {
+                error!("{:?} | Save ... FAIL ... {}", file, e);
+                Err(TomlError::Io(e))
+            }
+        }
+    }
+    // Every change is saved, an error is only logged since the miners are still known until the exit.
+    fn changed(&self) {
+        if let Err(e) = self.save() {
+            warn!(
+                "{:?} | Could not save the remote miners: {}",
+                File::XvbRemotePools,
+                e
+            );
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+    pub fn get(&self, name: &str) -> Option<&RemotePool> {
+        self.pools.get(name)
+    }
+    pub fn insert(&mut self, name: String, pool: RemotePool) {
+        self.pools.insert(name, pool);
+        self.changed();
+    }
+    pub fn remove(&mut self, name: &str) -> Option<RemotePool> {
+        let pool = self.pools.remove(name);
+        if pool.is_some() {
+            self.changed();
+        }
+        pool
+    }
+    // The miner is now on this XvB pool.
+    pub fn set_xvb(&mut self, name: &str, xvb: Pool) {
+        if let Some(remote) = self.pools.get_mut(name) {
+            remote.xvb = xvb;
+            self.changed();
+        }
+    }
+    // All the miners, to send them back to their pool.
+    pub fn take(&mut self) -> HashMap<String, RemotePool> {
+        let pools = std::mem::take(&mut self.pools);
+        if !pools.is_empty() {
+            self.changed();
+        }
+        pools
+    }
+}
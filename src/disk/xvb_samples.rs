@@ -0,0 +1,163 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Hashrate sent to P2Pool and XvB by the algorithm during the last hour.
+// It is used to know which part of the hashrate seen by P2Pool and XvB is external,
+// so it is written at every tick of the algorithm to survive a restart of Gupaxx.
+// A cycle interrupted by the restart is kept for the time it was mined.
+
+use super::*;
+use crate::XVB_TIME_ALGO;
+
+// Samples older than this are dropped.
+pub const XVB_SAMPLES_MAX_AGE: i64 = 3600;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct XvbSample {
+    // UNIX timestamp of the end of the cycle
+    pub timestamp: i64,
+    // hashrate in H/s averaged over a whole cycle
+    pub p2pool: f32,
+    pub xvb: f32,
+}
+
+// Cycle of the algorithm in progress.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct XvbCycle {
+    pub started: i64,
+    // UNIX timestamp of the last tick
+    pub updated: i64,
+    // time already mined on each pool
+    pub p2pool_secs: u32,
+    pub xvb_secs: u32,
+    // hashrate of the miner at the last tick, in H/s
+    pub hashrate: f32,
+}
+
+impl XvbCycle {
+    // The part of the cycle that was mined, as a sample.
+    pub fn sample(&self) -> XvbSample {
+        let part = |secs: u32| self.hashrate * secs as f32 / XVB_TIME_ALGO as f32;
+        XvbSample {
+            timestamp: self.updated,
+            p2pool: part(self.p2pool_secs),
+            xvb: part(self.xvb_secs),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct XvbSamples {
+    #[serde(skip)]
+    pub path: PathBuf,
+    pub samples: Vec<XvbSample>,
+    pub cycle: Option<XvbCycle>,
+}
+
+pub fn get_xvb_samples_path(os_data_path: &Path) -> PathBuf {
+    let mut path = os_data_path.to_path_buf();
+    path.push(XVB_SAMPLES_JSON);
+    path
+}
+
+impl XvbSamples {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // A missing file is an empty history.
+    pub fn read(path: &Path) -> Result<Self, TomlError> {
+        let file = File::XvbSamples;
+        if !path.exists() {
+            info!("{:?} | [{}] does not exist yet", file, path.display());
+            return Ok(Self {
+                path: path.to_path_buf(),
+                ..Default::default()
+            });
+        }
+        let string = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("{:?} | Read ... FAIL ... {}", file, e);
+                return Err(TomlError::Io(e));
+            }
+        };
+        match serde_json::from_str::<Self>(&string) {
+            Ok(samples) => {
+                info!("{:?} | Read {} samples ... OK", file, samples.samples.len());
+                Ok(Self {
+                    path: path.to_path_buf(),
+                    ..samples
+                })
+            }
+            Err(e) => {
+                error!("{:?} | Parse ... FAIL ... {}", file, e);
+                Err(TomlError::Parse("[xvb_samples] deserialize"))
+            }
+        }
+    }
+    // Written to a temporary file renamed over the previous one,
+    // so that a crash while saving does not lose the history.
+    pub fn save(&self) -> Result<(), TomlError> {
+        let file = File::XvbSamples;
+        let string =
+            serde_json::to_string(self).map_err(|_| TomlError::Parse("[xvb_samples] serialize"))?;
+        let tmp = self.path.with_extension("json.tmp");
+        match fs::write(&tmp, string).and_then(|_| fs::rename(&tmp, &self.path)) {
+            Ok(_) => {
+                debug!("{:?} | Save ... OK", file);
+                Ok(())
+            }
+            Err(e) => {
+                error!("{:?} | Save ... FAIL ... {}", file, e);
+                Err(TomlError::Io(e))
+            }
+        }
+    }
+    // Add the sample of an ended cycle, the cycle in progress is done.
+    pub fn push(&mut self, sample: XvbSample) {
+        self.cycle = None;
+        self.samples.push(sample);
+        self.prune(sample.timestamp);
+    }
+    // Keep the interrupted cycle as a sample, for the time it was mined.
+    pub fn close_cycle(&mut self, now: i64) {
+        if let Some(cycle) = self.cycle.take() {
+            if cycle.p2pool_secs + cycle.xvb_secs > 0 {
+                self.samples.push(cycle.sample());
+            }
+        }
+        self.prune(now);
+    }
+    fn prune(&mut self, now: i64) {
+        self.samples
+            .retain(|s| now - s.timestamp < XVB_SAMPLES_MAX_AGE);
+    }
+    // Samples of the last hour put in the slot of the cycle they ended in, the oldest first.
+    // A slot without sample is a cycle where nothing was sent.
+    pub fn slots(&self, now: i64) -> Vec<(f32, f32)> {
+        let capacity = (XVB_SAMPLES_MAX_AGE / XVB_TIME_ALGO as i64) as usize;
+        let mut slots = vec![(0.0, 0.0); capacity];
+        for sample in &self.samples {
+            let age = (now - sample.timestamp).max(0) / XVB_TIME_ALGO as i64;
+            if let Some(i) = capacity.checked_sub(age as usize + 1) {
+                slots[i].0 += sample.p2pool;
+                slots[i].1 += sample.xvb;
+            }
+        }
+        slots
+    }
+}
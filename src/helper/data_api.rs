@@ -0,0 +1,173 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Event-driven ingestion of the P2Pool data-api files.
+// A file watcher (inotify on Linux) flags every file rewritten by P2Pool,
+// so the watchdog only reads and parses the files that changed.
+// If the watcher can not be created or fails, every file is read on each loop like before.
+// The directories of an attached P2Pool are not owned by Gupaxx and are never created,
+// so missing ones mean polling until the next start.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use log::{debug, info, warn};
+use notify::{
+    Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+    event::{AccessKind, AccessMode},
+};
+
+// read the file even without event after this delay, in case one was missed.
+const POLL_FALLBACK: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataApiFile {
+    Local,
+    Network,
+    Pool,
+    P2p,
+}
+
+impl DataApiFile {
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+pub struct DataApiWatcher {
+    changed: Arc<[AtomicBool; 4]>,
+    failed: Arc<AtomicBool>,
+    last_read: [Instant; 4],
+    // events stop when the watcher is dropped
+    watcher: Option<RecommendedWatcher>,
+}
+
+impl DataApiWatcher {
+    // paths in order: local, network, pool, p2p
+    // create_dirs: create the missing directories, only when P2Pool is started by Gupaxx.
+    pub fn new(local: &Path, network: &Path, pool: &Path, p2p: &Path, create_dirs: bool) -> Self {
+        let paths = [
+            local.to_path_buf(),
+            network.to_path_buf(),
+            pool.to_path_buf(),
+            p2p.to_path_buf(),
+        ];
+        // everything must be read at least once
+        let changed = Arc::new([const { AtomicBool::new(true) }; 4]);
+        let failed = Arc::new(AtomicBool::new(false));
+        let watcher = match Self::watch(&paths, &changed, &failed, create_dirs) {
+            Ok(watcher) => {
+                info!("P2Pool | Watching data-api files for changes ... OK");
+                Some(watcher)
+            }
+            Err(e) => {
+                warn!(
+                    "P2Pool | Watching data-api files for changes ... FAIL ... {}, falling back to polling",
+                    e
+                );
+                None
+            }
+        };
+        Self {
+            changed,
+            failed,
+            last_read: [Instant::now(); 4],
+            watcher,
+        }
+    }
+    fn watch(
+        paths: &[PathBuf; 4],
+        changed: &Arc<[AtomicBool; 4]>,
+        failed: &Arc<AtomicBool>,
+        create_dirs: bool,
+    ) -> notify::Result<RecommendedWatcher> {
+        let watched = paths.clone();
+        let changed = Arc::clone(changed);
+        let failed = Arc::clone(failed);
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+                Ok(event) => {
+                    // reads of the files (including ours) must not trigger a new read
+                    let written = matches!(
+                        event.kind,
+                        EventKind::Create(_)
+                            | EventKind::Modify(_)
+                            | EventKind::Access(AccessKind::Close(AccessMode::Write))
+                    );
+                    if !written {
+                        return;
+                    }
+                    for path in &event.paths {
+                        if let Some(i) = watched.iter().position(|p| p == path) {
+                            debug!("P2Pool | data-api file changed: {}", path.display());
+                            changed[i].store(true, Ordering::Relaxed);
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "P2Pool | data-api file watcher error: {}, falling back to polling",
+                        e
+                    );
+                    failed.store(true, Ordering::Relaxed);
+                }
+            })?;
+        // Watch the directories, the files are replaced by P2Pool.
+        // They are created now so they can be watched before P2Pool creates them.
+        // Watching a missing directory fails, so polling is used instead.
+        let mut dirs: Vec<&Path> = paths.iter().filter_map(|p| p.parent()).collect();
+        dirs.sort();
+        dirs.dedup();
+        for dir in dirs {
+            if create_dirs {
+                std::fs::create_dir_all(dir)?;
+            }
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        Ok(watcher)
+    }
+    // Is the watcher working, false means polling on every loop.
+    pub fn is_watching(&self) -> bool {
+        self.watcher.is_some() && !self.failed.load(Ordering::Relaxed)
+    }
+    // Returns true if one of the files must be read, because it was rewritten since the last call,
+    // the watcher is not working or the file was not read since [POLL_FALLBACK].
+    // The files are then considered as read.
+    pub fn take(&mut self, files: &[DataApiFile]) -> bool {
+        let watching = self.is_watching();
+        let mut read = false;
+        for file in files {
+            let i = file.index();
+            let changed = self.changed[i].swap(false, Ordering::Relaxed);
+            if !watching || changed || self.last_read[i].elapsed() >= POLL_FALLBACK {
+                read = true;
+            }
+        }
+        if read {
+            for file in files {
+                self.last_read[file.index()] = Instant::now();
+            }
+        }
+        read
+    }
+}
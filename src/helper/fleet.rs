@@ -0,0 +1,201 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Monitoring of the XMRig, XMRig-Proxy and Node instances running on other hosts.
+// They are polled with the same requests used for the processes started by Gupaxx,
+// the results are shown in the [Fleet] submenu of the [Status] tab.
+
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use log::{debug, info};
+use reqwest::Client;
+
+use crate::{
+    XMRIG_API_SUMMARY_ENDPOINT,
+    disk::{
+        state::Node,
+        status::{RemoteInstance, RemoteKind},
+    },
+    helper::{
+        Helper,
+        node::{PrivNodeApi, PubNodeApi},
+        xrig::{
+            xmrig::{PrivXmrigApi, PubXmrigApi},
+            xmrig_proxy::{PrivXmrigProxyApi, PubXmrigProxyApi},
+        },
+    },
+    macros::arc_mut,
+    miscs::http_client,
+};
+
+// delay between two polls of the remote instances.
+const FLEET_INTERVAL: Duration = Duration::from_secs(5);
+// an instance not answering in time is shown as unreachable until the next poll.
+const FLEET_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Default)]
+pub struct PubFleetApi {
+    // instances to poll, kept in sync with the state by the GUI.
+    pub instances: Vec<RemoteInstance>,
+    // result of the last poll
+    pub stats: Vec<RemoteStats>,
+}
+
+impl PubFleetApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // stats of an instance, if it was polled since it was added or modified.
+    pub fn find(&self, instance: &RemoteInstance) -> Option<&RemoteStats> {
+        self.stats.iter().find(|s| &s.instance == instance)
+    }
+}
+
+#[derive(Clone)]
+pub struct RemoteStats {
+    pub instance: RemoteInstance,
+    // None if the API responded
+    pub error: Option<String>,
+    pub api: RemoteApi,
+}
+
+#[derive(Clone)]
+pub enum RemoteApi {
+    Xmrig(Box<PubXmrigApi>),
+    XmrigProxy(PubXmrigProxyApi),
+    Node(PubNodeApi),
+}
+
+impl RemoteStats {
+    async fn request(
+        client: &reqwest_middleware::ClientWithMiddleware,
+        client_node: &Client,
+        instance: RemoteInstance,
+    ) -> Self {
+        let uri = format!(
+            "http://{}:{}/{}",
+            instance.host, instance.port, XMRIG_API_SUMMARY_ENDPOINT
+        );
+        let (error, api) = match instance.kind {
+            RemoteKind::Xmrig => {
+                let public = arc_mut!(PubXmrigApi::new());
+                let error =
+                    match PrivXmrigApi::request_xmrig_api(client, &uri, &instance.token).await {
+                        Ok(private) => {
+                            PubXmrigApi::update_from_priv(&public, private);
+                            None
+                        }
+                        Err(e) => Some(e.to_string()),
+                    };
+                let public = public.lock().unwrap().clone();
+                (error, RemoteApi::Xmrig(Box::new(public)))
+            }
+            RemoteKind::XmrigProxy => {
+                let public = arc_mut!(PubXmrigProxyApi::new());
+                let error =
+                    match PrivXmrigProxyApi::request_xp_api(client, &uri, &instance.token).await {
+                        Ok(private) => {
+                            PubXmrigProxyApi::update_from_priv(&public, private);
+                            None
+                        }
+                        Err(e) => Some(e.to_string()),
+                    };
+                let public = public.lock().unwrap().clone();
+                (error, RemoteApi::XmrigProxy(public))
+            }
+            RemoteKind::Node => {
+                // the request only needs the address of the RPC
+                let state = Node {
+                    api_ip: instance.host.clone(),
+                    api_port: instance.port.clone(),
+                    ..Default::default()
+                };
+                let public = arc_mut!(PubNodeApi::new());
+                let error = match PrivNodeApi::request_api(client_node, &state).await {
+                    Ok(private) => {
+                        PubNodeApi::update_from_priv(&public, private);
+                        None
+                    }
+                    Err(e) => Some(e.to_string()),
+                };
+                let public = public.lock().unwrap().clone();
+                (error, RemoteApi::Node(public))
+            }
+        };
+        if let Some(e) = &error {
+            debug!(
+                "Fleet | Could not send API request to {} ({}:{})\n{}",
+                instance.name, instance.host, instance.port, e
+            );
+        }
+        Self {
+            instance,
+            error,
+            api,
+        }
+    }
+}
+
+impl Helper {
+    pub fn spawn_fleet_watchdog(helper: &Arc<Mutex<Self>>) {
+        let fleet = Arc::clone(&helper.lock().unwrap().fleet);
+        thread::spawn(move || {
+            Self::fleet_watchdog(&fleet);
+        });
+    }
+    #[tokio::main]
+    async fn fleet_watchdog(fleet: &Arc<Mutex<PubFleetApi>>) {
+        // the requests of XMRig and XMRig-Proxy have their own timeout, they are not retried
+        // so that an unreachable instance does not block the poll, the next poll is the retry.
+        let client = reqwest_middleware::ClientBuilder::new(http_client(None)).build();
+        // without a timeout, an unreachable Node would block the poll for minutes.
+        let client_node = Client::builder()
+            .timeout(FLEET_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        info!("Fleet | Entering watchdog mode... woof!");
+        loop {
+            let now = Instant::now();
+            let instances = fleet.lock().unwrap().instances.clone();
+            // every instance is polled at the same time, so that an offline one does not delay the others.
+            let handles = instances
+                .into_iter()
+                .map(|instance| {
+                    let client = client.clone();
+                    let client_node = client_node.clone();
+                    tokio::spawn(async move {
+                        RemoteStats::request(&client, &client_node, instance).await
+                    })
+                })
+                .collect::<Vec<_>>();
+            let mut stats = Vec::with_capacity(handles.len());
+            for handle in handles {
+                if let Ok(stat) = handle.await {
+                    stats.push(stat);
+                }
+            }
+            fleet.lock().unwrap().stats = stats;
+            if let Some(wait) = FLEET_INTERVAL.checked_sub(now.elapsed()) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+    }
+}
@@ -41,11 +41,19 @@ use crate::helper::{
     p2pool::{ImgP2pool, PubP2poolApi},
     xrig::{xmrig::ImgXmrig, xmrig::PubXmrigApi},
 };
-use crate::{constants::*, disk::gupax_p2pool_api::GupaxP2poolApi, human::*, macros::*};
+use crate::{
+    constants::*,
+    disk::{gupax_p2pool_api::GupaxP2poolApi, xvb_history::XvbHistory, xvb_samples::XvbSamples},
+    human::*,
+    macros::*,
+};
 use derive_more::derive::Display;
 use enclose::enc;
+use fleet::PubFleetApi;
 use log::*;
+use mqtt::PubMqttApi;
 use node::{ImgNode, PubNodeApi};
+use notify::PubNotifyApi;
 use port_check::is_port_reachable_with_timeout;
 use portable_pty::Child;
 use readable::up::Uptime;
@@ -62,8 +70,12 @@ use std::{
 use strum::{EnumCount, EnumIter};
 use xrig::xmrig_proxy::ImgProxy;
 
-use self::xvb::{PubXvbApi, nodes::Pool};
+use self::xvb::{PubXvbApi, endpoints::XvbEndpoints, nodes::Pool};
+pub mod data_api;
+pub mod fleet;
+pub mod mqtt;
 pub mod node;
+pub mod notify;
 pub mod p2pool;
 pub mod tests;
 pub mod xrig;
@@ -106,9 +118,15 @@ pub struct Helper {
     pub_api_node: Arc<Mutex<PubNodeApi>>,     // Node API state (for Helper/Node thread)
     pub_api_xvb: Arc<Mutex<PubXvbApi>>,       // XvB API state (for Helper/XvB thread)
     pub gupax_p2pool_api: Arc<Mutex<GupaxP2poolApi>>, //
+    pub xvb_history: Arc<Mutex<XvbHistory>>,  // Rounds of XvB written by the XvB thread
+    pub xvb_samples: Arc<Mutex<XvbSamples>>, // Hashrate sent by the XvB algorithm, written by the XvB thread
+    pub xvb_endpoints: Arc<Mutex<XvbEndpoints>>, // Endpoints of XvB, the pools are refreshed by the XvB thread
     pub ip_public: Arc<Mutex<Option<Ipv4Addr>>>,
     pub ip_local: Arc<Mutex<Option<IpAddr>>>,
     pub proxy_port_reachable: Arc<Mutex<bool>>,
+    pub fleet: Arc<Mutex<PubFleetApi>>, // Remote instances polled by the fleet thread
+    pub notify: Arc<Mutex<PubNotifyApi>>, // Settings of the notifications, read by the notify thread
+    pub mqtt: Arc<Mutex<PubMqttApi>>, // Settings of the MQTT client and commands received by the MQTT thread
 }
 
 // The communication between the data here and the GUI thread goes as follows:
@@ -128,6 +146,9 @@ pub struct Sys {
     pub system_cpu_model: String,
     pub system_memory: String,
     pub system_cpu_usage: String,
+    pub system_cpu_temp: String,
+    // read by the XMRig watchdog to throttle the threads
+    pub cpu_temp: Option<f32>,
 }
 
 impl Sys {
@@ -137,6 +158,8 @@ impl Sys {
             gupax_cpu_usage: "???%".to_string(),
             gupax_memory_used_mb: "??? megabytes".to_string(),
             system_cpu_usage: "???%".to_string(),
+            system_cpu_temp: "???°C".to_string(),
+            cpu_temp: None,
             system_memory: "???GB / ???GB".to_string(),
             system_cpu_model: "???".to_string(),
         }
@@ -148,6 +171,21 @@ impl Default for Sys {
     }
 }
 
+// The hottest sensor of the CPU, other sensors (disks, GPU, motherboard) are ignored.
+// Labels are [hwmon name + sensor] on Linux, like [coretemp Package id 0] or [k10temp Tctl].
+pub fn cpu_temperature<'a>(sensors: impl Iterator<Item = (&'a str, Option<f32>)>) -> Option<f32> {
+    const CPU_LABELS: [&str; 7] = [
+        "package", "tctl", "tdie", "cpu", "coretemp", "k10temp", "zenpower",
+    ];
+    sensors
+        .filter(|(label, _)| {
+            let label = label.to_lowercase();
+            CPU_LABELS.iter().any(|l| label.contains(l))
+        })
+        .filter_map(|(_, temp)| temp.filter(|t| t.is_finite()))
+        .reduce(f32::max)
+}
+
 //---------------------------------------------------------------------------------------------------- [Process] Struct
 // This holds all the state of a (child) process.
 // The main GUI thread will use this to display console text, online state, etc.
@@ -405,6 +443,9 @@ impl Helper {
         ip_local: Arc<Mutex<Option<IpAddr>>>,
         ip_public: Arc<Mutex<Option<Ipv4Addr>>>,
         proxy_port_reachable: Arc<Mutex<bool>>,
+        fleet: Arc<Mutex<PubFleetApi>>,
+        notify: Arc<Mutex<PubNotifyApi>>,
+        mqtt: Arc<Mutex<PubMqttApi>>,
     ) -> Self {
         Self {
             instant,
@@ -431,9 +472,16 @@ impl Helper {
             img_xmrig,
             img_proxy,
             gupax_p2pool_api,
+            // read from disk when initializing [App], like [gupax_p2pool_api]
+            xvb_history: arc_mut!(XvbHistory::new()),
+            xvb_samples: arc_mut!(XvbSamples::new()),
+            xvb_endpoints: arc_mut!(XvbEndpoints::default()),
             ip_local,
             ip_public,
             proxy_port_reachable,
+            fleet,
+            notify,
+            mqtt,
         }
     }
 
@@ -478,6 +526,7 @@ impl Helper {
     #[inline(always)] // called once
     fn update_pub_sys_from_sysinfo(
         sysinfo: &sysinfo::System,
+        components: &sysinfo::Components,
         pub_sys: &mut Sys,
         pid: &sysinfo::Pid,
         helper: &Helper,
@@ -505,11 +554,23 @@ impl Helper {
             }
             format!("{:.2}%", total / (max_threads as f32))
         };
+        let cpu_temp = cpu_temperature(
+            components
+                .list()
+                .iter()
+                .map(|c| (c.label(), c.temperature())),
+        );
+        let system_cpu_temp = match cpu_temp {
+            Some(temp) => format!("{:.1}°C", temp),
+            None => "???°C".to_string(),
+        };
         *pub_sys = Sys {
             gupax_uptime,
             gupax_cpu_usage,
             gupax_memory_used_mb,
             system_cpu_usage,
+            system_cpu_temp,
+            cpu_temp,
             system_memory,
             system_cpu_model,
         };
@@ -563,6 +624,7 @@ impl Helper {
 
         let sysinfo_cpu = sysinfo::CpuRefreshKind::everything();
         let sysinfo_processes = sysinfo::ProcessRefreshKind::nothing().with_cpu();
+        let mut components = sysinfo::Components::new_with_refreshed_list();
 
         thread::spawn(move || {
             info!(
@@ -653,18 +715,21 @@ impl Helper {
 
                 // 2. Selectively refresh [sysinfo] for only what we need (better performance).
                 sysinfo.refresh_cpu_specifics(sysinfo_cpu);
-                debug!("Helper | Sysinfo refresh (1/3) ... [cpu]");
+                debug!("Helper | Sysinfo refresh (1/4) ... [cpu]");
                 sysinfo.refresh_processes_specifics(
                     sysinfo::ProcessesToUpdate::All,
                     false,
                     sysinfo_processes,
                 );
-                debug!("Helper | Sysinfo refresh (2/3) ... [processes]");
+                debug!("Helper | Sysinfo refresh (2/4) ... [processes]");
                 sysinfo.refresh_memory();
-                debug!("Helper | Sysinfo refresh (3/3) ... [memory]");
+                debug!("Helper | Sysinfo refresh (3/4) ... [memory]");
+                components.refresh(false);
+                debug!("Helper | Sysinfo refresh (4/4) ... [components]");
                 debug!("Helper | Sysinfo OK, running [update_pub_sys_from_sysinfo()]");
                 Self::update_pub_sys_from_sysinfo(
                     &sysinfo,
+                    &components,
                     &mut lock_pub_sys,
                     &pid,
                     &lock,
@@ -928,6 +993,42 @@ fn signal_end(
     }
     false
 }
+// Signals for a process attached with attach mode.
+// There is no child to kill, Stop only detaches Gupaxx from the process and Restart attaches it again.
+fn signal_end_attached(
+    process: &mut Process,
+    start: &Instant,
+    gui_api_output_raw: &mut String,
+) -> bool {
+    match process.signal {
+        ProcessSignal::Stop => process.state = ProcessState::Dead,
+        ProcessSignal::Restart => process.state = ProcessState::Waiting,
+        _ => return false,
+    }
+    debug!(
+        "{} Watchdog | {:?} SIGNAL caught",
+        process.name, process.signal
+    );
+    let uptime = HumanTime::into_human(start.elapsed());
+    info!(
+        "{} Watchdog | Detached ... Uptime was: [{}]",
+        process.name,
+        uptime.display(false)
+    );
+    let name = process.name.to_owned();
+    if let Err(e) = writeln!(
+        gui_api_output_raw,
+        "{}\n{} detached | Uptime: [{}] | The external process is still running\n{}\n\n\n\n",
+        name,
+        HORI_CONSOLE,
+        uptime.display(false),
+        HORI_CONSOLE
+    ) {
+        error!("{} Watchdog | GUI Uptime write failed: {}", name, e);
+    }
+    process.signal = ProcessSignal::None;
+    true
+}
 async fn sleep_end_loop(now: Instant, name: ProcessName) {
     // Sleep (only if 999ms hasn't passed)
     let elapsed = now.elapsed().as_millis();
@@ -0,0 +1,415 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// MQTT client publishing the state of the processes for home automation (Home Assistant, ...).
+// Values are published retained under [prefix/process/...] when they change,
+// [prefix/status] is [online] while Gupaxx is connected and [offline] otherwise.
+// If enabled, commands received on [prefix/process/set] (start, stop, restart) and
+// [prefix/xmrig/threads/set] are given to the GUI thread, which has the state needed to run them.
+// A repaint is requested for each command so they do not wait for the next input of the user.
+// The threads given by a command are used until the next start of Gupaxx, the saved state is not modified.
+// Retained messages are ignored, they would run an old command again at each connection.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use log::{debug, info, warn};
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS};
+
+use crate::{
+    disk::{
+        gupax_p2pool_api::GupaxP2poolApi,
+        state::{Gupax, Mqtt},
+    },
+    helper::{
+        Helper, Process, ProcessName, ProcessSignal, ProcessState,
+        p2pool::PubP2poolApi,
+        xrig::{
+            xmrig::{ImgXmrig, PubXmrigApi},
+            xmrig_proxy::PubXmrigProxyApi,
+        },
+        xvb::PubXvbApi,
+    },
+    utils::xmr::AtomicUnit,
+};
+
+// delay between two publications of the values.
+const MQTT_INTERVAL: Duration = Duration::from_secs(5);
+// delay before connecting again after an error.
+const MQTT_RETRY: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MqttCommand {
+    Signal(ProcessName, ProcessSignal),
+    Threads(u16),
+}
+
+#[derive(Clone, Default)]
+pub struct PubMqttApi {
+    // kept in sync with the state by the GUI.
+    pub settings: Mqtt,
+    pub connected: bool,
+    // last error of the connection
+    pub error: Option<String>,
+    // received commands, run by the GUI thread.
+    pub commands: Vec<MqttCommand>,
+    // context of the GUI, to wake it up when a command is received.
+    pub ctx: Option<egui::Context>,
+}
+
+impl PubMqttApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // settings of the state, also after a reset of the settings.
+    pub fn sync(&mut self, gupax: &Gupax) {
+        if self.settings != gupax.mqtt {
+            self.settings.clone_from(&gupax.mqtt);
+        }
+    }
+}
+
+// Name of the process in the topics.
+pub fn topic_name(name: ProcessName) -> &'static str {
+    match name {
+        ProcessName::Node => "node",
+        ProcessName::P2pool => "p2pool",
+        ProcessName::Xmrig => "xmrig",
+        ProcessName::XmrigProxy => "xmrig_proxy",
+        ProcessName::Xvb => "xvb",
+    }
+}
+
+fn state_payload(state: ProcessState) -> &'static str {
+    match state {
+        ProcessState::Alive => "alive",
+        ProcessState::Dead => "dead",
+        ProcessState::Failed => "failed",
+        ProcessState::Middle => "middle",
+        ProcessState::Waiting => "waiting",
+        ProcessState::Syncing => "syncing",
+        ProcessState::NotMining => "not_mining",
+        ProcessState::OfflinePoolsAll => "offline_pools_all",
+    }
+}
+
+// Command of a message received on a command topic.
+pub fn parse_command(prefix: &str, topic: &str, payload: &str) -> Option<MqttCommand> {
+    let topic = topic.strip_prefix(prefix)?.strip_prefix('/')?;
+    let payload = payload.trim();
+    if topic == "xmrig/threads/set" {
+        return payload
+            .parse::<u16>()
+            .ok()
+            .filter(|t| *t > 0)
+            .map(MqttCommand::Threads);
+    }
+    let name = [
+        ProcessName::Node,
+        ProcessName::P2pool,
+        ProcessName::Xmrig,
+        ProcessName::XmrigProxy,
+        ProcessName::Xvb,
+    ]
+    .into_iter()
+    .find(|n| topic.strip_suffix("/set") == Some(topic_name(*n)))?;
+    let signal = match payload.to_lowercase().as_str() {
+        "start" => ProcessSignal::Start,
+        "stop" => ProcessSignal::Stop,
+        "restart" => ProcessSignal::Restart,
+        _ => return None,
+    };
+    Some(MqttCommand::Signal(name, signal))
+}
+
+// Values published, read from the processes and their APIs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MqttStats {
+    pub states: Vec<(ProcessName, ProcessState)>,
+    // H/s
+    pub xmrig_hashrate: f32,
+    pub xmrig_threads: String,
+    pub xp_hashrate: f32,
+    pub p2pool_hashrate: u64,
+    pub shares: Option<u64>,
+    pub payouts: u64,
+    // atomic units
+    pub xmr: u64,
+    pub xvb_round: Option<String>,
+    // H/s
+    pub xvb_donated_1h: f32,
+    pub xvb_donated_24h: f32,
+}
+
+// (topic, payload) of every value.
+pub fn messages(prefix: &str, stats: &MqttStats) -> Vec<(String, String)> {
+    let topic = |t: &str| [prefix, "/", t].concat();
+    let mut messages = stats
+        .states
+        .iter()
+        .map(|(name, state)| {
+            (
+                topic(&[topic_name(*name), "/state"].concat()),
+                state_payload(*state).to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    messages.extend([
+        (
+            topic("xmrig/hashrate"),
+            format!("{:.0}", stats.xmrig_hashrate),
+        ),
+        (topic("xmrig/threads"), stats.xmrig_threads.clone()),
+        (
+            topic("xmrig_proxy/hashrate"),
+            format!("{:.0}", stats.xp_hashrate),
+        ),
+        (topic("p2pool/hashrate"), stats.p2pool_hashrate.to_string()),
+        (
+            topic("p2pool/shares"),
+            stats.shares.unwrap_or_default().to_string(),
+        ),
+        (topic("p2pool/payouts"), stats.payouts.to_string()),
+        (
+            topic("p2pool/xmr"),
+            format!("{}", AtomicUnit::from_u64(stats.xmr)),
+        ),
+        (
+            topic("xvb/round"),
+            stats
+                .xvb_round
+                .clone()
+                .unwrap_or_else(|| "none".to_string()),
+        ),
+        (
+            topic("xvb/donated_1h"),
+            format!("{:.0}", stats.xvb_donated_1h),
+        ),
+        (
+            topic("xvb/donated_24h"),
+            format!("{:.0}", stats.xvb_donated_24h),
+        ),
+    ]);
+    messages
+}
+
+// APIs the values are read from.
+struct Sources {
+    processes: Vec<Arc<Mutex<Process>>>,
+    gui_api_xmrig: Arc<Mutex<PubXmrigApi>>,
+    gui_api_xp: Arc<Mutex<PubXmrigProxyApi>>,
+    gui_api_p2pool: Arc<Mutex<PubP2poolApi>>,
+    gui_api_xvb: Arc<Mutex<PubXvbApi>>,
+    gupax_p2pool_api: Arc<Mutex<GupaxP2poolApi>>,
+    img_xmrig: Arc<Mutex<ImgXmrig>>,
+}
+
+impl Sources {
+    fn read(&self) -> MqttStats {
+        let states = self
+            .processes
+            .iter()
+            .map(|p| {
+                let p = p.lock().unwrap();
+                (p.name, p.state)
+            })
+            .collect();
+        let (payouts, xmr) = {
+            let api = self.gupax_p2pool_api.lock().unwrap();
+            (api.payout_u64, api.xmr.to_u64())
+        };
+        let (p2pool_hashrate, shares) = {
+            let api = self.gui_api_p2pool.lock().unwrap();
+            (api.hashrate_15m, api.shares_found)
+        };
+        let (xvb_round, xvb_donated_1h, xvb_donated_24h) = {
+            let stats = &self.gui_api_xvb.lock().unwrap().stats_priv;
+            (
+                stats.round_participate.as_ref().map(|r| r.to_string()),
+                stats.donor_1hr_avg * 1000.0,
+                stats.donor_24hr_avg * 1000.0,
+            )
+        };
+        MqttStats {
+            states,
+            xmrig_hashrate: self.gui_api_xmrig.lock().unwrap().hashrate_raw,
+            xmrig_threads: self.img_xmrig.lock().unwrap().threads.clone(),
+            xp_hashrate: self.gui_api_xp.lock().unwrap().hashrate_1m,
+            p2pool_hashrate,
+            shares,
+            payouts,
+            xmr,
+            xvb_round,
+            xvb_donated_1h,
+            xvb_donated_24h,
+        }
+    }
+}
+
+fn options(settings: &Mqtt) -> Option<MqttOptions> {
+    let port = settings.port.parse::<u16>().ok()?;
+    if settings.host.is_empty() {
+        return None;
+    }
+    let mut options = MqttOptions::new(
+        ["gupaxx-", &settings.prefix.replace('/', "-")].concat(),
+        &settings.host,
+        port,
+    );
+    options.set_keep_alive(Duration::from_secs(30));
+    options.set_last_will(LastWill::new(
+        [&settings.prefix, "/status"].concat(),
+        "offline",
+        QoS::AtLeastOnce,
+        true,
+    ));
+    if !settings.username.is_empty() {
+        options.set_credentials(&settings.username, &settings.password);
+    }
+    Some(options)
+}
+
+impl Helper {
+    pub fn spawn_mqtt_watchdog(helper: &Arc<Mutex<Self>>) {
+        let lock = helper.lock().unwrap();
+        let sources = Sources {
+            processes: vec![
+                Arc::clone(&lock.node),
+                Arc::clone(&lock.p2pool),
+                Arc::clone(&lock.xmrig),
+                Arc::clone(&lock.xmrig_proxy),
+                Arc::clone(&lock.xvb),
+            ],
+            gui_api_xmrig: Arc::clone(&lock.gui_api_xmrig),
+            gui_api_xp: Arc::clone(&lock.gui_api_xp),
+            gui_api_p2pool: Arc::clone(&lock.gui_api_p2pool),
+            gui_api_xvb: Arc::clone(&lock.gui_api_xvb),
+            gupax_p2pool_api: Arc::clone(&lock.gupax_p2pool_api),
+            img_xmrig: Arc::clone(&lock.img_xmrig),
+        };
+        let mqtt = Arc::clone(&lock.mqtt);
+        drop(lock);
+        thread::spawn(move || {
+            Self::mqtt_watchdog(&sources, &mqtt);
+        });
+    }
+    #[tokio::main]
+    async fn mqtt_watchdog(sources: &Sources, mqtt: &Arc<Mutex<PubMqttApi>>) {
+        info!("MQTT | Entering watchdog mode... woof!");
+        loop {
+            let settings = mqtt.lock().unwrap().settings.clone();
+            let options = match options(&settings) {
+                Some(o) if settings.enabled => o,
+                _ => {
+                    mqtt.lock().unwrap().connected = false;
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
+            info!("MQTT | Connecting to {}:{}", settings.host, settings.port);
+            let (client, mut eventloop) = AsyncClient::new(options, 64);
+            let status = [&settings.prefix, "/status"].concat();
+            let mut published = HashMap::new();
+            let mut ticker = tokio::time::interval(MQTT_INTERVAL);
+            loop {
+                tokio::select! {
+                    event = eventloop.poll() => match event {
+                        Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                            info!("MQTT | Connected ... OK");
+                            let mut api = mqtt.lock().unwrap();
+                            api.connected = true;
+                            api.error = None;
+                            drop(api);
+                            // the broker may have lost the retained values and the subscriptions
+                            published.clear();
+                            let _ = client.try_publish(&status, QoS::AtLeastOnce, true, "online");
+                            if settings.commands {
+                                let topics = [
+                                    [&settings.prefix, "/+/set"].concat(),
+                                    [&settings.prefix, "/xmrig/threads/set"].concat(),
+                                ];
+                                for topic in topics {
+                                    if let Err(e) = client.try_subscribe(topic, QoS::AtLeastOnce) {
+                                        warn!("MQTT | Subscribe ... FAIL ... {}", e);
+                                    }
+                                }
+                            }
+                        }
+                        Ok(Event::Incoming(Packet::Publish(p))) if settings.commands => {
+                            let payload = String::from_utf8_lossy(&p.payload);
+                            match parse_command(&settings.prefix, &p.topic, &payload) {
+                                Some(_) if p.retain => {
+                                    debug!("MQTT | Ignored retained command on [{}]: {}", p.topic, payload)
+                                }
+                                Some(command) => {
+                                    info!("MQTT | Command received: {:?}", command);
+                                    let mut api = mqtt.lock().unwrap();
+                                    api.commands.push(command);
+                                    if let Some(ctx) = &api.ctx {
+                                        ctx.request_repaint();
+                                    }
+                                }
+                                None => debug!("MQTT | Ignored message on [{}]: {}", p.topic, payload),
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            warn!("MQTT | Connection ... FAIL ... {}", e);
+                            {
+                                let mut api = mqtt.lock().unwrap();
+                                api.connected = false;
+                                api.error = Some(e.to_string());
+                            }
+                            // the next poll connects again
+                            tokio::time::sleep(MQTT_RETRY).await;
+                        }
+                    },
+                    _ = ticker.tick() => {
+                        if mqtt.lock().unwrap().settings != settings {
+                            info!("MQTT | Settings changed, disconnecting");
+                            let _ = client.try_publish(&status, QoS::AtLeastOnce, true, "offline");
+                            let _ = client.try_disconnect();
+                            break;
+                        }
+                        if !mqtt.lock().unwrap().connected {
+                            continue;
+                        }
+                        // only the values that changed are published
+                        for (topic, payload) in messages(&settings.prefix, &sources.read()) {
+                            if published.get(&topic) == Some(&payload) {
+                                continue;
+                            }
+                            match client.try_publish(&topic, QoS::AtLeastOnce, true, payload.clone()) {
+                                Ok(_) => {
+                                    published.insert(topic, payload);
+                                }
+                                Err(e) => debug!("MQTT | Publish [{}] ... FAIL ... {}", topic, e),
+                            }
+                        }
+                    }
+                }
+            }
+            mqtt.lock().unwrap().connected = false;
+            // let the disconnection be sent
+            let _ = tokio::time::timeout(Duration::from_secs(1), eventloop.poll()).await;
+        }
+    }
+}
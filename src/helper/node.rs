@@ -32,12 +32,13 @@ use sysinfo::MemoryRefreshKind;
 use tokio::spawn;
 
 use crate::{
-    disk::state::{Node, StartOptionsMode},
+    disk::state::{Node, Socks5, StartOptionsMode},
     helper::{
         ProcessName, ProcessSignal, ProcessState, check_died, check_user_input, signal_end,
-        sleep_end_loop,
+        signal_end_attached, sleep_end_loop,
     },
     macros::{arc_mut, sleep},
+    miscs::output_console,
 };
 use std::fmt::Write;
 
@@ -76,6 +77,8 @@ impl Helper {
     pub fn build_node_args(
         state: &crate::disk::state::Node,
         mode: StartOptionsMode,
+        socks5: Option<&str>,
+        tor: bool,
     ) -> Vec<String> {
         let mut args = Vec::with_capacity(500);
 
@@ -128,6 +131,15 @@ impl Helper {
                 if state.pruned {
                     args.push("--prune-blockchain".to_string());
                 }
+                // broadcast transactions through the I2P router and accept anonymous peers from it
+                if let Some(i2p) = state.i2p.proxy() {
+                    args.push("--tx-proxy".to_string());
+                    args.push(format!("i2p,{i2p},16"));
+                }
+                if let Some(inbound) = state.i2p.inbound() {
+                    args.push("--anonymous-inbound".to_string());
+                    args.push(inbound);
+                }
             }
             StartOptionsMode::Custom => {
                 // This parses the input
@@ -138,6 +150,18 @@ impl Helper {
                 }
             }
         }
+        // route outgoing p2p connections through the SOCKS5 proxy,
+        // transactions are broadcast to onion peers only if the proxy is Tor.
+        if let Some(socks5) = socks5 {
+            if !matches!(mode, StartOptionsMode::Custom) {
+                args.push("--proxy".to_string());
+                args.push(socks5.to_string());
+                if tor {
+                    args.push("--tx-proxy".to_string());
+                    args.push(format!("tor,{socks5},16"));
+                }
+            }
+        }
         args
     }
     #[cold]
@@ -156,7 +180,7 @@ impl Helper {
     #[inline(never)]
     // The "restart frontend" to a "frontend" function.
     // Basically calls to kill the current p2pool, waits a little, then starts the below function in a a new thread, then exit.
-    pub fn restart_node(helper: &Arc<Mutex<Self>>, state: &Node, path: &Path) {
+    pub fn restart_node(helper: &Arc<Mutex<Self>>, state: &Node, path: &Path, socks5: &Socks5) {
         info!("Node | Attempting to restart...");
         helper.lock().unwrap().node.lock().unwrap().signal = ProcessSignal::Restart;
         helper.lock().unwrap().node.lock().unwrap().state = ProcessState::Middle;
@@ -164,6 +188,7 @@ impl Helper {
         let helper = Arc::clone(helper);
         let state = state.clone();
         let path = path.to_path_buf();
+        let socks5 = socks5.clone();
         // This thread lives to wait, start p2pool then die.
         thread::spawn(move || {
             while helper.lock().unwrap().node.lock().unwrap().state != ProcessState::Waiting {
@@ -172,15 +197,19 @@ impl Helper {
             }
             // Ok, process is not alive, start the new one!
             info!("Node | Old process seems dead, starting new one!");
-            Self::start_node(&helper, &state, &path);
+            Self::start_node(&helper, &state, &path, &socks5);
         });
         info!("Node | Restart ... OK");
     }
     #[cold]
     #[inline(never)]
     // The "frontend" function that parses the arguments, and spawns either the [Simple] or [Advanced] Node watchdog thread.
-    pub fn start_node(helper: &Arc<Mutex<Self>>, state: &Node, path: &Path) {
+    pub fn start_node(helper: &Arc<Mutex<Self>>, state: &Node, path: &Path, socks5: &Socks5) {
         helper.lock().unwrap().node.lock().unwrap().state = ProcessState::Middle;
+        if state.attached() {
+            Self::attach_node(helper, state);
+            return;
+        }
         let mode = if state.simple {
             StartOptionsMode::Simple
         } else if !state.arguments.is_empty() {
@@ -190,7 +219,7 @@ impl Helper {
         };
         let (rpc_port, zmq_port) = state.ports();
         *helper.lock().unwrap().img_node.lock().unwrap() = ImgNode { rpc_port, zmq_port };
-        let args = Self::build_node_args(state, mode);
+        let args = Self::build_node_args(state, mode, socks5.node().as_deref(), socks5.tor);
 
         // Print arguments & user settings to console
         crate::disk::print_dash(&format!("Node | Launch arguments: {:#?}", args));
@@ -307,24 +336,13 @@ impl Helper {
                 PubNodeApi::update_from_output(pub_api, &output_pub, start.elapsed());
                 // update data from api
                 debug!("Node Watchdog | Attempting HTTP API request...");
-                match PrivNodeApi::request_api(&client, &state).await {
-                    Ok(priv_api) => {
-                        debug!(
-                            "Node Watchdog | HTTP API request OK, attempting [update_from_priv()]"
+                if let Err(err) = Self::update_from_rpc(&client, &state, process, pub_api).await {
+                    // if node is just starting, do not throw an error
+                    if start.elapsed() > Duration::from_secs(10) {
+                        warn!(
+                            "Node Watchdog | Could not send HTTP API request to node\n{}",
+                            err
                         );
-                        if priv_api.result.synchronized && priv_api.result.status == "OK" {
-                            process.lock().unwrap().state = ProcessState::Alive
-                        }
-                        PubNodeApi::update_from_priv(pub_api, priv_api);
-                    }
-                    Err(err) => {
-                        // if node is just starting, do not throw an error
-                        if start.elapsed() > Duration::from_secs(10) {
-                            warn!(
-                                "Node Watchdog | Could not send HTTP API request to node\n{}",
-                                err
-                            );
-                        }
                     }
                 }
             }
@@ -336,6 +354,111 @@ impl Helper {
         info!("XMRig-Proxy Watchdog | Watchdog thread exiting... Goodbye!");
         // sleep
     }
+    // Update the public API from the RPC of the node.
+    async fn update_from_rpc(
+        client: &Client,
+        state: &Node,
+        process: &Arc<Mutex<Process>>,
+        pub_api: &Arc<Mutex<PubNodeApi>>,
+    ) -> std::result::Result<(), anyhow::Error> {
+        let priv_api = PrivNodeApi::request_api(client, state).await?;
+        debug!("Node Watchdog | HTTP API request OK, attempting [update_from_priv()]");
+        if priv_api.result.synchronized && priv_api.result.status == "OK" {
+            process.lock().unwrap().state = ProcessState::Alive
+        }
+        PubNodeApi::update_from_priv(pub_api, priv_api);
+        // peers reached through I2P/Tor, only listed by get_connections
+        match PrivNodeConnectionsApi::request_api(client, state).await {
+            Ok(connections) => PubNodeApi::update_from_connections(pub_api, connections),
+            Err(err) => debug!(
+                "Node Watchdog | Could not get the node connections\n{}",
+                err
+            ),
+        }
+        Ok(())
+    }
+    #[cold]
+    #[inline(never)]
+    // Monitor a node started outside of Gupaxx through its RPC, without owning the process.
+    fn attach_node(helper: &Arc<Mutex<Self>>, state: &Node) {
+        let (rpc_port, zmq_port) = state.ports();
+        *helper.lock().unwrap().img_node.lock().unwrap() = ImgNode { rpc_port, zmq_port };
+        let process = Arc::clone(&helper.lock().unwrap().node);
+        let gui_api = Arc::clone(&helper.lock().unwrap().gui_api_node);
+        let pub_api = Arc::clone(&helper.lock().unwrap().pub_api_node);
+        let state = state.clone();
+        thread::spawn(move || {
+            Self::spawn_node_attach_watchdog(&process, &gui_api, &pub_api, state);
+        });
+    }
+    #[tokio::main]
+    #[allow(clippy::await_holding_lock)]
+    async fn spawn_node_attach_watchdog(
+        process: &Arc<Mutex<Process>>,
+        gui_api: &Arc<Mutex<PubNodeApi>>,
+        pub_api: &Arc<Mutex<PubNodeApi>>,
+        state: Node,
+    ) {
+        let client = Client::new();
+        let mut lock = process.lock().unwrap();
+        lock.state = ProcessState::Syncing;
+        lock.signal = ProcessSignal::None;
+        lock.start = Instant::now();
+        let start = lock.start;
+        let output_pub = Arc::clone(&lock.output_pub);
+        drop(lock);
+        // reset stats
+        *pub_api.lock().unwrap() = PubNodeApi::new();
+        *gui_api.lock().unwrap() = PubNodeApi::new();
+        output_console(
+            &mut gui_api.lock().unwrap().output,
+            &format!(
+                "Attached to the Node running outside of Gupaxx, monitoring it through its RPC at http://{}:{}",
+                state.api_ip, state.api_port
+            ),
+            ProcessName::Node,
+        );
+        let mut reachable = true;
+        info!("Node | Entering attached watchdog mode... woof!");
+        loop {
+            let now = Instant::now();
+            debug!("Node Watchdog | ----------- Start of loop -----------");
+            if signal_end_attached(
+                &mut process.lock().unwrap(),
+                &start,
+                &mut gui_api.lock().unwrap().output,
+            ) {
+                break;
+            }
+            PubNodeApi::update_from_output(pub_api, &output_pub, start.elapsed());
+            match Self::update_from_rpc(&client, &state, process, pub_api).await {
+                Ok(_) if !reachable => {
+                    reachable = true;
+                    output_console(
+                        &mut gui_api.lock().unwrap().output,
+                        "The RPC of the Node is reachable again",
+                        ProcessName::Node,
+                    );
+                }
+                Err(err) if reachable => {
+                    reachable = false;
+                    process.lock().unwrap().state = ProcessState::Syncing;
+                    warn!(
+                        "Node Watchdog | Could not send HTTP API request to node\n{}",
+                        err
+                    );
+                    output_console(
+                        &mut gui_api.lock().unwrap().output,
+                        &format!("The RPC of the Node is not reachable: {err}"),
+                        ProcessName::Node,
+                    );
+                }
+                _ => (),
+            }
+            sleep_end_loop(now, ProcessName::Node).await;
+        }
+        info!("Node Watchdog | Attached watchdog thread exiting... Goodbye!");
+    }
 }
 #[derive(Clone)]
 pub struct PubNodeApi {
@@ -348,6 +471,7 @@ pub struct PubNodeApi {
     pub nettype: String,
     pub outgoing_connections: u16,
     pub incoming_connections: u16,
+    pub anonymous_connections: u16,
     pub status: String,
     pub synchronized: bool,
 }
@@ -369,6 +493,7 @@ impl PubNodeApi {
             nettype: String::from("???"),
             outgoing_connections: 0,
             incoming_connections: 0,
+            anonymous_connections: 0,
             status: String::from("Offline"),
             synchronized: false,
         }
@@ -384,7 +509,7 @@ impl PubNodeApi {
             gui_api.output.push_str(&buf);
         }
     }
-    fn update_from_priv(public: &Arc<Mutex<Self>>, private: PrivNodeApi) {
+    pub(super) fn update_from_priv(public: &Arc<Mutex<Self>>, private: PrivNodeApi) {
         let mut public = public.lock().unwrap();
         *public = Self {
             blockheight: HumanNumber::from_u64(private.result.height),
@@ -399,6 +524,9 @@ impl PubNodeApi {
             ..std::mem::take(&mut *public)
         }
     }
+    fn update_from_connections(public: &Arc<Mutex<Self>>, private: PrivNodeConnectionsApi) {
+        public.lock().unwrap().anonymous_connections = private.anonymous_connections();
+    }
     pub fn update_from_output(
         public: &Arc<Mutex<Self>>,
         output_pub: &Arc<Mutex<String>>,
@@ -418,7 +546,7 @@ impl PubNodeApi {
     }
 }
 #[derive(Deserialize, Serialize)]
-struct PrivNodeApi {
+pub(super) struct PrivNodeApi {
     result: ResultNodeJson,
 }
 #[derive(Deserialize, Serialize)]
@@ -434,7 +562,7 @@ struct ResultNodeJson {
     pub synchronized: bool,
 }
 impl PrivNodeApi {
-    async fn request_api(
+    pub(super) async fn request_api(
         client: &Client,
         state: &Node,
     ) -> std::result::Result<Self, anyhow::Error> {
@@ -472,6 +600,43 @@ impl PrivNodeApi {
         Ok(private)
     }
 }
+#[derive(Deserialize, Serialize)]
+pub(super) struct PrivNodeConnectionsApi {
+    result: ResultNodeConnectionsJson,
+}
+#[derive(Deserialize, Serialize)]
+struct ResultNodeConnectionsJson {
+    // monerod omits the list when there is no connection
+    connections: Option<Vec<NodeConnectionJson>>,
+}
+#[derive(Deserialize, Serialize)]
+struct NodeConnectionJson {
+    // 1: ipv4, 2: ipv6, 3: i2p, 4: tor
+    address_type: u8,
+}
+impl PrivNodeConnectionsApi {
+    pub(super) fn anonymous_connections(&self) -> u16 {
+        self.result
+            .connections
+            .iter()
+            .flatten()
+            .filter(|c| matches!(c.address_type, 3 | 4))
+            .count() as u16
+    }
+    async fn request_api(
+        client: &Client,
+        state: &Node,
+    ) -> std::result::Result<Self, anyhow::Error> {
+        let adr = format!("http://{}:{}/json_rpc", state.api_ip, state.api_port);
+        Ok(client
+            .post(adr)
+            .body(r#"{"jsonrpc":"2.0","id":"0","method":"get_connections"}"#)
+            .send()
+            .await?
+            .json::<Self>()
+            .await?)
+    }
+}
 #[derive(Debug, Clone)]
 pub struct ImgNode {
     pub rpc_port: u16,
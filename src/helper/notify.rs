@@ -0,0 +1,334 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Notifications of mining events outside of the window, on the desktop (Linux) and/or to a webhook.
+// The processes and their APIs are read every second, an event is a change between two readings,
+// so that the watchdogs of the processes do not need to know about notifications.
+
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use strum::{Display, EnumIter};
+
+use crate::{
+    disk::{
+        gupax_p2pool_api::GupaxP2poolApi,
+        state::{Gupax, Notifications},
+    },
+    helper::{Helper, Process, ProcessName, ProcessState, p2pool::PubP2poolApi, xvb::PubXvbApi},
+    miscs::http_client,
+    utils::{
+        constants::{
+            GUPAX_NOTIFY_CRASH, GUPAX_NOTIFY_NODE_SYNC, GUPAX_NOTIFY_PAYOUT, GUPAX_NOTIFY_SHARE,
+            GUPAX_NOTIFY_UPDATE, GUPAX_NOTIFY_XVB_OFFLINE, GUPAX_NOTIFY_XVB_WIN,
+        },
+        xmr::AtomicUnit,
+    },
+};
+
+// delay between two readings of the processes.
+const NOTIFY_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, EnumIter, Serialize, Deserialize)]
+pub enum NotifyEvent {
+    #[strum(to_string = "Payout")]
+    Payout,
+    #[strum(to_string = "Share found")]
+    Share,
+    #[strum(to_string = "Process failed")]
+    Crash,
+    #[strum(to_string = "XvB round won")]
+    XvbWin,
+    #[strum(to_string = "Node lost sync")]
+    NodeSync,
+    #[strum(to_string = "XvB pools offline")]
+    XvbOffline,
+    #[strum(to_string = "Update available")]
+    Update,
+}
+
+impl NotifyEvent {
+    pub const fn help_msg(&self) -> &str {
+        match self {
+            Self::Payout => GUPAX_NOTIFY_PAYOUT,
+            Self::Share => GUPAX_NOTIFY_SHARE,
+            Self::Crash => GUPAX_NOTIFY_CRASH,
+            Self::XvbWin => GUPAX_NOTIFY_XVB_WIN,
+            Self::NodeSync => GUPAX_NOTIFY_NODE_SYNC,
+            Self::XvbOffline => GUPAX_NOTIFY_XVB_OFFLINE,
+            Self::Update => GUPAX_NOTIFY_UPDATE,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    pub event: NotifyEvent,
+    pub message: String,
+}
+
+#[derive(Clone, Default)]
+pub struct PubNotifyApi {
+    // kept in sync with the state by the GUI.
+    pub settings: Notifications,
+    // SOCKS5 proxy used to reach the webhook
+    pub socks5: Option<String>,
+}
+
+impl PubNotifyApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // settings of the state, also after a reset of the settings.
+    pub fn sync(&mut self, gupax: &Gupax) {
+        if self.settings != gupax.notifications {
+            self.settings.clone_from(&gupax.notifications);
+        }
+        let socks5 = gupax.socks5.gupaxx();
+        if self.socks5 != socks5 {
+            self.socks5 = socks5;
+        }
+    }
+}
+
+// What is read from the processes every second.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    // payouts logged by Gupaxx and their sum in atomic units
+    pub payouts: u64,
+    pub xmr: u64,
+    // shares found since P2Pool started, given by its API
+    pub shares: Option<u64>,
+    pub states: Vec<(ProcessName, ProcessState)>,
+    pub xvb_win: bool,
+    // newer version of Gupaxx found by the last check for updates
+    pub update: Option<String>,
+}
+
+impl Snapshot {
+    fn state(&self, name: ProcessName) -> Option<ProcessState> {
+        self.states
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, s)| *s)
+    }
+}
+
+// Events between two readings.
+pub fn events(before: &Snapshot, now: &Snapshot) -> Vec<Notification> {
+    let mut events = vec![];
+    let mut push = |event, message: String| events.push(Notification { event, message });
+    if now.payouts > before.payouts {
+        push(
+            NotifyEvent::Payout,
+            format!(
+                "P2Pool payout received: {} XMR",
+                AtomicUnit::from_u64(now.xmr.saturating_sub(before.xmr))
+            ),
+        );
+    }
+    // the count of shares starts again from 0 when P2Pool restarts
+    if let (Some(before), Some(now)) = (before.shares, now.shares) {
+        if now > before {
+            push(
+                NotifyEvent::Share,
+                format!("P2Pool found a share ({now} since it started)"),
+            );
+        }
+    }
+    for (name, state) in &now.states {
+        let previous = before.state(*name);
+        if *state == ProcessState::Failed && previous.is_some_and(|s| s != ProcessState::Failed) {
+            push(NotifyEvent::Crash, format!("{name} failed"));
+        }
+        if *name == ProcessName::Node
+            && *state == ProcessState::Syncing
+            && previous == Some(ProcessState::Alive)
+        {
+            push(
+                NotifyEvent::NodeSync,
+                "The Node is not synchronized with the network anymore".to_string(),
+            );
+        }
+        if *name == ProcessName::Xvb
+            && *state == ProcessState::OfflinePoolsAll
+            && previous.is_some_and(|s| s != ProcessState::OfflinePoolsAll)
+        {
+            push(
+                NotifyEvent::XvbOffline,
+                "The XvB pools are offline, mining on P2Pool only".to_string(),
+            );
+        }
+    }
+    if now.xvb_win && !before.xvb_win {
+        push(
+            NotifyEvent::XvbWin,
+            "You are winning the current XvB round!".to_string(),
+        );
+    }
+    if now.update.is_some() && now.update != before.update {
+        push(
+            NotifyEvent::Update,
+            format!(
+                "Gupaxx {} is available",
+                now.update.as_deref().unwrap_or_default()
+            ),
+        );
+    }
+    events
+}
+
+fn snapshot(
+    processes: &[Arc<Mutex<Process>>],
+    gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
+    gui_api_p2pool: &Arc<Mutex<PubP2poolApi>>,
+    gui_api_xvb: &Arc<Mutex<PubXvbApi>>,
+    update: &Arc<Mutex<Option<String>>>,
+) -> Snapshot {
+    let (payouts, xmr) = {
+        let api = gupax_p2pool_api.lock().unwrap();
+        (api.payout_u64, api.xmr.to_u64())
+    };
+    Snapshot {
+        payouts,
+        xmr,
+        shares: gui_api_p2pool.lock().unwrap().shares_found,
+        states: processes
+            .iter()
+            .map(|p| {
+                let p = p.lock().unwrap();
+                (p.name, p.state)
+            })
+            .collect(),
+        xvb_win: gui_api_xvb.lock().unwrap().stats_priv.win_current,
+        update: update.lock().unwrap().clone(),
+    }
+}
+
+async fn deliver(api: &PubNotifyApi, notification: &Notification) {
+    let settings = &api.settings;
+    if !settings.events.contains(&notification.event) {
+        return;
+    }
+    info!("Notify | {}: {}", notification.event, notification.message);
+    #[cfg(target_os = "linux")]
+    if settings.desktop {
+        let status = tokio::process::Command::new("notify-send")
+            .arg("--app-name=Gupaxx")
+            .arg(notification.event.to_string())
+            .arg(&notification.message)
+            .status()
+            .await;
+        match status {
+            Ok(s) if s.success() => debug!("Notify | Desktop notification ... OK"),
+            Ok(s) => warn!("Notify | Desktop notification ... FAIL ... {}", s),
+            Err(e) => warn!("Notify | Could not run notify-send: {}", e),
+        }
+    }
+    if settings.webhook && !settings.webhook_url.is_empty() {
+        let body = json!({
+            "event": notification.event,
+            "title": notification.event.to_string(),
+            "message": notification.message,
+            "timestamp": chrono::Utc::now().timestamp(),
+        });
+        let response = http_client(api.socks5.as_deref())
+            .post(&settings.webhook_url)
+            .timeout(Duration::from_secs(10))
+            .json(&body)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status());
+        match response {
+            Ok(_) => debug!("Notify | Webhook ... OK"),
+            Err(e) => warn!(
+                "Notify | Webhook {} ... FAIL ... {}",
+                settings.webhook_url, e
+            ),
+        }
+    }
+}
+
+impl Helper {
+    pub fn spawn_notify_watchdog(helper: &Arc<Mutex<Self>>, update: &Arc<Mutex<Option<String>>>) {
+        let lock = helper.lock().unwrap();
+        let processes = vec![
+            Arc::clone(&lock.node),
+            Arc::clone(&lock.p2pool),
+            Arc::clone(&lock.xmrig),
+            Arc::clone(&lock.xmrig_proxy),
+            Arc::clone(&lock.xvb),
+        ];
+        let gupax_p2pool_api = Arc::clone(&lock.gupax_p2pool_api);
+        let gui_api_p2pool = Arc::clone(&lock.gui_api_p2pool);
+        let gui_api_xvb = Arc::clone(&lock.gui_api_xvb);
+        let notify = Arc::clone(&lock.notify);
+        drop(lock);
+        let update = Arc::clone(update);
+        thread::spawn(move || {
+            Self::notify_watchdog(
+                &processes,
+                &gupax_p2pool_api,
+                &gui_api_p2pool,
+                &gui_api_xvb,
+                &notify,
+                &update,
+            );
+        });
+    }
+    #[tokio::main]
+    async fn notify_watchdog(
+        processes: &[Arc<Mutex<Process>>],
+        gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
+        gui_api_p2pool: &Arc<Mutex<PubP2poolApi>>,
+        gui_api_xvb: &Arc<Mutex<PubXvbApi>>,
+        notify: &Arc<Mutex<PubNotifyApi>>,
+        update: &Arc<Mutex<Option<String>>>,
+    ) {
+        info!("Notify | Entering watchdog mode... woof!");
+        let read = || {
+            snapshot(
+                processes,
+                gupax_p2pool_api,
+                gui_api_p2pool,
+                gui_api_xvb,
+                update,
+            )
+        };
+        // nothing is notified for the state found at startup
+        let mut before = read();
+        loop {
+            tokio::time::sleep(NOTIFY_INTERVAL).await;
+            let now = read();
+            let notifications = events(&before, &now);
+            before = now;
+            if notifications.is_empty() {
+                continue;
+            }
+            let api = notify.lock().unwrap().clone();
+            for notification in &notifications {
+                deliver(&api, notification).await;
+            }
+        }
+    }
+}
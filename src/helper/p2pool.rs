@@ -22,14 +22,18 @@ use crate::components::node::RemoteNode;
 use crate::disk::state::Node;
 use crate::disk::state::P2pool;
 use crate::disk::state::P2poolChain;
+use crate::disk::state::Socks5;
 use crate::disk::state::StartOptionsMode;
 use crate::helper::ProcessName;
 use crate::helper::ProcessSignal;
 use crate::helper::ProcessState;
 use crate::helper::check_died;
 use crate::helper::check_user_input;
+use crate::helper::data_api::{DataApiFile, DataApiWatcher};
 use crate::helper::signal_end;
+use crate::helper::signal_end_attached;
 use crate::helper::sleep_end_loop;
+use crate::miscs::output_console;
 use crate::regex::P2POOL_REGEX;
 use crate::regex::contains_end_status;
 use crate::regex::contains_statuscommand;
@@ -199,6 +203,7 @@ impl Helper {
         path: &Path,
         backup_hosts: Option<Vec<PoolNode>>,
         override_to_local_node: bool,
+        socks5: &Socks5,
     ) {
         info!("P2Pool | Attempting to restart...");
         helper.lock().unwrap().p2pool.lock().unwrap().signal = ProcessSignal::Restart;
@@ -208,6 +213,7 @@ impl Helper {
         let state = state.clone();
         let state_node = state_node.clone();
         let path = path.to_path_buf();
+        let socks5 = socks5.clone();
         // This thread lives to wait, start p2pool then die.
         thread::spawn(move || {
             while helper.lock().unwrap().p2pool.lock().unwrap().state != ProcessState::Waiting {
@@ -223,6 +229,7 @@ impl Helper {
                 &path,
                 backup_hosts,
                 override_to_local_node,
+                &socks5,
             );
         });
         info!("P2Pool | Restart ... OK");
@@ -238,8 +245,13 @@ impl Helper {
         path: &Path,
         backup_hosts: Option<Vec<PoolNode>>,
         override_to_local_node: bool,
+        socks5: &Socks5,
     ) {
         helper.lock().unwrap().p2pool.lock().unwrap().state = ProcessState::Middle;
+        if state.attached() {
+            Self::attach_p2pool(helper, state, path);
+            return;
+        }
         let (api_path_local, api_path_network, api_path_pool, api_path_p2p) =
             Self::mutate_img_p2pool(state, helper, path);
         let mode = if state.simple {
@@ -286,6 +298,10 @@ impl Helper {
             local_node_zmq,
             local_node_rpc,
             mode,
+            socks5
+                .p2pool()
+                .or_else(|| state_node.i2p.p2pool())
+                .as_deref(),
         );
 
         // Print arguments & user settings to console
@@ -304,6 +320,7 @@ impl Helper {
         // starting the thread even if the option is disabled allows to apply the change immediately in case it is enabled again without asking the user to restart p2pool.
         // Start this thread only if we don't already override to local node
         if !override_to_local_node {
+            let socks5 = socks5.clone();
             thread::spawn(
                 enc!((helper, state, state_node, path, backup_hosts) move || {
                     Self::watch_switch_p2pool_to_local_node(
@@ -312,6 +329,7 @@ impl Helper {
                         &state_node,
                         &path,
                         backup_hosts,
+                        &socks5,
                     );
                 }),
             );
@@ -413,6 +431,10 @@ impl Helper {
                 in_peers: state.in_peers.to_string(),
             };
         }
+        Self::data_api_paths(api_path)
+    }
+    // paths of the files (local, network, pool, p2p) inside the data-api directory
+    fn data_api_paths(api_path: PathBuf) -> (PathBuf, PathBuf, PathBuf, PathBuf) {
         let mut api_path_local = api_path.clone();
         let mut api_path_network = api_path.clone();
         let mut api_path_pool = api_path.clone();
@@ -433,6 +455,7 @@ impl Helper {
     // Takes in some [State/P2pool] and parses it to build the actual command arguments.
     // Returns the [Vec] of actual arguments, and mutates the [ImgP2pool] for the main GUI thread
     // It returns a value... and mutates a deeply nested passed argument... this is some pretty bad code...
+    #[allow(clippy::too_many_arguments)]
     pub fn build_p2pool_args(
         state: &P2pool,
         path: &Path,
@@ -442,6 +465,7 @@ impl Helper {
         local_node_rpc_port: u16,
         // Allows to provide a different mode without mutating the state
         mode: StartOptionsMode,
+        socks5: Option<&str>,
     ) -> Vec<String> {
         let mut args = Vec::with_capacity(500);
         let path = path.to_path_buf();
@@ -570,6 +594,13 @@ impl Helper {
             }
             _ => (),
         }
+        // every outgoing connection (p2p, remote nodes) goes through the SOCKS5 proxy
+        if let Some(socks5) = socks5 {
+            if !matches!(mode, StartOptionsMode::Custom) {
+                args.push("--socks5".to_string());
+                args.push(socks5.to_string());
+            }
+        }
         args
     }
 
@@ -642,6 +673,16 @@ impl Helper {
         let output_parse = Arc::clone(&process.lock().unwrap().output_parse);
         let output_pub = Arc::clone(&process.lock().unwrap().output_pub);
 
+        // Files of the data-api are only read when P2Pool rewrites them.
+        // The directories are created by the watcher, so the default files below can be written.
+        let mut data_api = DataApiWatcher::new(
+            &api_path_local,
+            &api_path_network,
+            &api_path_pool,
+            &api_path_p2p,
+            true,
+        );
+
         debug!("P2Pool | Cleaning old [local] API files...");
         // Attempt to remove stale API file
         match std::fs::remove_file(&api_path_local) {
@@ -750,47 +791,20 @@ impl Helper {
                     start.elapsed(),
                 );
 
-                // Read [local] API
-                debug!("P2Pool Watchdog | Attempting [local] API file read");
-                if let Ok(string) = Self::path_to_string(&api_path_local, ProcessName::P2pool) {
-                    // Deserialize
-                    if let Ok(local_api) = PrivP2poolLocalApi::from_str(&string) {
-                        // Update the structs.
-                        PubP2poolApi::update_from_local(&mut pub_api_lock, local_api);
-                    }
-                }
-                // Read [p2p] API
-                // allows to know if p2p is synced and connected to a Node.
-                debug!("P2Pool Watchdog | Attempting [p2p] API file read");
-                if let Ok(string) = Self::path_to_string(&api_path_p2p, ProcessName::P2pool) {
-                    // Deserialize
-                    if let Ok(p2p_api) = PrivP2PoolP2PApi::from_str(&string) {
-                        // Update the structs.
-                        PubP2poolApi::update_from_p2p(&mut pub_api_lock, p2p_api);
-                    }
+                // Read the files of the data-api rewritten by P2Pool
+                if Self::update_from_data_api(
+                    &mut data_api,
+                    &api_path_local,
+                    &api_path_network,
+                    &api_path_pool,
+                    &api_path_p2p,
+                    &mut pub_api_lock,
+                ) {
+                    last_p2pool_request = tokio::time::Instant::now();
                 }
-
                 // check if state must be changed based on local and p2p API
                 pub_api_lock.update_state(&mut process_lock);
 
-                debug!("P2Pool Watchdog | Attempting [network] & [pool] API file read");
-                if let (Ok(network_api), Ok(pool_api)) = (
-                    Self::path_to_string(&api_path_network, ProcessName::P2pool),
-                    Self::path_to_string(&api_path_pool, ProcessName::P2pool),
-                ) {
-                    if let (Ok(network_api), Ok(pool_api)) = (
-                        PrivP2poolNetworkApi::from_str(&network_api),
-                        PrivP2poolPoolApi::from_str(&pool_api),
-                    ) {
-                        PubP2poolApi::update_from_network_pool(
-                            &mut pub_api_lock,
-                            network_api,
-                            pool_api,
-                        );
-                        last_p2pool_request = tokio::time::Instant::now();
-                    }
-                }
-
                 let last_status_request_expired =
                     last_status_request.elapsed() >= Duration::from_secs(60);
                 if (last_status_request_expired || first_loop)
@@ -823,6 +837,171 @@ impl Helper {
         // 5. If loop broke, we must be done here.
         info!("P2Pool Watchdog | Watchdog thread exiting... Goodbye!");
     }
+    // Read the files of the data-api that changed since the last call.
+    // Returns true if the [network] and [pool] stats were updated.
+    fn update_from_data_api(
+        data_api: &mut DataApiWatcher,
+        api_path_local: &Path,
+        api_path_network: &Path,
+        api_path_pool: &Path,
+        api_path_p2p: &Path,
+        pub_api: &mut PubP2poolApi,
+    ) -> bool {
+        // Read [local] API
+        if data_api.take(&[DataApiFile::Local]) {
+            debug!("P2Pool Watchdog | Attempting [local] API file read");
+            if let Ok(string) = Self::path_to_string(api_path_local, ProcessName::P2pool) {
+                // Deserialize
+                if let Ok(local_api) = PrivP2poolLocalApi::from_str(&string) {
+                    // Update the structs.
+                    PubP2poolApi::update_from_local(pub_api, local_api);
+                }
+            }
+        }
+        // Read [p2p] API
+        // allows to know if p2p is synced and connected to a Node.
+        if data_api.take(&[DataApiFile::P2p]) {
+            debug!("P2Pool Watchdog | Attempting [p2p] API file read");
+            if let Ok(string) = Self::path_to_string(api_path_p2p, ProcessName::P2pool) {
+                // Deserialize
+                if let Ok(p2p_api) = PrivP2PoolP2PApi::from_str(&string) {
+                    // Update the structs.
+                    PubP2poolApi::update_from_p2p(pub_api, p2p_api);
+                }
+            }
+        }
+        // both are needed to update, so both are read if one changed
+        if data_api.take(&[DataApiFile::Network, DataApiFile::Pool]) {
+            debug!("P2Pool Watchdog | Attempting [network] & [pool] API file read");
+            if let (Ok(network_api), Ok(pool_api)) = (
+                Self::path_to_string(api_path_network, ProcessName::P2pool),
+                Self::path_to_string(api_path_pool, ProcessName::P2pool),
+            ) {
+                if let (Ok(network_api), Ok(pool_api)) = (
+                    PrivP2poolNetworkApi::from_str(&network_api),
+                    PrivP2poolPoolApi::from_str(&pool_api),
+                ) {
+                    PubP2poolApi::update_from_network_pool(pub_api, network_api, pool_api);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+    #[cold]
+    #[inline(never)]
+    // Monitor a P2Pool started outside of Gupaxx through its data-api, without owning the process.
+    // P2Pool must be started with --data-api <dir> --local-api.
+    fn attach_p2pool(helper: &Arc<Mutex<Self>>, state: &P2pool, path: &Path) {
+        // the image is filled from the settings, they should match the ones of the external P2Pool.
+        let (mut api_path_local, mut api_path_network, mut api_path_pool, mut api_path_p2p) =
+            Self::mutate_img_p2pool(state, helper, path);
+        if !state.attach_data_api.is_empty() {
+            (
+                api_path_local,
+                api_path_network,
+                api_path_pool,
+                api_path_p2p,
+            ) = Self::data_api_paths(PathBuf::from(&state.attach_data_api));
+        }
+        crate::disk::print_dash(&format!(
+            "P2Pool | Attaching | Local API Path: {:#?} | Network API Path: {:#?} | Pool API Path: {:#?} | P2P API Path {:#?}",
+            api_path_local, api_path_network, api_path_pool, api_path_p2p
+        ));
+        let process = Arc::clone(&helper.lock().unwrap().p2pool);
+        let gui_api = Arc::clone(&helper.lock().unwrap().gui_api_p2pool);
+        let pub_api = Arc::clone(&helper.lock().unwrap().pub_api_p2pool);
+        thread::spawn(move || {
+            Self::spawn_p2pool_attach_watchdog(
+                process,
+                gui_api,
+                pub_api,
+                api_path_local,
+                api_path_network,
+                api_path_pool,
+                api_path_p2p,
+            );
+        });
+    }
+    #[tokio::main]
+    #[allow(clippy::await_holding_lock)]
+    async fn spawn_p2pool_attach_watchdog(
+        process: Arc<Mutex<Process>>,
+        gui_api: Arc<Mutex<PubP2poolApi>>,
+        pub_api: Arc<Mutex<PubP2poolApi>>,
+        api_path_local: PathBuf,
+        api_path_network: PathBuf,
+        api_path_pool: PathBuf,
+        api_path_p2p: PathBuf,
+    ) {
+        let mut lock = process.lock().unwrap();
+        lock.state = ProcessState::Syncing;
+        lock.signal = ProcessSignal::None;
+        lock.start = Instant::now();
+        let start = lock.start;
+        let output_parse = Arc::clone(&lock.output_parse);
+        let output_pub = Arc::clone(&lock.output_pub);
+        drop(lock);
+        reset_data_p2pool(&pub_api, &gui_api);
+        let mut data_api = DataApiWatcher::new(
+            &api_path_local,
+            &api_path_network,
+            &api_path_pool,
+            &api_path_p2p,
+            // the data-api of the attached P2Pool is not ours
+            false,
+        );
+        output_console(
+            &mut gui_api.lock().unwrap().output,
+            &format!(
+                "Attached to the P2Pool running outside of Gupaxx, monitoring it through its data-api at {}",
+                api_path_local
+                    .parent()
+                    .and_then(|p| p.parent())
+                    .unwrap_or(&api_path_local)
+                    .display()
+            ),
+            ProcessName::P2pool,
+        );
+        let mut last_p2pool_request = tokio::time::Instant::now();
+        info!("P2Pool | Entering attached watchdog mode... woof!");
+        loop {
+            let now = Instant::now();
+            debug!("P2Pool Watchdog | ----------- Start of loop -----------");
+            {
+                gui_api.lock().unwrap().tick = (last_p2pool_request.elapsed().as_secs() % 60) as u8;
+                if signal_end_attached(
+                    &mut process.lock().unwrap(),
+                    &start,
+                    &mut gui_api.lock().unwrap().output,
+                ) {
+                    break;
+                }
+                let mut process_lock = process.lock().unwrap();
+                let mut pub_api_lock = pub_api.lock().unwrap();
+                // there is no output, only the uptime is updated
+                PubP2poolApi::update_from_output(
+                    &mut pub_api_lock,
+                    &output_parse,
+                    &output_pub,
+                    start.elapsed(),
+                );
+                if Self::update_from_data_api(
+                    &mut data_api,
+                    &api_path_local,
+                    &api_path_network,
+                    &api_path_pool,
+                    &api_path_p2p,
+                    &mut pub_api_lock,
+                ) {
+                    last_p2pool_request = tokio::time::Instant::now();
+                }
+                pub_api_lock.update_state(&mut process_lock);
+            }
+            sleep_end_loop(now, ProcessName::P2pool).await;
+        }
+        info!("P2Pool Watchdog | Attached watchdog thread exiting... Goodbye!");
+    }
     #[tokio::main]
     #[allow(clippy::await_holding_lock)]
     async fn watch_switch_p2pool_to_local_node(
@@ -831,6 +1010,7 @@ impl Helper {
         state_node: &Node,
         path_p2pool: &Path,
         backup_hosts: Option<Vec<PoolNode>>,
+        socks5: &Socks5,
     ) {
         // do not try to restart immediately after a first start, or else the two start will be in conflict.
         sleep(Duration::from_secs(10)).await;
@@ -851,7 +1031,15 @@ impl Helper {
                 drop(process);
                 drop(node_process);
                 drop(helper_lock);
-                Helper::restart_p2pool(helper, state, state_node, path_p2pool, backup_hosts, true);
+                Helper::restart_p2pool(
+                    helper,
+                    state,
+                    state_node,
+                    path_p2pool,
+                    backup_hosts,
+                    true,
+                    socks5,
+                );
                 break;
             }
             drop(gui_api);
@@ -935,8 +1123,7 @@ pub struct PubP2poolApi {
     pub monero_difficulty_u64: u64,
     pub p2pool_hashrate_u64: u64,
     pub monero_hashrate_u64: u64,
-    // Tick. Every loop this gets incremented.
-    // At 60, it indicated we should read the below API files.
+    // Seconds (modulo 60) since the [network] and [pool] API files were last updated.
     pub tick: u8,
     // Network API
     pub monero_difficulty: HumanNumber, // e.g: [15,000,000]
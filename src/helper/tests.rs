@@ -296,6 +296,7 @@ Uptime         = 0h 2m 4s
             &img_p2pool,
             &proxy_state,
             &p2pool_state,
+            &XvbEndpoints::default(),
         );
         println!("{:#?}", process);
         assert!(process.lock().unwrap().state == ProcessState::NotMining);
@@ -315,6 +316,7 @@ Uptime         = 0h 2m 4s
             &img_p2pool,
             &proxy_state,
             &p2pool_state,
+            &XvbEndpoints::default(),
         );
         assert!(process.lock().unwrap().state == ProcessState::Alive);
     }
@@ -520,23 +522,26 @@ Uptime         = 0h 2m 4s
         helper::{p2pool::PubP2poolApi, xrig::xmrig::PubXmrigApi},
     };
 
-    use crate::helper::xvb::{PubXvbApi, public_stats::XvbPubStats};
+    use crate::disk::xvb_samples::XvbSamples;
+    use crate::helper::xvb::{PubXvbApi, endpoints::XvbEndpoints, public_stats::XvbPubStats};
     use reqwest_middleware::ClientWithMiddleware as Client;
 
     #[test]
     fn public_api_deserialize() {
-        let client = client();
+        let client = client(None);
         let new_data = thread::spawn(move || corr(&client)).join().unwrap();
         assert!(!new_data.reward_yearly.is_empty());
     }
     #[tokio::main]
     async fn corr(client: &Client) -> XvbPubStats {
-        XvbPubStats::request_api(client).await.unwrap()
+        XvbPubStats::request_api(client, &XvbEndpoints::default())
+            .await
+            .unwrap()
     }
 
     #[test]
     fn test_manual_xvb_mode() {
-        let client = client();
+        let client = client(None);
         let pub_api = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xvb = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xmrig = Arc::new(Mutex::new(PubXmrigApi::new()));
@@ -553,6 +558,7 @@ Uptime         = 0h 2m 4s
             PathBuf::new(),
         )));
         let time_donated = Arc::new(Mutex::new(u32::default()));
+        let samples = Arc::new(Mutex::new(XvbSamples::new()));
         let rig = "test_rig";
         let xp_alive = false;
         let share = 1;
@@ -580,6 +586,7 @@ Uptime         = 0h 2m 4s
             &xmrig_img,
             &p2pool_img,
             &p2pool_process,
+            &samples,
         );
 
         assert_eq!(algo.stats.target_donation_hashrate, 1000.0);
@@ -587,7 +594,7 @@ Uptime         = 0h 2m 4s
 
     #[test]
     fn test_manual_p2pool_mode() {
-        let client = client();
+        let client = client(None);
         let pub_api = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xvb = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xmrig = Arc::new(Mutex::new(PubXmrigApi::new()));
@@ -596,6 +603,7 @@ Uptime         = 0h 2m 4s
         let token_xmrig = "12345678";
         let state_p2pool = P2pool::default();
         let time_donated = Arc::new(Mutex::new(u32::default()));
+        let samples = Arc::new(Mutex::new(XvbSamples::new()));
         let rig = "test_rig";
         let xp_alive = false;
         let share = 1;
@@ -631,6 +639,7 @@ Uptime         = 0h 2m 4s
             &xmrig_img,
             &p2pool_img,
             &p2pool_process,
+            &samples,
         );
 
         assert_eq!(algo.stats.target_donation_hashrate, 9000.0);
@@ -638,7 +647,7 @@ Uptime         = 0h 2m 4s
 
     #[test]
     fn test_manual_donor_level_mode_donor() {
-        let client = client();
+        let client = client(None);
         let pub_api = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xvb = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xmrig = Arc::new(Mutex::new(PubXmrigApi::new()));
@@ -647,6 +656,7 @@ Uptime         = 0h 2m 4s
         let token_xmrig = "12345678";
         let state_p2pool = P2pool::default();
         let time_donated = Arc::new(Mutex::new(u32::default()));
+        let samples = Arc::new(Mutex::new(XvbSamples::new()));
         let rig = "test_rig";
         let xp_alive = false;
         let share = 1;
@@ -687,6 +697,7 @@ Uptime         = 0h 2m 4s
             &xmrig_img,
             &p2pool_img,
             &p2pool_process,
+            &samples,
         );
 
         assert_eq!(algo.stats.target_donation_hashrate, 1000.0);
@@ -694,7 +705,7 @@ Uptime         = 0h 2m 4s
 
     #[test]
     fn test_auto_mode() {
-        let client = client();
+        let client = client(None);
         let pub_api = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xvb = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xmrig = Arc::new(Mutex::new(PubXmrigApi::new()));
@@ -703,6 +714,7 @@ Uptime         = 0h 2m 4s
         let token_xmrig = "12345678";
         let state_p2pool = P2pool::default();
         let time_donated = Arc::new(Mutex::new(u32::default()));
+        let samples = Arc::new(Mutex::new(XvbSamples::new()));
         let rig = "test_rig";
         let xp_alive = false;
         let share = 1;
@@ -738,6 +750,7 @@ Uptime         = 0h 2m 4s
             &xmrig_img,
             &p2pool_img,
             &p2pool_process,
+            &samples,
         );
 
         assert_eq!(algo.stats.target_donation_hashrate, 10000.0);
@@ -764,6 +777,7 @@ Uptime         = 0h 2m 4s
             &xmrig_img,
             &p2pool_img,
             &p2pool_process,
+            &samples,
         );
 
         assert_eq!(algo.stats.target_donation_hashrate, 1000.0);
@@ -771,7 +785,7 @@ Uptime         = 0h 2m 4s
 
     #[test]
     fn test_hero_mode() {
-        let client = client();
+        let client = client(None);
         let pub_api = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xvb = Arc::new(Mutex::new(PubXvbApi::new()));
         let gui_api_xmrig = Arc::new(Mutex::new(PubXmrigApi::new()));
@@ -780,6 +794,7 @@ Uptime         = 0h 2m 4s
         let token_xmrig = "12345678";
         let state_p2pool = P2pool::default();
         let time_donated = Arc::new(Mutex::new(u32::default()));
+        let samples = Arc::new(Mutex::new(XvbSamples::new()));
         let rig = "test_rig";
         let xp_alive = false;
         let share = 1;
@@ -816,6 +831,7 @@ Uptime         = 0h 2m 4s
             &xmrig_img,
             &p2pool_img,
             &p2pool_process,
+            &samples,
         );
 
         assert_eq!(algo.stats.target_donation_hashrate, 18460.7);
@@ -840,6 +856,7 @@ Uptime         = 0h 2m 4s
             &xmrig_img,
             &p2pool_img,
             &p2pool_process,
+            &samples,
         );
 
         assert_eq!(algo.stats.target_donation_hashrate, 20000.0);
@@ -862,6 +879,7 @@ Uptime         = 0h 2m 4s
             18083,
             18081,
             StartOptionsMode::Custom,
+            None,
         );
         assert_eq!(
             arguments
@@ -871,4 +889,1121 @@ Uptime         = 0h 2m 4s
             args
         );
     }
+
+    #[test]
+    fn socks5_args() {
+        use crate::disk::state::{FailoverPool, Node, Socks5, Xmrig};
+        let socks5 = Socks5 {
+            enabled: true,
+            ip: "localhost".to_string(),
+            ..Default::default()
+        };
+        let p2pool = P2pool::default();
+        let args = Helper::build_p2pool_args(
+            &p2pool,
+            Path::new(""),
+            &None,
+            false,
+            18083,
+            18081,
+            StartOptionsMode::Simple,
+            socks5.p2pool().as_deref(),
+        );
+        assert!(args.windows(2).any(|a| a == ["--socks5", "127.0.0.1:9050"]));
+        let args = Helper::build_node_args(
+            &Node::default(),
+            StartOptionsMode::Simple,
+            socks5.node().as_deref(),
+            socks5.tor,
+        );
+        assert!(args.windows(2).any(|a| a == ["--proxy", "127.0.0.1:9050"]));
+        assert!(
+            args.windows(2)
+                .any(|a| a == ["--tx-proxy", "tor,127.0.0.1:9050,16"])
+        );
+        // transactions are not sent to onion peers through a proxy which is not Tor
+        let args = Helper::build_node_args(
+            &Node::default(),
+            StartOptionsMode::Simple,
+            socks5.node().as_deref(),
+            false,
+        );
+        assert!(args.windows(2).any(|a| a == ["--proxy", "127.0.0.1:9050"]));
+        assert!(!args.iter().any(|a| a == "--tx-proxy"));
+        // monerod does not accept a hostname for its proxy
+        let hostname = Socks5 {
+            ip: "proxy.example.com".to_string(),
+            ..socks5.clone()
+        };
+        assert!(hostname.node_ip_invalid());
+        assert!(hostname.node().is_none());
+        assert_eq!(
+            hostname.p2pool(),
+            Some("proxy.example.com:9050".to_string())
+        );
+        assert!(!socks5.node_ip_invalid());
+        // disabled by default for XMRig, and never used when the proxy is off
+        assert!(socks5.xmrig().is_none());
+        // the proxy of a pool must follow its [--url], before the next pool
+        let xmrig = Xmrig {
+            ip: "pool.example.com".to_string(),
+            port: "3333".to_string(),
+            failover: ["failover.example.com", "127.0.0.1"]
+                .into_iter()
+                .map(|ip| FailoverPool {
+                    name: ip.to_string(),
+                    ip: ip.to_string(),
+                    port: "3333".to_string(),
+                    user: String::new(),
+                    rig: String::new(),
+                    tls: false,
+                    keepalive: false,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let args = Helper::build_xmrig_args(
+            &xmrig,
+            StartOptionsMode::Advanced,
+            3333,
+            Some("127.0.0.1:9050"),
+        );
+        let pools = args
+            .split(|a| a == "--url")
+            .skip(1)
+            .map(|pool| {
+                (
+                    pool[0].as_str(),
+                    pool.windows(2).any(|a| a == ["--proxy", "127.0.0.1:9050"]),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pools,
+            vec![
+                ("pool.example.com:3333", true),
+                ("failover.example.com:3333", true),
+                ("127.0.0.1:3333", false),
+            ]
+        );
+        let socks5 = Socks5::default();
+        assert!(socks5.node().is_none());
+        assert!(socks5.gupaxx().is_none());
+    }
+
+    #[test]
+    fn i2p_args() {
+        use crate::disk::state::{I2p, Node};
+        use crate::helper::node::PrivNodeConnectionsApi;
+        let node = Node {
+            simple: false,
+            i2p: I2p {
+                enabled: true,
+                inbound: true,
+                inbound_address: "abcd.b32.i2p".to_string(),
+                use_p2pool: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let args = Helper::build_node_args(&node, StartOptionsMode::Advanced, None, false);
+        assert!(
+            args.windows(2)
+                .any(|a| a == ["--tx-proxy", "i2p,127.0.0.1:4447,16"])
+        );
+        assert!(
+            args.windows(2)
+                .any(|a| a == ["--anonymous-inbound", "abcd.b32.i2p,127.0.0.1:18085,32"])
+        );
+        assert_eq!(node.i2p.p2pool(), Some("127.0.0.1:4447".to_string()));
+        // no inbound without the b32 address of the server tunnel
+        let i2p = I2p {
+            enabled: true,
+            inbound: true,
+            ..Default::default()
+        };
+        assert!(i2p.inbound().is_none());
+        assert!(i2p.p2pool().is_none());
+        assert!(I2p::default().proxy().is_none());
+
+        let data = r#"{"id":"0","jsonrpc":"2.0","result":{"connections":[{"address_type":1},{"address_type":3},{"address_type":4},{"address_type":2}],"status":"OK"}}"#;
+        let connections: PrivNodeConnectionsApi = serde_json::from_str(data).unwrap();
+        assert_eq!(connections.anonymous_connections(), 2);
+        let data = r#"{"id":"0","jsonrpc":"2.0","result":{"status":"OK"}}"#;
+        let connections: PrivNodeConnectionsApi = serde_json::from_str(data).unwrap();
+        assert_eq!(connections.anonymous_connections(), 0);
+    }
+
+    #[test]
+    fn data_api_watcher() {
+        use crate::helper::data_api::{DataApiFile, DataApiWatcher};
+        let dir = std::env::temp_dir().join("gupaxx_test_data_api_watcher");
+        let _ = std::fs::remove_dir_all(&dir);
+        let local = dir.join("local/stratum");
+        let network = dir.join("network/stats");
+        let pool = dir.join("pool/stats");
+        let p2p = dir.join("local/p2p");
+        // the directories of an attached P2Pool are not created, polling is used instead
+        let watcher = DataApiWatcher::new(&local, &network, &pool, &p2p, false);
+        assert!(!watcher.is_watching());
+        assert!(!dir.exists());
+        let mut watcher = DataApiWatcher::new(&local, &network, &pool, &p2p, true);
+        assert!(watcher.is_watching());
+        // everything is read the first time
+        assert!(watcher.take(&[DataApiFile::Local]));
+        assert!(watcher.take(&[DataApiFile::Network, DataApiFile::Pool]));
+        assert!(watcher.take(&[DataApiFile::P2p]));
+        assert!(!watcher.take(&[DataApiFile::Local]));
+        // only the rewritten file must be read again
+        std::fs::write(&pool, "{}").unwrap();
+        let mut changed = false;
+        for _ in 0..50 {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            if watcher.take(&[DataApiFile::Network, DataApiFile::Pool]) {
+                changed = true;
+                break;
+            }
+        }
+        assert!(changed);
+        assert!(!watcher.take(&[DataApiFile::Local]));
+        assert!(!watcher.take(&[DataApiFile::P2p]));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cpu_temperature() {
+        use crate::helper::cpu_temperature;
+        let sensors = [
+            ("nvme Composite", Some(95.0)),
+            ("coretemp Package id 0", Some(71.0)),
+            ("coretemp Core 3", Some(74.5)),
+            ("acpitz temp1", Some(40.0)),
+        ];
+        assert_eq!(cpu_temperature(sensors.into_iter()), Some(74.5));
+        let sensors = [("k10temp Tctl", Some(f32::NAN)), ("k10temp Tccd1", None)];
+        assert_eq!(cpu_temperature(sensors.into_iter()), None);
+        assert_eq!(
+            cpu_temperature([("amdgpu edge", Some(60.0))].into_iter()),
+            None
+        );
+    }
+
+    #[test]
+    fn xmrig_backends() {
+        use crate::helper::xrig::xmrig::{
+            PrivXmrigBackend, ThreadStatus, XmrigBackend, thread_statuses,
+        };
+        use crate::utils::topology::{CpuTopology, L3Cache, LogicalCpu};
+        let data = r#"[
+            {"type":"cpu","enabled":true,"algo":"rx/0","profile":"rx","hw-aes":true,"priority":-1,"msr":true,"asm":"ryzen","argon2-impl":"AVX2","hugepages":[3,4],"memory":8388608,
+             "hashrate":[4000.5,null,null],
+             "threads":[{"intensity":1,"affinity":0,"av":1,"hashrate":[1000.1,null,null]},{"intensity":1,"affinity":-1,"av":1,"hashrate":[1000.2,null,null]}]},
+            {"type":"opencl","enabled":false},
+            {"type":"cuda","enabled":false}
+        ]"#;
+        let backends: Vec<PrivXmrigBackend> = serde_json::from_str(data).unwrap();
+        let backends = backends
+            .into_iter()
+            .map(XmrigBackend::from)
+            .collect::<Vec<_>>();
+        assert_eq!(backends.len(), 3);
+        assert_eq!(backends[0].hugepages, "75% (3/4)");
+        assert_eq!(backends[0].memory_mb, 8);
+        assert_eq!(backends[0].threads[0].affinity, Some(0));
+        assert_eq!(backends[0].threads[1].affinity, None);
+        assert!(!backends[1].enabled && backends[1].threads.is_empty());
+
+        // 4 cores with SMT, CPU n and n+4 are siblings
+        let topology = CpuTopology {
+            cpus: (0..8)
+                .map(|id| LogicalCpu {
+                    id,
+                    core: (id % 4) as u32,
+                    node: 0,
+                    l3: 0,
+                })
+                .collect(),
+            l3_caches: vec![L3Cache {
+                size_kb: 16384,
+                cpus: (0..8).collect(),
+            }],
+        };
+        let h = |h: f32| [Some(h), None, None];
+        let threads = [
+            (Some(0), h(1000.0)),
+            (Some(1), h(1000.0)),
+            (Some(1), h(990.0)),
+            (Some(4), h(1000.0)),
+            (Some(2), h(600.0)),
+            (None, h(0.0)),
+        ];
+        assert_eq!(
+            thread_statuses(&threads, &topology),
+            vec![
+                ThreadStatus::SharedCore,
+                ThreadStatus::SharedCpu,
+                ThreadStatus::SharedCpu,
+                ThreadStatus::SharedCore,
+                ThreadStatus::Slow,
+                ThreadStatus::Stalled,
+            ]
+        );
+        // nothing is reported while the hashrate is measured, siblings are fine when every core is used
+        let threads = [
+            (Some(0), [None; 3]),
+            (Some(4), [None; 3]),
+            (Some(1), [None; 3]),
+            (Some(2), [None; 3]),
+            (Some(3), [None; 3]),
+        ];
+        assert!(
+            thread_statuses(&threads, &topology)
+                .iter()
+                .all(ThreadStatus::is_ok)
+        );
+    }
+
+    #[test]
+    fn xmrig_proxy_workers() {
+        use crate::helper::xrig::xmrig_proxy::{
+            PrivXmrigProxyMiners, PrivXmrigProxyWorkers, ProxyWorker, WorkerAlerts, WorkerStatus,
+        };
+        let workers = r#"{"mode":"rig_id","workers":[
+            ["rig1","192.168.1.10",1,120,0,2,12000000,1700000000000,10.5,10.1,10.0,9.8,9.7],
+            ["rig2","192.168.1.11",0,30,1,0,3000000,0,0.0,0.0,0.0,0.0,0.0]
+        ]}"#;
+        let miners = r#"{"format":["id","ip","tx","rx","state","diff","user","password","rig_id","agent"],"miners":[
+            [1,"192.168.1.10",100,200,2,120001,"x","x","rig1","XMRig/6.22.2 (Linux x86_64) libuv/1.48.0 gcc/13.2.1"]
+        ]}"#;
+        let workers: PrivXmrigProxyWorkers = serde_json::from_str(workers).unwrap();
+        let miners: PrivXmrigProxyMiners = serde_json::from_str(miners).unwrap();
+        let now = 1700000065000;
+        let mut workers = workers
+            .workers
+            .into_iter()
+            .map(|w| ProxyWorker::from_priv(w, now, Some(&miners)))
+            .collect::<Vec<_>>();
+        assert_eq!(workers[0].hashrate[0], 10500.0);
+        assert_eq!(workers[0].last_seen, Some(65));
+        assert_eq!(workers[0].invalid, 2);
+        assert!(
+            workers[0]
+                .agent
+                .as_ref()
+                .unwrap()
+                .starts_with("XMRig/6.22.2")
+        );
+        assert_eq!(workers[1].last_seen, None);
+        assert_eq!(workers[1].agent, None);
+
+        let mut alerts = WorkerAlerts::default();
+        assert!(alerts.check(&mut workers).is_empty());
+        assert_eq!(workers[0].status, WorkerStatus::Ok);
+        assert_eq!(workers[1].status, WorkerStatus::Offline);
+        // the hashrate drops, the alert is sent only once
+        workers[0].hashrate[0] = 4000.0;
+        assert_eq!(alerts.check(&mut workers).len(), 1);
+        assert_eq!(workers[0].status, WorkerStatus::Slow);
+        assert!(alerts.check(&mut workers).is_empty());
+        workers[0].hashrate[0] = 9000.0;
+        assert_eq!(alerts.check(&mut workers).len(), 1);
+        assert_eq!(workers[0].status, WorkerStatus::Ok);
+        // the worker loses its connection, then comes back
+        workers[0].connections = 0;
+        let msg = alerts.check(&mut workers);
+        assert_eq!(msg.len(), 1);
+        assert!(msg[0].contains("rig1") && msg[0].contains("missing"));
+        assert_eq!(workers[0].status, WorkerStatus::Missing);
+        assert!(alerts.check(&mut workers).is_empty());
+        workers[0].connections = 2;
+        assert_eq!(alerts.check(&mut workers).len(), 1);
+        assert_eq!(workers[0].status, WorkerStatus::Ok);
+        // the worker is removed from the list by the proxy
+        let mut workers = vec![workers.remove(1)];
+        assert_eq!(alerts.check(&mut workers).len(), 1);
+    }
+
+    #[test]
+    fn xvb_round_tracker() {
+        use crate::helper::xvb::{
+            priv_stats::XvbPrivStats,
+            public_stats::XvbPubStats,
+            rounds::{RoundTracker, XvbRound},
+        };
+        use std::time::{Duration, Instant};
+        let mut tracker = RoundTracker::default();
+        let mut stats_pub = XvbPubStats::default();
+        let stats_priv = XvbPrivStats {
+            donor_1hr_avg: 2.0,
+            donor_24hr_avg: 1.5,
+            round_participate: Some(XvbRound::Donor),
+            ..Default::default()
+        };
+        let address = "4AbCdEfG...12345678";
+        // nothing without public stats, the first raffle seen is only remembered
+        assert!(tracker.check(&stats_pub, &stats_priv, address, 0).is_none());
+        stats_pub.block_height = 100;
+        stats_pub.roll_winner = 5;
+        assert!(tracker.check(&stats_pub, &stats_priv, address, 0).is_none());
+        // 60 seconds on XvB at 1000 H/s, then 60 seconds on P2Pool
+        let start = Instant::now();
+        for i in 0..=120 {
+            tracker.tick(i <= 60, 1000.0, start + Duration::from_secs(i));
+        }
+        assert!(tracker.check(&stats_pub, &stats_priv, address, 0).is_none());
+        stats_pub.block_height = 101;
+        stats_pub.winner = address.to_string();
+        let round = tracker.check(&stats_pub, &stats_priv, address, 42).unwrap();
+        assert_eq!(round.timestamp, 42);
+        assert_eq!(round.block_height, 101);
+        assert_eq!(round.round, Some(XvbRound::Donor));
+        assert_eq!(round.donated_secs, 60);
+        assert_eq!(round.donated_hr_hours, 1000.0 * 60.0 / 3600.0);
+        assert!(round.won);
+        // the donation is reset for the next round
+        stats_pub.roll_round = 7;
+        stats_pub.winner = "someone".to_string();
+        let round = tracker.check(&stats_pub, &stats_priv, address, 43).unwrap();
+        assert_eq!(round.donated_secs, 0);
+        assert!(!round.won);
+    }
+
+    #[test]
+    fn xmrig_bench() {
+        use crate::disk::status::BenchSize;
+        use crate::helper::xrig::bench::{BenchOutput, bench_args};
+        assert_eq!(
+            bench_args(BenchSize::TenMillion, 4, true, true),
+            vec![
+                "--bench=10M",
+                "--threads",
+                "4",
+                "--no-color",
+                "--randomx-1gb-pages"
+            ]
+        );
+        assert_eq!(
+            bench_args(BenchSize::OneMillion, 1, false, true)
+                .last()
+                .unwrap(),
+            "--no-huge-pages"
+        );
+        let mut output = BenchOutput::default();
+        for line in [
+            " * HUGE PAGES   supported",
+            "[2025-01-01 12:00:00.000]  randomx  allocated 2336 MB (2080+256) huge pages 100% 1168/1168 +JIT (25 ms)",
+            "[2025-01-01 12:00:01.000]  cpu      READY threads 8/8 (8) huge pages 50% 4/8 memory 16384 KB (10 ms)",
+        ] {
+            output.update(line);
+        }
+        assert_eq!(output.seconds, None);
+        assert!(!output.hugepages());
+        output.update("[2025-01-01 12:04:11.000]  cpu      READY threads 8/8 (8) huge pages 100% 8/8 memory 16384 KB (10 ms)");
+        output.update("[2025-01-01 12:04:11.513]  bench    benchmark finished in 250.513 seconds (hash sum = 7F0B2F2FA6BA9A03)");
+        assert_eq!(output.seconds, Some(250.513));
+        assert!(output.hugepages());
+    }
+
+    #[tokio::test]
+    async fn xvb_mock_server() {
+        use crate::components::stratum::{PROBE_WORKER, probe_stratum};
+        use crate::disk::state::Xvb;
+        use crate::helper::xvb::{mock::MockXvb, nodes::Pool, priv_stats::XvbPrivStats};
+        let mock = MockXvb::start_with_nodes(&["European", "North America", "Asia"]).unwrap();
+        let endpoints = mock.endpoints();
+        // no retry so that the outages fail right away
+        let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+        let address = "4AbCdEfGhIjKlMnOpQrStUvWxYz";
+        // public stats and round changes
+        let stats = XvbPubStats::request_api(&client, &endpoints).await.unwrap();
+        assert_eq!(stats.block_height, 3000000);
+        mock.next_round("4AbCdEfG...UvWxYz");
+        let stats = XvbPubStats::request_api(&client, &endpoints).await.unwrap();
+        assert_eq!(stats.block_height, 3000001);
+        assert_eq!(stats.roll_round, 21);
+        assert_eq!(stats.winner, "4AbCdEfG...UvWxYz");
+        mock.script().public_online = false;
+        assert!(XvbPubStats::request_api(&client, &endpoints).await.is_err());
+        // private stats and token failure
+        {
+            let mut script = mock.script();
+            script.token = Some("123456789".to_string());
+            script.donor_1hr_avg = 2.5;
+        }
+        let stats = XvbPrivStats::request_api(&client, &endpoints, address, "123456789")
+            .await
+            .unwrap();
+        assert_eq!(stats.donor_1hr_avg, 2.5);
+        let err = XvbPrivStats::request_api(&client, &endpoints, address, "000000000")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("token is invalid"));
+        // stratum probe, with the job in the answer of the login or sent after
+        let (host, port) = (&endpoints.nodes[0].host, &endpoints.nodes[0].port);
+        let probe = probe_stratum(host, port, false, None).await.unwrap();
+        assert_eq!(probe.job_ms, Some(probe.login_ms));
+        assert_eq!(mock.script().logins, vec![PROBE_WORKER.to_string()]);
+        mock.script().nodes[0].job_delay = Some(std::time::Duration::from_millis(200));
+        let probe = probe_stratum(host, port, false, None).await.unwrap();
+        assert!(probe.job_ms.unwrap() >= probe.login_ms + 200);
+        mock.script().nodes[0].job_delay = None;
+        // a refused login is ranked by the time to connect
+        mock.script().nodes[0].refuse_login = true;
+        let probe = probe_stratum(host, port, false, None).await.unwrap();
+        assert!(probe.refused);
+        assert_eq!(probe.job_ms, None);
+        assert_eq!(probe.score(), probe.connect_ms);
+        mock.script().nodes[0].refuse_login = false;
+        // a remote pool is never joined directly when a proxy is given
+        assert!(
+            probe_stratum("pool.example.com", "3333", false, Some("127.0.0.1:1"))
+                .await
+                .is_err()
+        );
+        // pool selection by latency and failures, then with outages
+        let pub_api = Arc::new(Mutex::new(PubXvbApi::new()));
+        let gui_api = Arc::new(Mutex::new(PubXvbApi::new()));
+        let process = Arc::new(Mutex::new(Process::new(
+            ProcessName::Xvb,
+            "".to_string(),
+            PathBuf::new(),
+        )));
+        let process_p2pool = Arc::new(Mutex::new(Process::new(
+            ProcessName::P2pool,
+            "".to_string(),
+            PathBuf::new(),
+        )));
+        let p2pool_img = Arc::new(Mutex::new(ImgP2pool::new()));
+        let p2pool_state = P2pool::default();
+        let xvb_state = Xvb::default();
+        let fastest = || async {
+            Pool::update_fastest_pool(
+                &client,
+                &endpoints.with_listed_nodes(&client).await,
+                &pub_api,
+                &gui_api,
+                &process,
+                &process_p2pool,
+                &p2pool_img,
+                &p2pool_state,
+                &xvb_state,
+                None,
+            )
+            .await;
+            gui_api.lock().unwrap().stats_priv.pool.clone()
+        };
+        let node = |i: usize| Pool::xvb(endpoints.nodes[i].clone());
+        {
+            let mut script = mock.script();
+            script.nodes[0].latency = std::time::Duration::from_millis(300);
+            script.nodes[2].latency = std::time::Duration::from_millis(100);
+        }
+        assert_eq!(fastest().await, node(1));
+        assert_eq!(node(1).url(), endpoints.nodes[1].host);
+        assert_eq!(node(1).port(), endpoints.nodes[1].port);
+        assert!(!node(1).tls());
+        assert_eq!(
+            crate::regex::pool_from_address(&endpoints.nodes[2].address(), 3355, 3333, &endpoints),
+            Some(node(2))
+        );
+        // the pool in use fails, the next one of the ranking is used without probing again
+        assert_eq!(
+            gui_api.lock().unwrap().failover,
+            vec![endpoints.nodes[2].clone(), endpoints.nodes[0].clone()]
+        );
+        assert!(Pool::failover(&gui_api, &process));
+        assert_eq!(gui_api.lock().unwrap().stats_priv.pool, node(2));
+        assert_eq!(
+            gui_api.lock().unwrap().failover,
+            vec![endpoints.nodes[0].clone()]
+        );
+        // the fastest pool fails, the next one is used and the failure is remembered
+        mock.script().nodes[1].online = false;
+        let (host, port) = (&endpoints.nodes[1].host, &endpoints.nodes[1].port);
+        assert!(probe_stratum(host, port, false, None).await.is_err());
+        assert_eq!(fastest().await, node(2));
+        mock.script().nodes[1].online = true;
+        assert_eq!(fastest().await, node(2));
+        // a pool with a node not synchronized is not used, the one which failed is the last resort
+        mock.script().nodes[2].synchronized = false;
+        assert_eq!(fastest().await, node(0));
+        mock.script().nodes[2].synchronized = true;
+        // a pool removed from the list given by the API is not used anymore
+        mock.script().nodes[2].listed = false;
+        assert_eq!(fastest().await, node(0));
+        assert_eq!(endpoints.with_listed_nodes(&client).await.nodes.len(), 2);
+        // manual selection
+        let xvb_state = Xvb {
+            manual_pool_enabled: true,
+            manual_pool: "European".to_string(),
+            ..Default::default()
+        };
+        Pool::update_fastest_pool(
+            &client,
+            &endpoints,
+            &pub_api,
+            &gui_api,
+            &process,
+            &process_p2pool,
+            &p2pool_img,
+            &p2pool_state,
+            &xvb_state,
+            None,
+        )
+        .await;
+        assert_eq!(gui_api.lock().unwrap().stats_priv.pool, node(0));
+        // all pools are offline
+        for n in mock.script().nodes.iter_mut() {
+            n.online = false;
+        }
+        assert!(matches!(fastest().await, Pool::P2pool(_)));
+        assert_eq!(process.lock().unwrap().state, ProcessState::OfflinePoolsAll);
+    }
+
+    #[test]
+    fn xvb_rank_nodes() {
+        use crate::components::stratum::PROBE_TIMEOUT;
+        use crate::helper::xvb::{
+            endpoints::{XvbNode, validate_nodes},
+            nodes::{PoolFailures, XVB_POOL_FAILURE_WINDOW, rank_nodes},
+        };
+        use std::time::{Duration, Instant};
+        let node = |name: &str| XvbNode {
+            name: name.to_string(),
+            host: format!("{name}.example.com"),
+            port: "4247".to_string(),
+            tls: true,
+            rpc_port: None,
+        };
+        let (a, b, c) = (node("a"), node("b"), node("c"));
+        let pings = vec![
+            (a.clone(), Some(100)),
+            (b.clone(), Some(50)),
+            (c.clone(), None),
+        ];
+        let now = Instant::now();
+        let mut failures = PoolFailures::default();
+        // offline pools are not ranked
+        assert_eq!(
+            rank_nodes(pings.clone(), &failures, now),
+            vec![b.clone(), a.clone()]
+        );
+        // a recent failure ranks the pool after a slower one
+        failures.record(&b, now);
+        assert_eq!(failures.recent(&b, now), 1);
+        assert_eq!(
+            rank_nodes(pings.clone(), &failures, now),
+            vec![a.clone(), b.clone()]
+        );
+        // even when it answers the fastest possible and the other pool at the timeout
+        assert_eq!(
+            rank_nodes(
+                vec![
+                    (a.clone(), Some(PROBE_TIMEOUT.as_millis())),
+                    (b.clone(), Some(1))
+                ],
+                &failures,
+                now
+            ),
+            vec![a.clone(), b.clone()]
+        );
+        // the failure is forgotten after the window
+        let later = now + XVB_POOL_FAILURE_WINDOW + Duration::from_secs(1);
+        assert_eq!(failures.recent(&b, later), 0);
+        assert_eq!(
+            rank_nodes(pings, &failures, later),
+            vec![b.clone(), a.clone()]
+        );
+        // validation of the list of pools
+        assert!(validate_nodes(&[a.clone(), b.clone()]).is_ok());
+        assert!(validate_nodes(&[]).is_err());
+        assert!(validate_nodes(&[a.clone(), a.clone()]).is_err());
+        let mut invalid = c.clone();
+        invalid.port = "http".to_string();
+        assert!(validate_nodes(&[invalid]).is_err());
+    }
+    #[test]
+    fn xvb_cycle_ledger() {
+        use crate::helper::xvb::ledger::{CycleLedger, Delivery};
+        // 120s on XvB with 72000 of accepted difficulty is 120H/s over the cycle of 600s
+        let xvb = Delivery::new(120, Some(1000), Some(73000), 2000.0);
+        assert_eq!(xvb.difficulty, Some(72000));
+        assert_eq!(xvb.cycle_hashrate(), 120.0);
+        // the counter was reset by a restart of the miner
+        let restarted = Delivery::new(120, Some(50000), Some(6000), 2000.0);
+        assert_eq!(restarted.difficulty, Some(6000));
+        // without the counter, the hashrate of the miner is used for the time spent
+        let estimated = Delivery::new(480, None, Some(6000), 1000.0);
+        assert_eq!(estimated.difficulty, None);
+        assert_eq!(estimated.cycle_hashrate(), 800.0);
+        // no shares is a measure of 0
+        assert_eq!(
+            Delivery::new(0, Some(10), Some(10), 0.0).cycle_hashrate(),
+            0.0
+        );
+        let ledger = CycleLedger {
+            p2pool: estimated,
+            xvb,
+        };
+        assert_eq!(
+            ledger.summary(),
+            "Cycle delivered: P2Pool 480s, 0.800kH/s (estimated) | XvB 120s, 0.120kH/s (72000 accepted difficulty)"
+        );
+    }
+    #[test]
+    fn xvb_plan_miners() {
+        use crate::XVB_TIME_ALGO;
+        use crate::helper::xvb::ledger::Delivery;
+        use crate::helper::xvb::miners::plan_miners;
+        // nothing to donate
+        assert_eq!(plan_miners(&[1000.0, 3000.0], 0.0), vec![0, 0]);
+        // the rig of 3000H/s is dedicated, the local miner of 1000H/s does the rest
+        assert_eq!(
+            plan_miners(&[1000.0, 3000.0, 5000.0], 3500.0),
+            vec![300, XVB_TIME_ALGO, 0]
+        );
+        // the smallest miner left is switched
+        assert_eq!(
+            plan_miners(&[4000.0, 2000.0, 8000.0], 9000.0),
+            vec![0, 300, XVB_TIME_ALGO]
+        );
+        // a switch too short is not done, an almost full one is a dedicated miner
+        assert_eq!(
+            plan_miners(&[1000.0, 3000.0], 3001.0),
+            vec![0, XVB_TIME_ALGO]
+        );
+        assert_eq!(
+            plan_miners(&[1000.0, 3000.0], 3999.0),
+            vec![XVB_TIME_ALGO, XVB_TIME_ALGO]
+        );
+        // a miner without hashrate is not used
+        assert_eq!(plan_miners(&[0.0, 2000.0], 1000.0), vec![0, 300]);
+        // parts of a cycle on the same pool are added
+        let first = Delivery::new(300, Some(0), Some(30_000), 1000.0);
+        let second = Delivery::new(300, Some(30_000), Some(66_000), 1200.0);
+        let merged = Delivery {
+            remote: 500.0,
+            ..first.merge(second)
+        };
+        assert_eq!(merged.secs, 600);
+        assert_eq!(merged.difficulty, Some(66_000));
+        assert_eq!(merged.cycle_hashrate(), 610.0);
+        assert_eq!(Delivery::default().merge(first), first);
+    }
+    #[test]
+    fn xvb_plan_tiers() {
+        use crate::helper::xvb::planner::{PlannerInputs, plan_tiers};
+        use crate::helper::xvb::rounds::XvbRound;
+        let inputs = PlannerInputs {
+            hashrate: 12000.0,
+            share_min_hashrate: 2000.0,
+            xvb_external_hashrate: 500.0,
+        };
+        let plans = plan_tiers(&inputs, 0.0, 0.0);
+        assert_eq!(plans.len(), 4);
+        // the external hashrate is part of the donation
+        let donor = &plans[0];
+        assert_eq!(donor.round, XvbRound::Donor);
+        assert_eq!(donor.donation, 500.0);
+        assert_eq!(donor.coverage, Some(5.75));
+        // all the spareable hashrate with the external one is sent until the 24h average is reached
+        assert!((donor.hours.unwrap() - 24.0 * 1000.0 / 10500.0).abs() < 0.001);
+        let vip = &plans[1];
+        assert_eq!(vip.donation, 9500.0);
+        assert!((vip.fraction.unwrap() - 9500.0 / 12000.0).abs() < 0.001);
+        assert!(vip.keeps_share());
+        // more than the hashrate, the share would be lost and the round type can not be reached
+        let whale = &plans[2];
+        assert_eq!(whale.coverage, Some(0.0));
+        assert!(!whale.keeps_share());
+        assert_eq!(whale.hours, None);
+        // the 1h average is given a margin
+        let plans = plan_tiers(&inputs, 900.0, 2000.0);
+        assert_eq!(plans[0].hours, Some(0.0));
+        // no hashrate is needed for the share
+        let plans = plan_tiers(
+            &PlannerInputs {
+                share_min_hashrate: 0.0,
+                ..inputs
+            },
+            0.0,
+            0.0,
+        );
+        assert_eq!(plans[1].coverage, None);
+        assert!(plans[1].keeps_share());
+    }
+    #[test]
+    fn notify_events() {
+        use crate::helper::notify::{NotifyEvent, Snapshot, events};
+        let before = Snapshot {
+            payouts: 3,
+            xmr: 1_000_000,
+            shares: Some(2),
+            states: vec![
+                (ProcessName::Node, ProcessState::Alive),
+                (ProcessName::P2pool, ProcessState::Alive),
+                (ProcessName::Xvb, ProcessState::Alive),
+            ],
+            xvb_win: false,
+            update: None,
+        };
+        // nothing changed
+        assert!(events(&before, &before.clone()).is_empty());
+        let now = Snapshot {
+            payouts: 4,
+            xmr: 3_500_000,
+            shares: Some(3),
+            states: vec![
+                (ProcessName::Node, ProcessState::Syncing),
+                (ProcessName::P2pool, ProcessState::Failed),
+                (ProcessName::Xvb, ProcessState::OfflinePoolsAll),
+            ],
+            xvb_win: true,
+            update: Some("v1.11.0".to_string()),
+        };
+        let notifications = events(&before, &now);
+        assert_eq!(
+            notifications.iter().map(|n| n.event).collect::<Vec<_>>(),
+            vec![
+                NotifyEvent::Payout,
+                NotifyEvent::Share,
+                NotifyEvent::NodeSync,
+                NotifyEvent::Crash,
+                NotifyEvent::XvbOffline,
+                NotifyEvent::XvbWin,
+                NotifyEvent::Update,
+            ]
+        );
+        assert_eq!(
+            notifications[0].message,
+            "P2Pool payout received: 0.000002500000 XMR"
+        );
+        assert_eq!(notifications[3].message, "P2pool failed");
+        // the same state is not notified again
+        assert!(events(&now, &now.clone()).is_empty());
+        // a restart of P2Pool resets the shares, a Node starting to sync is not a lost sync
+        let stopped = Snapshot {
+            shares: Some(5),
+            states: vec![(ProcessName::Node, ProcessState::Dead)],
+            ..before.clone()
+        };
+        let restarted = Snapshot {
+            shares: Some(0),
+            states: vec![(ProcessName::Node, ProcessState::Syncing)],
+            ..before
+        };
+        assert!(events(&stopped, &restarted).is_empty());
+    }
+    #[test]
+    fn mqtt_parse_command() {
+        use crate::helper::ProcessSignal;
+        use crate::helper::mqtt::{MqttCommand, parse_command};
+        let prefix = "home/rig1";
+        assert_eq!(
+            parse_command(prefix, "home/rig1/p2pool/set", "start"),
+            Some(MqttCommand::Signal(
+                ProcessName::P2pool,
+                ProcessSignal::Start
+            ))
+        );
+        assert_eq!(
+            parse_command(prefix, "home/rig1/xmrig_proxy/set", " Restart\n"),
+            Some(MqttCommand::Signal(
+                ProcessName::XmrigProxy,
+                ProcessSignal::Restart
+            ))
+        );
+        assert_eq!(
+            parse_command(prefix, "home/rig1/xvb/set", "stop"),
+            Some(MqttCommand::Signal(ProcessName::Xvb, ProcessSignal::Stop))
+        );
+        assert_eq!(
+            parse_command(prefix, "home/rig1/xmrig/threads/set", "6"),
+            Some(MqttCommand::Threads(6))
+        );
+        // wrong prefix, process, payload or number of threads
+        assert_eq!(parse_command(prefix, "home/rig2/xmrig/set", "stop"), None);
+        assert_eq!(parse_command(prefix, "home/rig1xmrig/set", "stop"), None);
+        assert_eq!(parse_command(prefix, "home/rig1/monerod/set", "stop"), None);
+        assert_eq!(parse_command(prefix, "home/rig1/xmrig/set", "pause"), None);
+        assert_eq!(parse_command(prefix, "home/rig1/xmrig/state", "stop"), None);
+        assert_eq!(
+            parse_command(prefix, "home/rig1/xmrig/threads/set", "0"),
+            None
+        );
+        assert_eq!(
+            parse_command(prefix, "home/rig1/xmrig/threads/set", "-1"),
+            None
+        );
+    }
+    #[test]
+    fn mqtt_messages() {
+        use crate::helper::mqtt::{MqttStats, messages};
+        let stats = MqttStats {
+            states: vec![
+                (ProcessName::P2pool, ProcessState::Alive),
+                (ProcessName::Xvb, ProcessState::OfflinePoolsAll),
+            ],
+            xmrig_hashrate: 10_123.6,
+            xmrig_threads: "8".to_string(),
+            xp_hashrate: 0.0,
+            p2pool_hashrate: 9_800,
+            shares: None,
+            payouts: 3,
+            xmr: 1_500_000_000_000,
+            xvb_round: None,
+            xvb_donated_1h: 1_200.0,
+            xvb_donated_24h: 950.4,
+        };
+        let messages = messages("gupaxx", &stats);
+        let get = |topic: &str| {
+            messages
+                .iter()
+                .find(|(t, _)| t == topic)
+                .map(|(_, p)| p.as_str())
+        };
+        assert_eq!(get("gupaxx/p2pool/state"), Some("alive"));
+        assert_eq!(get("gupaxx/xvb/state"), Some("offline_pools_all"));
+        // only the processes given have a state
+        assert_eq!(get("gupaxx/xmrig/state"), None);
+        assert_eq!(get("gupaxx/xmrig/hashrate"), Some("10124"));
+        assert_eq!(get("gupaxx/xmrig/threads"), Some("8"));
+        assert_eq!(get("gupaxx/xmrig_proxy/hashrate"), Some("0"));
+        assert_eq!(get("gupaxx/p2pool/hashrate"), Some("9800"));
+        assert_eq!(get("gupaxx/p2pool/shares"), Some("0"));
+        assert_eq!(get("gupaxx/p2pool/payouts"), Some("3"));
+        assert_eq!(get("gupaxx/xvb/round"), Some("none"));
+        assert_eq!(get("gupaxx/xvb/donated_1h"), Some("1200"));
+        assert_eq!(get("gupaxx/xvb/donated_24h"), Some("950"));
+        assert!(get("gupaxx/p2pool/xmr").is_some_and(|x| x.starts_with("1.5")));
+        // every topic is published once
+        let mut topics = messages.iter().map(|(t, _)| t).collect::<Vec<_>>();
+        topics.sort();
+        topics.dedup();
+        assert_eq!(topics.len(), messages.len());
+    }
+
+    // summary of a XMRig started outside of Gupaxx, with the connection used by attach mode
+    const ATTACH_XMRIG_SUMMARY: &str = r#"{
+        "worker_id": "attached",
+        "resources": {
+            "load_average": [1.0, 1.0, 1.0]
+        },
+        "results": {
+            "diff_current": 100000,
+            "shares_good": 2,
+            "shares_total": 2,
+            "hashes_total": 200000
+        },
+        "connection": {
+            "pool": "127.0.0.1:3333",
+            "ip": "127.0.0.1",
+            "uptime": 42,
+            "uptime_ms": 42000,
+            "diff": 100000,
+            "accepted": 2,
+            "rejected": 0
+        },
+        "hashrate": {
+            "total": [1000.0, 1000.0, 1000.0],
+            "highest": 1000.0
+        }
+    }"#;
+    // summary of a XMRig-Proxy started outside of Gupaxx
+    const ATTACH_XP_SUMMARY: &str = r#"{
+        "hashrate": {
+            "total": [1.0, 1.0, 1.0, 1.0, 1.0, 1.0]
+        },
+        "miners": {
+            "now": 1,
+            "max": 1
+        },
+        "results": {
+            "accepted": 2,
+            "rejected": 0,
+            "hashes_total": 200000
+        },
+        "upstreams": {
+            "active": 1,
+            "sleep": 0,
+            "error": 0,
+            "total": 1
+        }
+    }"#;
+    // HTTP API of an external process, answering the paths given with their JSON.
+    fn serve_api(routes: Arc<Mutex<Vec<(&'static str, String)>>>) -> u16 {
+        use std::io::{BufRead, BufReader, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                if reader.read_line(&mut line).is_err() {
+                    continue;
+                }
+                let path = line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                // headers
+                let mut header = String::new();
+                while reader.read_line(&mut header).is_ok_and(|n| n > 2) {
+                    header.clear();
+                }
+                let body = routes
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|(route, _)| path == format!("/{route}"))
+                    .map(|(_, body)| body.clone());
+                let (status, body) = match body {
+                    Some(body) => ("200 OK", body),
+                    None => ("404 Not Found", String::new()),
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+        port
+    }
+    fn wait_for(what: &str, condition: impl Fn() -> bool) {
+        let start = std::time::Instant::now();
+        while !condition() {
+            assert!(
+                start.elapsed() < std::time::Duration::from_secs(10),
+                "timeout waiting for {what}"
+            );
+            thread::sleep(std::time::Duration::from_millis(100));
+        }
+    }
+    #[test]
+    fn xmrig_attach_watchdog() {
+        use crate::XMRIG_API_SUMMARY_ENDPOINT;
+        use crate::helper::{ProcessSignal, xvb::nodes::Pool};
+        let routes = Arc::new(Mutex::new(vec![(
+            XMRIG_API_SUMMARY_ENDPOINT,
+            ATTACH_XMRIG_SUMMARY.to_string(),
+        )]));
+        let port = serve_api(routes.clone());
+        let process = Arc::new(Mutex::new(Process::new(
+            ProcessName::Xmrig,
+            String::new(),
+            PathBuf::new(),
+        )));
+        let gui_api = Arc::new(Mutex::new(PubXmrigApi::new()));
+        let pub_api = Arc::new(Mutex::new(PubXmrigApi::new()));
+        let watchdog = thread::spawn({
+            let (process, gui_api, pub_api) = (process.clone(), gui_api.clone(), pub_api.clone());
+            move || {
+                Helper::spawn_xmrig_attach_watchdog(
+                    &process,
+                    &gui_api,
+                    &pub_api,
+                    &format!("127.0.0.1:{port}"),
+                    "",
+                    (
+                        &Arc::new(Mutex::new(Process::new(
+                            ProcessName::P2pool,
+                            String::new(),
+                            PathBuf::new(),
+                        ))),
+                        &Arc::new(Mutex::new(ImgP2pool::new())),
+                        &P2pool::default(),
+                    ),
+                    (
+                        &Arc::new(Mutex::new(Process::new(
+                            ProcessName::XmrigProxy,
+                            String::new(),
+                            PathBuf::new(),
+                        ))),
+                        &Arc::new(Mutex::new(ImgProxy::new())),
+                        &XmrigProxy::default(),
+                    ),
+                    &Arc::new(Mutex::new(XvbEndpoints::default())),
+                )
+            }
+        });
+        // connected to the local P2Pool
+        wait_for("XMRig mining", || {
+            process.lock().unwrap().state == ProcessState::Alive
+        });
+        assert_eq!(pub_api.lock().unwrap().pool, Some(Pool::P2pool(3333)));
+        assert_eq!(pub_api.lock().unwrap().hashes_total, Some(200000));
+        // not connected to a pool
+        routes.lock().unwrap()[0].1 =
+            ATTACH_XMRIG_SUMMARY.replace("\"uptime\": 42", "\"uptime\": 0");
+        wait_for("XMRig not mining", || {
+            process.lock().unwrap().state == ProcessState::NotMining
+        });
+        assert_eq!(pub_api.lock().unwrap().pool, None);
+        // the API is gone, the process is not considered dead since it was not started by Gupaxx
+        routes.lock().unwrap().clear();
+        wait_for("XMRig API unreachable", || {
+            gui_api
+                .lock()
+                .unwrap()
+                .output
+                .contains("The HTTP API of XMRig is not reachable")
+        });
+        assert_eq!(process.lock().unwrap().state, ProcessState::NotMining);
+        // stop only detaches
+        process.lock().unwrap().signal = ProcessSignal::Stop;
+        watchdog.join().unwrap();
+        assert_eq!(process.lock().unwrap().state, ProcessState::Dead);
+        assert!(
+            gui_api
+                .lock()
+                .unwrap()
+                .output
+                .contains("The external process is still running")
+        );
+    }
+    #[test]
+    fn xmrig_proxy_attach_watchdog() {
+        use crate::XMRIG_API_SUMMARY_ENDPOINT;
+        use crate::helper::ProcessSignal;
+        let routes = Arc::new(Mutex::new(vec![(
+            XMRIG_API_SUMMARY_ENDPOINT,
+            ATTACH_XP_SUMMARY.to_string(),
+        )]));
+        let port = serve_api(routes.clone());
+        let state = XmrigProxy {
+            simple: false,
+            attach: true,
+            api_port: port.to_string(),
+            ..Default::default()
+        };
+        let process = Arc::new(Mutex::new(Process::new(
+            ProcessName::XmrigProxy,
+            String::new(),
+            PathBuf::new(),
+        )));
+        let gui_api = Arc::new(Mutex::new(PubXmrigProxyApi::new()));
+        let pub_api = Arc::new(Mutex::new(PubXmrigProxyApi::new()));
+        let watchdog = thread::spawn({
+            let (process, gui_api, pub_api) = (process.clone(), gui_api.clone(), pub_api.clone());
+            move || Helper::spawn_xp_attach_watchdog(&process, &gui_api, &pub_api, &state)
+        });
+        // an active upstream is a connection to a pool
+        wait_for("XMRig-Proxy mining", || {
+            process.lock().unwrap().state == ProcessState::Alive
+        });
+        assert_eq!(pub_api.lock().unwrap().hashes_total, Some(200000));
+        // older XMRig-Proxy without the upstreams and the total of hashes
+        let summary = ATTACH_XP_SUMMARY.replace(",\n            \"hashes_total\": 200000", "");
+        let (summary, _) = summary.split_once(",\n        \"upstreams\"").unwrap();
+        routes.lock().unwrap()[0].1 = format!("{summary}\n    }}");
+        wait_for("XMRig-Proxy not mining", || {
+            process.lock().unwrap().state == ProcessState::NotMining
+        });
+        assert_eq!(pub_api.lock().unwrap().hashes_total, None);
+        process.lock().unwrap().signal = ProcessSignal::Stop;
+        watchdog.join().unwrap();
+        assert_eq!(process.lock().unwrap().state, ProcessState::Dead);
+    }
 }
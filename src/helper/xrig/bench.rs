@@ -0,0 +1,190 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Local benchmark of XMRig, started from the [Benchmarks] submenu of the [Status] tab.
+// [xmrig --bench] computes a fixed number of hashes offline, the time it took gives the hashrate.
+// The result is kept in the state next to the reference benchmarks of [assets/cpu.json].
+
+use std::{
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{Arc, Mutex, mpsc},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Result, anyhow};
+use log::{info, warn};
+
+use crate::{
+    disk::status::{BenchSize, LocalBenchmark},
+    regex::XMRIG_REGEX,
+    utils::hugepages::{HugepagesReport, MemoryState},
+};
+
+#[derive(Debug, Default)]
+pub struct PubBenchApi {
+    pub running: bool,
+    // set by the GUI to stop the benchmark
+    pub cancel: bool,
+    // last line printed by XMRig, or the error of the last run
+    pub progress: String,
+    // result of the last run, taken by the GUI to save it in the state
+    pub result: Option<LocalBenchmark>,
+}
+
+impl PubBenchApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn start(
+        bench: &Arc<Mutex<Self>>,
+        path: &Path,
+        size: BenchSize,
+        threads: u16,
+        hugepages: bool,
+    ) {
+        let pages_1gb = MemoryState::detect().pages_1gb > 0;
+        let args = bench_args(size, threads, hugepages, pages_1gb);
+        info!("Benchmark | Starting XMRig with: {:?}", args);
+        {
+            let mut lock = bench.lock().unwrap();
+            lock.running = true;
+            lock.cancel = false;
+            lock.progress = "Starting XMRig...".to_string();
+            lock.result = None;
+        }
+        let bench = Arc::clone(bench);
+        let path = path.to_path_buf();
+        thread::spawn(move || {
+            let result =
+                Self::run(&bench, path, args).map(|(seconds, hugepages_ok)| LocalBenchmark {
+                    date: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+                    size,
+                    threads,
+                    hugepages: hugepages && hugepages_ok,
+                    seconds,
+                    hashrate: size.hashes() as f32 / seconds,
+                });
+            let mut lock = bench.lock().unwrap();
+            lock.running = false;
+            match result {
+                Ok(result) => {
+                    info!("Benchmark | {} H/s", result.hashrate);
+                    lock.progress.clear();
+                    lock.result = Some(result);
+                }
+                Err(e) => {
+                    warn!("Benchmark | Failed: {}", e);
+                    lock.progress = format!("Benchmark failed: {e}");
+                }
+            }
+        });
+    }
+    // Run XMRig until it prints the result, returns the seconds and if the huge pages were allocated.
+    fn run(bench: &Arc<Mutex<Self>>, path: PathBuf, args: Vec<String>) -> Result<(f32, bool)> {
+        let mut cmd = Command::new(&path);
+        cmd.args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .stdin(Stdio::null());
+        if let Some(dir) = path.parent() {
+            cmd.current_dir(dir);
+        }
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            cmd.creation_flags(0x08000000);
+        }
+        let mut child = cmd.spawn()?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("no output from XMRig"))?;
+        // the lines are read in another thread so that the benchmark can be canceled at any time.
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let mut output = BenchOutput::default();
+        loop {
+            if bench.lock().unwrap().cancel {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(anyhow!("canceled"));
+            }
+            match receiver.recv_timeout(Duration::from_millis(500)) {
+                Ok(line) => {
+                    output.update(&line);
+                    if let Some(seconds) = output.seconds {
+                        // XMRig may keep running after printing the result
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        return Ok((seconds, output.hugepages()));
+                    }
+                    bench.lock().unwrap().progress = line;
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    let status = child.wait()?;
+                    return Err(anyhow!("XMRig exited before the end ({status})"));
+                }
+            }
+        }
+    }
+}
+
+pub fn bench_args(size: BenchSize, threads: u16, hugepages: bool, pages_1gb: bool) -> Vec<String> {
+    let mut args = vec![
+        format!("--bench={}", size.arg()),
+        "--threads".to_string(),
+        threads.to_string(),
+        "--no-color".to_string(),
+    ];
+    if !hugepages {
+        args.push("--no-huge-pages".to_string());
+    } else if pages_1gb {
+        args.push("--randomx-1gb-pages".to_string());
+    }
+    args
+}
+
+// What is parsed from the output of a benchmark.
+#[derive(Debug, Default)]
+pub struct BenchOutput {
+    pub report: HugepagesReport,
+    pub seconds: Option<f32>,
+}
+
+impl BenchOutput {
+    pub fn update(&mut self, line: &str) {
+        self.report.update_from_output(line);
+        if let Some(c) = XMRIG_REGEX.bench_finished.captures(line) {
+            self.seconds = c[1].parse().ok().filter(|s: &f32| *s > 0.0);
+        }
+    }
+    // the dataset and every thread got their huge pages
+    pub fn hugepages(&self) -> bool {
+        self.report.dataset.is_some_and(|a| a.complete())
+            && self.report.threads.is_none_or(|a| a.complete())
+    }
+}
@@ -29,10 +29,14 @@ use serde_json::Value;
 use xmrig::ImgXmrig;
 use xmrig_proxy::ImgProxy;
 
+pub mod bench;
+pub mod throttle;
 pub mod xmrig;
 pub mod xmrig_proxy;
 
 // update config of xmrig or xmrig-proxy
+// the SOCKS5 proxy is only used for remote pools, local P2Pool/Proxy can not be joined through it.
+#[allow(clippy::too_many_arguments)]
 pub async fn update_xmrig_config(
     client: &Client,
     api_uri: &str,
@@ -40,6 +44,7 @@ pub async fn update_xmrig_config(
     node: &Pool,
     address: &str,
     rig: &str,
+    socks5: Option<&str>,
 ) -> Result<()> {
     // get config
     let request = client
@@ -70,6 +75,17 @@ pub async fn update_xmrig_config(
         .pointer_mut("/pools/0/keepalive")
         .ok_or_else(|| anyhow!("pools/0/keepalive does not exist in xmrig config"))? =
         node.keepalive().into();
+    let socks5 = match socks5 {
+        Some(socks5) if !matches!(node.url().as_str(), "127.0.0.1" | "localhost") => {
+            Value::from(socks5)
+        }
+        _ => Value::Null,
+    };
+    config
+        .pointer_mut("/pools/0")
+        .and_then(|pool| pool.as_object_mut())
+        .ok_or_else(|| anyhow!("pools/0 does not exist in xmrig config"))?
+        .insert("socks5".to_string(), socks5);
     // send new config
     client
         .put(api_uri)
@@ -81,11 +97,53 @@ pub async fn update_xmrig_config(
         .await?;
     anyhow::Ok(())
 }
+// first pool of the config of xmrig or xmrig-proxy, to put it back later with [set_xmrig_pool].
+pub async fn get_xmrig_pool(client: &Client, api_uri: &str, token: &str) -> Result<Value> {
+    let config = client
+        .get(api_uri)
+        .header(AUTHORIZATION, ["Bearer ", token].concat())
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await?
+        .json::<Value>()
+        .await?;
+    config
+        .pointer("/pools/0")
+        .cloned()
+        .ok_or_else(|| anyhow!("pools/0 does not exist in xmrig config"))
+}
+pub async fn set_xmrig_pool(
+    client: &Client,
+    api_uri: &str,
+    token: &str,
+    pool: Value,
+) -> Result<()> {
+    let mut config = client
+        .get(api_uri)
+        .header(AUTHORIZATION, ["Bearer ", token].concat())
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await?
+        .json::<Value>()
+        .await?;
+    *config
+        .pointer_mut("/pools/0")
+        .ok_or_else(|| anyhow!("pools/0 does not exist in xmrig config"))? = pool;
+    client
+        .put(api_uri)
+        .header("Authorization", ["Bearer ", token].concat())
+        .header("Content-Type", "application/json")
+        .timeout(std::time::Duration::from_secs(5))
+        .body(config.to_string())
+        .send()
+        .await?;
+    anyhow::Ok(())
+}
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 struct Hashrate {
     total: [Option<f32>; 3],
 }
-
+/// Take the runtime IP and port. Even if settings were changed, they will be the current ones.
 /// Take the runtime port. Even if settings were changed, the port will be the current one.
 /// to get config url, true. False for summary
 /// provide either xmrig_img or proxy_img
@@ -94,20 +152,17 @@ pub fn current_api_url_xrig(
     xmrig_img: Option<&ImgXmrig>,
     proxy_img: Option<&ImgProxy>,
 ) -> String {
-    let (port, endpoint) = if let Some(xmrig) = xmrig_img {
-        if config {
-            (xmrig.api_port, XMRIG_API_CONFIG_ENDPOINT)
-        } else {
-            (xmrig.api_port, XMRIG_API_SUMMARY_ENDPOINT)
-        }
+    let endpoint = if config {
+        XMRIG_API_CONFIG_ENDPOINT
+    } else {
+        XMRIG_API_SUMMARY_ENDPOINT
+    };
+    let (ip, port) = if let Some(xmrig) = xmrig_img {
+        (&xmrig.api_ip, xmrig.api_port)
     } else if let Some(proxy) = proxy_img {
-        if config {
-            (proxy.api_port, XMRIG_API_CONFIG_ENDPOINT)
-        } else {
-            (proxy.api_port, XMRIG_API_SUMMARY_ENDPOINT)
-        }
+        (&proxy.api_ip, proxy.api_port)
     } else {
         panic!("neither xmrig_img or proxy_img is some");
     };
-    format!("http://127.0.0.1:{port}/{endpoint}")
+    format!("http://{ip}:{port}/{endpoint}")
 }
@@ -0,0 +1,250 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Throttling of XMRig by the temperature of the CPU, which is read by the helper thread.
+// Above the soft threshold, half of the threads are stopped through the config API.
+// Above the hard threshold, XMRig is paused through the JSON-RPC API.
+// A level is left only after the temperature stayed below its threshold for the cooldown.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use reqwest::header::AUTHORIZATION;
+use reqwest_middleware::ClientWithMiddleware as Client;
+use serde_json::{Value, json};
+
+use crate::disk::state::Xmrig;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ThrottleLevel {
+    Normal,
+    Reduced,
+    Paused,
+}
+
+pub struct Throttle {
+    soft: f32,
+    hard: f32,
+    cooldown: Duration,
+    threads: u16, // threads XMRig was started with
+    pub level: ThrottleLevel,
+    cooling_since: Option<Instant>, // since when the temperature is below the threshold of the current level
+    saved_cpu: Option<Value>,       // [cpu] object of the config before the threads were reduced
+}
+
+impl Throttle {
+    // None if the throttling is disabled
+    pub fn new(state: &Xmrig, threads: u16) -> Option<Self> {
+        if !state.throttle {
+            return None;
+        }
+        Some(Self {
+            soft: state.temp_soft as f32,
+            hard: state.temp_hard as f32,
+            cooldown: Duration::from_secs(state.throttle_cooldown as u64),
+            threads,
+            level: ThrottleLevel::Normal,
+            cooling_since: None,
+            saved_cpu: None,
+        })
+    }
+    pub fn reduced_threads(&self) -> u16 {
+        (self.threads / 2).max(1)
+    }
+    // message for the console once XMRig is at the current level
+    pub fn message(&self, temp: f32) -> String {
+        match self.level {
+            ThrottleLevel::Paused => format!(
+                "CPU temperature at {temp:.1}°C, above {}°C: XMRig is paused",
+                self.hard
+            ),
+            ThrottleLevel::Reduced => format!(
+                "CPU temperature at {temp:.1}°C, above {}°C: XMRig is using {} threads out of {}",
+                self.soft,
+                self.reduced_threads(),
+                self.threads
+            ),
+            ThrottleLevel::Normal => format!(
+                "CPU temperature at {temp:.1}°C: XMRig is using its {} threads again",
+                self.threads
+            ),
+        }
+    }
+    // The level XMRig should be at, if it is not the current one.
+    // A hotter level is reached at once, a cooler one only after the cooldown.
+    pub fn next_level(&mut self, temp: f32, now: Instant) -> Option<ThrottleLevel> {
+        let target = if temp >= self.hard {
+            ThrottleLevel::Paused
+        } else if temp >= self.soft {
+            ThrottleLevel::Reduced
+        } else {
+            ThrottleLevel::Normal
+        };
+        if target >= self.level {
+            self.cooling_since = None;
+            return (target > self.level).then_some(target);
+        }
+        let since = *self.cooling_since.get_or_insert(now);
+        (now.duration_since(since) >= self.cooldown).then_some(target)
+    }
+    // Bring XMRig to the level. The current level only changes if every request succeeded.
+    pub async fn apply(
+        &mut self,
+        client: &Client,
+        api_uri_config: &str,
+        api_uri_rpc: &str,
+        token: &str,
+        level: ThrottleLevel,
+    ) -> Result<()> {
+        let reduce = level >= ThrottleLevel::Reduced;
+        if reduce != (self.level >= ThrottleLevel::Reduced) {
+            self.set_threads(client, api_uri_config, token, reduce)
+                .await?;
+        }
+        let pause = level == ThrottleLevel::Paused;
+        if pause != (self.level == ThrottleLevel::Paused) {
+            let method = if pause { "pause" } else { "resume" };
+            client
+                .post(api_uri_rpc)
+                .header(AUTHORIZATION, ["Bearer ", token].concat())
+                .timeout(Duration::from_secs(5))
+                .header("Content-Type", "application/json")
+                .body(json!({"jsonrpc": "2.0", "id": 1, "method": method}).to_string())
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+        self.level = level;
+        self.cooling_since = None;
+        Ok(())
+    }
+    // reduce the threads of the RandomX profile or restore the [cpu] object saved before.
+    async fn set_threads(
+        &mut self,
+        client: &Client,
+        api_uri_config: &str,
+        token: &str,
+        reduce: bool,
+    ) -> Result<()> {
+        let mut config = client
+            .get(api_uri_config)
+            .header(AUTHORIZATION, ["Bearer ", token].concat())
+            .send()
+            .await?
+            .json::<Value>()
+            .await?;
+        let cpu = config
+            .get_mut("cpu")
+            .ok_or_else(|| anyhow!("cpu does not exist in xmrig config"))?;
+        if reduce {
+            // a failed attempt may have reduced the threads already, keep the original object
+            if self.saved_cpu.is_none() {
+                self.saved_cpu = Some(cpu.clone());
+            }
+            *cpu = reduce_cpu_threads(cpu.clone(), self.reduced_threads());
+        } else if let Some(saved) = self.saved_cpu.take() {
+            *cpu = saved;
+        }
+        client
+            .put(api_uri_config)
+            .header(AUTHORIZATION, ["Bearer ", token].concat())
+            .header("Content-Type", "application/json")
+            .timeout(Duration::from_secs(5))
+            .body(config.to_string())
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+// The RandomX profile keeps the affinity of its first threads, or has no affinity at all.
+pub fn reduce_cpu_threads(mut cpu: Value, threads: u16) -> Value {
+    let profile = match cpu.get("rx").and_then(Value::as_array) {
+        Some(rx) if rx.len() >= threads as usize && rx.iter().all(Value::is_i64) => {
+            rx[..threads as usize].to_vec()
+        }
+        _ => vec![json!(-1); threads as usize],
+    };
+    if let Some(cpu) = cpu.as_object_mut() {
+        // a profile of the exact algorithm would be used before the one of the family
+        cpu.remove("rx/0");
+        cpu.insert("rx".to_string(), Value::Array(profile));
+    }
+    cpu
+}
+
+//---------------------------------------------------------------------------------------------------- TEST
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn throttle_levels() {
+        let state = Xmrig {
+            throttle: true,
+            temp_soft: 80,
+            temp_hard: 90,
+            throttle_cooldown: 60,
+            ..Default::default()
+        };
+        let mut throttle = Throttle::new(&state, 8).unwrap();
+        assert_eq!(throttle.reduced_threads(), 4);
+        let start = Instant::now();
+        assert_eq!(throttle.next_level(70.0, start), None);
+        // hotter levels are reached at once
+        assert_eq!(
+            throttle.next_level(85.0, start),
+            Some(ThrottleLevel::Reduced)
+        );
+        throttle.level = ThrottleLevel::Reduced;
+        assert_eq!(
+            throttle.next_level(92.0, start),
+            Some(ThrottleLevel::Paused)
+        );
+        throttle.level = ThrottleLevel::Paused;
+        // cooler levels only after the cooldown
+        assert_eq!(throttle.next_level(70.0, start), None);
+        let later = start + Duration::from_secs(30);
+        assert_eq!(throttle.next_level(75.0, later), None);
+        // heating again restarts the cooldown
+        assert_eq!(throttle.next_level(91.0, later), None);
+        assert_eq!(throttle.next_level(70.0, later), None);
+        assert_eq!(
+            throttle.next_level(70.0, start + Duration::from_secs(80)),
+            None
+        );
+        assert_eq!(
+            throttle.next_level(82.0, later + Duration::from_secs(60)),
+            Some(ThrottleLevel::Reduced)
+        );
+        assert!(Throttle::new(&Xmrig::default(), 8).is_none());
+    }
+
+    #[test]
+    fn reduce_threads() {
+        let cpu = json!({"enabled": true, "rx": [0, 2, 4, 6], "rx/0": [0, 1]});
+        assert_eq!(
+            reduce_cpu_threads(cpu, 2),
+            json!({"enabled": true, "rx": [0, 2]})
+        );
+        // profile with options or missing
+        let cpu = json!({"rx": {"threads": 8}});
+        assert_eq!(reduce_cpu_threads(cpu, 3), json!({"rx": [-1, -1, -1]}));
+        assert_eq!(reduce_cpu_threads(json!({}), 1), json!({"rx": [-1]}));
+    }
+}
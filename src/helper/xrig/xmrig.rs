@@ -1,15 +1,25 @@
 use crate::constants::*;
-use crate::disk::state::{P2pool, StartOptionsMode, XmrigProxy};
+use crate::disk::get_gupax_data_path;
+use crate::disk::state::{P2pool, Socks5, StartOptionsMode, Xmrig, XmrigProxy};
+use crate::disk::xmrig_config::{get_xmrig_config_path, write_xmrig_config};
 use crate::helper::p2pool::ImgP2pool;
+use crate::helper::xrig::throttle::Throttle;
 use crate::helper::xrig::update_xmrig_config;
-use crate::helper::{Helper, ProcessName, ProcessSignal, ProcessState};
+use crate::helper::xvb::endpoints::XvbEndpoints;
+use crate::helper::{Helper, ProcessName, ProcessSignal, ProcessState, Sys};
 use crate::helper::{Pool, PubXvbApi};
-use crate::helper::{Process, arc_mut, check_died, check_user_input, sleep, sleep_end_loop};
+use crate::helper::{
+    Process, arc_mut, check_died, check_user_input, signal_end_attached, sleep, sleep_end_loop,
+};
 use crate::human::HumanTime;
 use crate::miscs::{client, output_console};
-use crate::regex::{XMRIG_REGEX, contains_error, contains_usepool, detect_pool_xmrig};
+use crate::regex::{
+    XMRIG_REGEX, contains_error, contains_usepool, detect_pool_xmrig, pool_from_address,
+};
+use crate::utils::hugepages::{HugepagesReport, MemoryState};
 use crate::utils::human::HumanNumber;
 use crate::utils::sudo::SudoState;
+use crate::utils::topology::{CPU_TOPOLOGY, CpuTopology};
 use enclose::{enc, enclose};
 use log::*;
 use portable_pty::Child;
@@ -48,6 +58,7 @@ impl Helper {
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         proxy_img: &Arc<Mutex<ImgProxy>>,
         proxy_state: &XmrigProxy,
+        xvb_endpoints: &Arc<Mutex<XvbEndpoints>>,
     ) {
         use std::io::BufRead;
         let mut stdout = std::io::BufReader::new(reader).lines();
@@ -115,6 +126,7 @@ impl Helper {
                             process_p2pool.lock().unwrap().is_alive(),
                             &p2pool_img.lock().unwrap(),
                         ),
+                        &xvb_endpoints.lock().unwrap(),
                     );
                     if pool.is_none() {
                         error!("XMRig PTY Parse | pool is not understood, switching to backup.");
@@ -163,22 +175,15 @@ impl Helper {
 
     #[cold]
     #[inline(never)]
-    /// if the user has his visudo configured to not ask a password using sudo, this will return false
+    /// if the user has his visudo configured to not ask a password using sudo
+    /// or if the credentials of sudo are still cached, this will return false
     pub fn password_needed() -> bool {
-        // Make sure sudo timestamp is reset
-        let reset = std::process::Command::new("sudo")
-            .arg("--reset-timestamp")
+        let cmd = std::process::Command::new("sudo")
+            .args(["-n", "true"])
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .stdin(Stdio::piped())
             .status();
-        match reset {
-            Ok(_) => {}
-            Err(_) => return true,
-        };
-        let cmd = std::process::Command::new("sudo")
-            .args(["-n", "true"])
-            .status();
         if cmd.is_ok_and(|s| s.success()) {
             return false;
         }
@@ -186,6 +191,16 @@ impl Helper {
     }
     #[cold]
     #[inline(never)]
+    /// Must the sudo password be asked to start XMRig?
+    /// Not on Windows, not if XMRig is attached and not if the privileged setup reserved the hugepages.
+    pub fn xmrig_password_needed(state: &Xmrig, path: &Path) -> bool {
+        !cfg!(windows)
+            && !state.attached()
+            && !SudoState::xmrig_privileged(path)
+            && Self::password_needed()
+    }
+    #[cold]
+    #[inline(never)]
     // Just sets some signals for the watchdog thread to pick up on.
     pub fn stop_xmrig(helper: &Arc<Mutex<Self>>) {
         info!("XMRig | Attempting to stop...");
@@ -208,6 +223,7 @@ impl Helper {
         state_proxy: &XmrigProxy,
         path: &Path,
         sudo: Arc<Mutex<SudoState>>,
+        socks5: &Socks5,
     ) {
         info!("XMRig | Attempting to restart...");
         helper.lock().unwrap().xmrig.lock().unwrap().signal = ProcessSignal::Restart;
@@ -215,15 +231,17 @@ impl Helper {
 
         let path = path.to_path_buf();
         // This thread lives to wait, start xmrig then die.
-        thread::spawn(enc!((helper, state, state_p2pool, state_proxy)move || {
-            while helper.lock().unwrap().xmrig.lock().unwrap().state != ProcessState::Waiting {
-                warn!("XMRig | Want to restart but process is still alive, waiting...");
-                sleep!(1000);
-            }
-            // Ok, process is not alive, start the new one!
-            info!("XMRig | Old process seems dead, starting new one!");
-            Self::start_xmrig(&helper, &state, &state_p2pool, &state_proxy, &path, sudo);
-        }));
+        thread::spawn(
+            enc!((helper, state, state_p2pool, state_proxy, socks5)move || {
+                while helper.lock().unwrap().xmrig.lock().unwrap().state != ProcessState::Waiting {
+                    warn!("XMRig | Want to restart but process is still alive, waiting...");
+                    sleep!(1000);
+                }
+                // Ok, process is not alive, start the new one!
+                info!("XMRig | Old process seems dead, starting new one!");
+                Self::start_xmrig(&helper, &state, &state_p2pool, &state_proxy, &path, sudo, &socks5);
+            }),
+        );
         info!("XMRig | Restart ... OK");
     }
 
@@ -236,6 +254,7 @@ impl Helper {
         proxy_state: &XmrigProxy,
         path: &Path,
         sudo: Arc<Mutex<SudoState>>,
+        socks5: &Socks5,
     ) {
         // get the stratum port of p2pool
         //
@@ -247,6 +266,16 @@ impl Helper {
             &p2pool_img.lock().unwrap(),
         );
         helper.lock().unwrap().xmrig.lock().unwrap().state = ProcessState::Middle;
+        if state.attached() {
+            Self::attach_xmrig(
+                helper,
+                state,
+                p2pool_state,
+                proxy_state,
+                p2pool_stratum_port,
+            );
+            return;
+        }
         let api_ip_port = Self::mutate_img_xmrig(helper, state, p2pool_stratum_port);
         let mode = if state.simple {
             StartOptionsMode::Simple
@@ -255,7 +284,39 @@ impl Helper {
         } else {
             StartOptionsMode::Advanced
         };
-        let args = Self::build_xmrig_args(state, mode, p2pool_stratum_port);
+        // the proxy is kept in the image so that XvB can use it when switching to its pools.
+        let socks5 = if matches!(mode, StartOptionsMode::Custom) {
+            None
+        } else {
+            socks5.xmrig()
+        };
+        helper.lock().unwrap().img_xmrig.lock().unwrap().socks5 = socks5.clone();
+        let custom = matches!(mode, StartOptionsMode::Custom);
+        let threads = if matches!(mode, StartOptionsMode::Advanced) {
+            state.threads()
+        } else {
+            state.current_threads
+        };
+        let mut config = matches!(mode, StartOptionsMode::Advanced) && state.config_file;
+        // the 1GB pages reserved by the privileged setup are only used if XMRig is told to.
+        let pages_1gb = MemoryState::detect().pages_1gb > 0;
+        let mut args = Self::build_xmrig_args(state, mode, p2pool_stratum_port, socks5.as_deref());
+        if config {
+            match get_gupax_data_path()
+                .map(|path| get_xmrig_config_path(&path))
+                .and_then(|path| {
+                    write_xmrig_config(&path, state, socks5.as_deref(), pages_1gb).map(|_| path)
+                }) {
+                Ok(path) => args = vec![format!("--config={}", path.display())],
+                Err(e) => {
+                    warn!("XMRig | Could not write the config file, using the flags instead: {e}");
+                    config = false;
+                }
+            }
+        }
+        if !custom && !config && pages_1gb {
+            args.push("--randomx-1gb-pages".to_string());
+        }
         // Print arguments & user settings to console
         crate::disk::print_dash(&format!("XMRig | Launch arguments: {:#?}", args));
         info!("XMRig | Using path: [{}]", path.display());
@@ -274,6 +335,9 @@ impl Helper {
         let proxy_state = proxy_state.clone();
         let proxy_img = Arc::clone(&helper.lock().unwrap().img_proxy);
         let pub_api_xvb = Arc::clone(&helper.lock().unwrap().pub_api_xvb);
+        let pub_sys = Arc::clone(&helper.lock().unwrap().pub_sys);
+        let xvb_endpoints = Arc::clone(&helper.lock().unwrap().xvb_endpoints);
+        let throttle = Throttle::new(state, threads);
         thread::spawn(move || {
             Self::spawn_xmrig_watchdog(
                 process,
@@ -292,25 +356,179 @@ impl Helper {
                 &p2pool_img,
                 &proxy_state,
                 &proxy_img,
+                &pub_sys,
+                &xvb_endpoints,
+                throttle,
+            );
+        });
+    }
+    // Monitor a XMRig started outside of Gupaxx with its HTTP API only.
+    fn attach_xmrig(
+        helper: &Arc<Mutex<Self>>,
+        state: &crate::disk::state::Xmrig,
+        p2pool_state: &P2pool,
+        proxy_state: &XmrigProxy,
+        p2pool_stratum_port: u16,
+    ) {
+        let api_ip_port = Self::mutate_img_xmrig(helper, state, p2pool_stratum_port);
+        info!("XMRig | Attaching to the API at: [{}]", api_ip_port);
+        let process = Arc::clone(&helper.lock().unwrap().xmrig);
+        let gui_api = Arc::clone(&helper.lock().unwrap().gui_api_xmrig);
+        let pub_api = Arc::clone(&helper.lock().unwrap().pub_api_xmrig);
+        let process_xp = Arc::clone(&helper.lock().unwrap().xmrig_proxy);
+        let process_p2pool = Arc::clone(&helper.lock().unwrap().p2pool);
+        let p2pool_img = Arc::clone(&helper.lock().unwrap().img_p2pool);
+        let proxy_img = Arc::clone(&helper.lock().unwrap().img_proxy);
+        let xvb_endpoints = Arc::clone(&helper.lock().unwrap().xvb_endpoints);
+        let token = state.token.clone();
+        let p2pool_state = p2pool_state.clone();
+        let proxy_state = proxy_state.clone();
+        thread::spawn(move || {
+            Self::spawn_xmrig_attach_watchdog(
+                &process,
+                &gui_api,
+                &pub_api,
+                &api_ip_port,
+                &token,
+                (&process_p2pool, &p2pool_img, &p2pool_state),
+                (&process_xp, &proxy_img, &proxy_state),
+                &xvb_endpoints,
             );
         });
     }
+    #[tokio::main]
+    #[allow(clippy::await_holding_lock)]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn spawn_xmrig_attach_watchdog(
+        process: &Arc<Mutex<Process>>,
+        gui_api: &Arc<Mutex<PubXmrigApi>>,
+        pub_api: &Arc<Mutex<PubXmrigApi>>,
+        api_ip_port: &str,
+        token: &str,
+        p2pool: (&Arc<Mutex<Process>>, &Arc<Mutex<ImgP2pool>>, &P2pool),
+        proxy: (&Arc<Mutex<Process>>, &Arc<Mutex<ImgProxy>>, &XmrigProxy),
+        xvb_endpoints: &Arc<Mutex<XvbEndpoints>>,
+    ) {
+        let client = client(None);
+        let api_uri_summary = format!("http://{api_ip_port}/{XMRIG_API_SUMMARY_ENDPOINT}");
+        let api_uri_backends = format!("http://{api_ip_port}/{XMRIG_API_BACKENDS_ENDPOINT}");
+        let api_uri_config = format!("http://{api_ip_port}/{XMRIG_API_CONFIG_ENDPOINT}");
+        let mut lock = process.lock().unwrap();
+        lock.state = ProcessState::NotMining;
+        lock.signal = ProcessSignal::None;
+        lock.start = Instant::now();
+        let start = lock.start;
+        drop(lock);
+        // Reset stats before loop
+        *pub_api.lock().unwrap() = PubXmrigApi::new();
+        *gui_api.lock().unwrap() = PubXmrigApi::new();
+        output_console(
+            &mut gui_api.lock().unwrap().output,
+            &format!(
+                "Attached to the XMRig running outside of Gupaxx, monitoring it through its HTTP API at http://{api_ip_port}"
+            ),
+            ProcessName::Xmrig,
+        );
+        let mut reachable = true;
+        info!("XMRig | Entering attached watchdog mode... woof!");
+        loop {
+            let now = Instant::now();
+            debug!("XMRig Watchdog | ----------- Start of loop -----------");
+            if signal_end_attached(
+                &mut process.lock().unwrap(),
+                &start,
+                &mut gui_api.lock().unwrap().output,
+            ) {
+                break;
+            }
+            pub_api.lock().unwrap().uptime = HumanTime::into_human(start.elapsed());
+            match PrivXmrigApi::request_xmrig_api(&client, &api_uri_summary, token).await {
+                Ok(priv_api) => {
+                    if !reachable {
+                        reachable = true;
+                        output_console(
+                            &mut gui_api.lock().unwrap().output,
+                            "The HTTP API of XMRig is reachable again",
+                            ProcessName::Xmrig,
+                        );
+                    }
+                    // without console, the connection uptime tells if XMRig is connected to a pool.
+                    let pool = if priv_api.connection.uptime > 0 {
+                        priv_api.connection.pool.as_deref().and_then(|address| {
+                            pool_from_address(
+                                address,
+                                proxy
+                                    .2
+                                    .current_ports(
+                                        proxy.0.lock().unwrap().is_alive(),
+                                        &proxy.1.lock().unwrap(),
+                                    )
+                                    .0,
+                                p2pool.2.current_port(
+                                    p2pool.0.lock().unwrap().is_alive(),
+                                    &p2pool.1.lock().unwrap(),
+                                ),
+                                &xvb_endpoints.lock().unwrap(),
+                            )
+                        })
+                    } else {
+                        None
+                    };
+                    process.lock().unwrap().state = if pool.is_some() {
+                        ProcessState::Alive
+                    } else {
+                        ProcessState::NotMining
+                    };
+                    pub_api.lock().unwrap().pool = pool;
+                    PubXmrigApi::update_from_priv(pub_api, priv_api);
+                    PubXmrigApi::update_backends(
+                        pub_api,
+                        &client,
+                        &api_uri_backends,
+                        &api_uri_config,
+                        token,
+                    )
+                    .await;
+                }
+                Err(err) => {
+                    process.lock().unwrap().state = ProcessState::NotMining;
+                    pub_api.lock().unwrap().pool = None;
+                    if reachable {
+                        reachable = false;
+                        warn!(
+                            "XMRig Watchdog | Could not send HTTP API request to: {}\n{}",
+                            api_uri_summary, err
+                        );
+                        output_console(
+                            &mut gui_api.lock().unwrap().output,
+                            &format!("The HTTP API of XMRig is not reachable: {err}"),
+                            ProcessName::Xmrig,
+                        );
+                    }
+                }
+            }
+            sleep_end_loop(now, ProcessName::Xmrig).await;
+        }
+        info!("XMRig Watchdog | Attached watchdog thread exiting... Goodbye!");
+    }
     pub fn mutate_img_xmrig(
         helper: &Arc<Mutex<Self>>,
         state: &crate::disk::state::Xmrig,
         stratum_port: u16,
     ) -> String {
-        let mut api_ip = String::with_capacity(15);
+        // the default of XMRig if [--http-host] is not given
+        let mut api_ip = "127.0.0.1".to_string();
         let mut api_port = String::with_capacity(5);
         if state.simple {
-            api_ip = "127.0.0.1".to_string();
             api_port = "18088".to_string();
 
             *helper.lock().unwrap().img_xmrig.lock().unwrap() = ImgXmrig {
                 threads: state.current_threads.to_string(),
                 url: format!("127.0.0.1:{stratum_port} (Local P2Pool)"),
+                api_ip: api_ip.clone(),
                 api_port: XMRIG_API_PORT_DEFAULT,
                 token: state.token.clone(),
+                socks5: None,
             };
         } else if !state.arguments.is_empty() {
             // This parses the input and attempts to fill out
@@ -340,6 +558,7 @@ impl Helper {
                 }
                 last = arg;
             }
+            xmrig_image.api_ip.clone_from(&api_ip);
         } else {
             let ip = if state.ip == "localhost" || state.ip.is_empty() {
                 "127.0.0.1"
@@ -359,9 +578,11 @@ impl Helper {
             let url = format!("{}:{}", ip, state.port); // Combine IP:Port into one string
             *helper.lock().unwrap().img_xmrig.lock().unwrap() = ImgXmrig {
                 url: url.clone(),
-                threads: state.current_threads.to_string(),
+                threads: state.threads().to_string(),
+                api_ip: api_ip.clone(),
                 api_port: state.api_port.parse().unwrap_or(XMRIG_API_PORT_DEFAULT),
                 token: state.token.clone(),
+                socks5: None,
             };
         }
 
@@ -377,6 +598,7 @@ impl Helper {
         // Allows to provide a different mode without mutating the state
         mode: StartOptionsMode,
         p2pool_stratum_port: u16,
+        socks5: Option<&str>,
     ) -> Vec<String> {
         let mut args = Vec::with_capacity(500);
         // some args needs to be added to both simple/advanced
@@ -386,7 +608,12 @@ impl Helper {
                 args.push(format!("--http-access-token={}", state.token)); // HTTP API Port
                 args.push("--http-no-restricted".to_string());
                 args.push("--threads".to_string());
-                args.push(state.current_threads.to_string()); // Threads
+                // the thread layout is only used with advanced settings
+                if matches!(mode, StartOptionsMode::Advanced) {
+                    args.push(state.threads().to_string());
+                } else {
+                    args.push(state.current_threads.to_string());
+                } // Threads
                 if state.pause != 0 {
                     args.push("--pause-on-active".to_string());
                     args.push(state.pause.to_string());
@@ -445,6 +672,41 @@ impl Helper {
                 if state.keepalive {
                     args.push("--keepalive".to_string());
                 } // Keepalive
+                // a local pool can not be reached through the proxy.
+                // Must be given before the failover pools, XMRig applies it to the last [--url].
+                if let Some(socks5) = socks5 {
+                    if ip != "127.0.0.1" {
+                        args.push("--proxy".to_string());
+                        args.push(socks5.to_string());
+                    }
+                }
+                args.extend(state.layout_args()); // Priority, RandomX mode, Affinity
+                // Failover pools, the options after a [--url] only apply to its pool
+                for pool in &state.failover {
+                    let url = pool.url();
+                    args.push("--url".to_string());
+                    args.push(url.clone());
+                    args.push("--user".to_string());
+                    if pool.user.is_empty() {
+                        args.push(state.address.clone());
+                    } else {
+                        args.push(pool.user.clone());
+                    }
+                    args.push("--rig-id".to_string());
+                    args.push(pool.rig.clone());
+                    if pool.tls {
+                        args.push("--tls".to_string());
+                    }
+                    if pool.keepalive {
+                        args.push("--keepalive".to_string());
+                    }
+                    if let Some(socks5) = socks5 {
+                        if !url.starts_with("127.0.0.1:") {
+                            args.push("--proxy".to_string());
+                            args.push(socks5.to_string());
+                        }
+                    }
+                }
             }
             StartOptionsMode::Custom => {
                 // This parses the input and attempts to fill out
@@ -461,9 +723,18 @@ impl Helper {
     }
 
     // We actually spawn [sudo] on Unix, with XMRig being the argument.
+    // If the privileged setup reserved the hugepages, XMRig is spawned directly.
     #[cfg(target_family = "unix")]
-    fn create_xmrig_cmd_unix(args: Vec<String>, path: PathBuf) -> portable_pty::CommandBuilder {
-        let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new("sudo");
+    fn create_xmrig_cmd_unix(
+        args: Vec<String>,
+        path: PathBuf,
+        use_sudo: bool,
+    ) -> portable_pty::CommandBuilder {
+        let mut cmd = if use_sudo {
+            portable_pty::cmdbuilder::CommandBuilder::new("sudo")
+        } else {
+            portable_pty::cmdbuilder::CommandBuilder::new(path.clone())
+        };
         cmd.args(args);
         cmd.cwd(path.as_path().parent().unwrap());
         cmd
@@ -502,12 +773,17 @@ impl Helper {
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         proxy_state: &XmrigProxy,
         proxy_img: &Arc<Mutex<ImgProxy>>,
+        pub_sys: &Arc<Mutex<Sys>>,
+        xvb_endpoints: &Arc<Mutex<XvbEndpoints>>,
+        mut throttle: Option<Throttle>,
     ) {
         // The actual binary we're executing is [sudo], technically
         // the XMRig path is just an argument to sudo, so add it.
         // Before that though, add the ["--prompt"] flag and set it
         // to emptiness so that it doesn't show up in the output.
-        if cfg!(unix) {
+        // sudo is not used at all if the hugepages were reserved by the privileged setup.
+        let use_sudo = cfg!(unix) && !SudoState::xmrig_privileged(&path);
+        if use_sudo {
             args.splice(..0, vec![path.display().to_string()]);
             // do not use prompt when sudo is not needed
             // success is still to false if sudo has not been used to start xmrig
@@ -533,8 +809,8 @@ impl Helper {
         let output_parse = Arc::clone(&process.lock().unwrap().output_parse);
         let output_pub = Arc::clone(&process.lock().unwrap().output_pub);
         spawn(
-            enclose!((pub_api_xvb, process_xp, p2pool_state, p2pool_img, process_p2pool, proxy_img, proxy_state) async move {
-                Self::read_pty_xmrig(output_parse, output_pub, reader, process_xvb, process_xp, process_p2pool, &pub_api_xvb, &p2pool_state, &p2pool_img, &proxy_img, &proxy_state).await;
+            enclose!((pub_api_xvb, process_xp, p2pool_state, p2pool_img, process_p2pool, proxy_img, proxy_state, xvb_endpoints) async move {
+                Self::read_pty_xmrig(output_parse, output_pub, reader, process_xvb, process_xp, process_p2pool, &pub_api_xvb, &p2pool_state, &p2pool_img, &proxy_img, &proxy_state, &xvb_endpoints).await;
             }),
         );
         // 1b. Create command
@@ -542,7 +818,7 @@ impl Helper {
         #[cfg(target_os = "windows")]
         let cmd = Self::create_xmrig_cmd_windows(args, path);
         #[cfg(target_family = "unix")]
-        let cmd = Self::create_xmrig_cmd_unix(args, path);
+        let cmd = Self::create_xmrig_cmd_unix(args, path, use_sudo);
         // 1c. Create child
         debug!("XMRig | Creating child...");
         let child_pty = arc_mut!(pair.slave.spawn_command(cmd).unwrap());
@@ -550,7 +826,7 @@ impl Helper {
 
         let mut stdin = pair.master.take_writer().unwrap();
         // 2. Input [sudo] pass, wipe, then drop.
-        if cfg!(unix) && sudo.lock().unwrap().success {
+        if use_sudo && sudo.lock().unwrap().success {
             debug!("XMRig | Inputting [sudo] and wiping...");
             if let Err(e) = writeln!(stdin, "{}", sudo.lock().unwrap().pass) {
                 error!("XMRig | Sudo STDIN error: {}", e);
@@ -575,7 +851,7 @@ impl Helper {
         let output_parse = Arc::clone(&process.lock().unwrap().output_parse);
         let output_pub = Arc::clone(&process.lock().unwrap().output_pub);
 
-        let client = client();
+        let client = client(None);
         let start = process.lock().unwrap().start;
         let api_uri_config = {
             if !api_ip_port.ends_with('/') {
@@ -589,6 +865,8 @@ impl Helper {
             }
             "http://".to_owned() + &api_ip_port + XMRIG_API_SUMMARY_ENDPOINT
         };
+        let api_uri_rpc = "http://".to_owned() + &api_ip_port + XMRIG_API_JSON_RPC_ENDPOINT;
+        let api_uri_backends = "http://".to_owned() + &api_ip_port + XMRIG_API_BACKENDS_ENDPOINT;
         info!("XMRig | Final API URI: {}", api_uri_config);
 
         // Reset stats before loop
@@ -667,6 +945,7 @@ impl Helper {
                     p2pool_img,
                     proxy_state,
                     p2pool_state,
+                    &xvb_endpoints.lock().unwrap(),
                 );
             }
             // Send an HTTP API request
@@ -683,6 +962,37 @@ impl Helper {
                     );
                 }
             }
+            PubXmrigApi::update_backends(
+                &pub_api,
+                &client,
+                &api_uri_backends,
+                &api_uri_config,
+                token,
+            )
+            .await;
+            // Throttle the threads by the temperature of the CPU
+            if let Some(throttle) = throttle.as_mut() {
+                let temp = pub_sys.lock().unwrap().cpu_temp;
+                if let Some((temp, level)) =
+                    temp.and_then(|t| throttle.next_level(t, Instant::now()).map(|l| (t, l)))
+                {
+                    let msg = match throttle
+                        .apply(&client, &api_uri_config, &api_uri_rpc, token, level)
+                        .await
+                    {
+                        Ok(_) => throttle.message(temp),
+                        Err(err) => {
+                            warn!("XMRig Watchdog | Could not throttle XMRig: {}", err);
+                            format!("Failure to throttle XMRig with HTTP API.\nError: {}", err)
+                        }
+                    };
+                    output_console(
+                        &mut gui_api.lock().unwrap().output,
+                        &msg,
+                        ProcessName::Xmrig,
+                    );
+                }
+            }
             // if mining on proxy and proxy is not alive, switch back to p2pool node
             debug!("update from priv ok");
             // unlock first process_xp and then pub_api
@@ -712,6 +1022,7 @@ impl Helper {
                     &pool,
                     "",
                     GUPAX_VERSION_UNDERSCORE,
+                    None,
                 )
                 .await
                 {
@@ -813,8 +1124,10 @@ impl Helper {
 pub struct ImgXmrig {
     pub threads: String,
     pub url: String,
+    pub api_ip: String, // IP of the API, the one of the attached XMRig
     pub api_port: u16,
     pub token: String,
+    pub socks5: Option<String>, // SOCKS5 proxy to use for remote pools
 }
 
 impl Default for ImgXmrig {
@@ -828,8 +1141,10 @@ impl ImgXmrig {
         Self {
             threads: "???".to_string(),
             url: "???".to_string(),
+            api_ip: "127.0.0.1".to_string(),
             api_port: XMRIG_API_PORT_DEFAULT,
             token: String::new(),
+            socks5: None,
         }
     }
 }
@@ -849,7 +1164,14 @@ pub struct PubXmrigApi {
     pub hashrate_raw: f32,
     pub hashrate_raw_1m: f32,
     pub hashrate_raw_15m: f32,
+    // total difficulty of the accepted shares since the start of XMRig
+    pub hashes_total: Option<u64>,
     pub pool: Option<Pool>,
+    pub hugepages: HugepagesReport,
+    // per-thread stats of [/2/backends]
+    pub backends: Vec<XmrigBackend>,
+    // RandomX mode of the config (auto, fast, light), requested once
+    pub randomx_mode: Option<String>,
 }
 
 impl Default for PubXmrigApi {
@@ -872,7 +1194,11 @@ impl PubXmrigApi {
             hashrate_raw: 0.0,
             hashrate_raw_1m: 0.0,
             hashrate_raw_15m: 0.0,
+            hashes_total: None,
             pool: None,
+            hugepages: HugepagesReport::default(),
+            backends: Vec::new(),
+            randomx_mode: None,
         }
     }
 
@@ -904,6 +1230,7 @@ impl PubXmrigApi {
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         proxy_state: &XmrigProxy,
         p2pool_state: &P2pool,
+        endpoints: &XvbEndpoints,
     ) {
         // 1. Take the process's current output buffer and combine it with Pub (if not empty)
         let mut output_pub = output_pub.lock().unwrap();
@@ -918,7 +1245,9 @@ impl PubXmrigApi {
         drop(output_pub);
 
         let mut output_parse = output_parse.lock().unwrap();
-        // 2. Check for "new job"/"no active...".
+        // 2. Check what XMRig says about huge pages at startup.
+        public.hugepages.update_from_output(&output_parse);
+        // 3. Check for "new job"/"no active...".
         if XMRIG_REGEX.new_job.is_match(&output_parse) {
             process.state = ProcessState::Alive;
             // get the pool we mine on to put it on stats
@@ -928,6 +1257,7 @@ impl PubXmrigApi {
                     .current_ports(process_proxy.is_alive(), &proxy_img.lock().unwrap())
                     .0,
                 p2pool_state.current_port(process_p2pool.is_alive(), &p2pool_img.lock().unwrap()),
+                endpoints,
             ) {
                 public.pool = Some(name_pool);
             }
@@ -936,13 +1266,13 @@ impl PubXmrigApi {
             public.pool = None;
         }
 
-        // 3. Throw away [output_parse]
+        // 4. Throw away [output_parse]
         output_parse.clear();
         drop(output_parse);
     }
 
     // Formats raw private data into ready-to-print human readable version.
-    fn update_from_priv(public: &Arc<Mutex<Self>>, private: PrivXmrigApi) {
+    pub(crate) fn update_from_priv(public: &Arc<Mutex<Self>>, private: PrivXmrigApi) {
         let mut public = public.lock().unwrap();
         let hashrate_raw = match private.hashrate.total.first() {
             Some(Some(h)) => *h,
@@ -972,9 +1302,60 @@ impl PubXmrigApi {
             hashrate_raw,
             hashrate_raw_1m,
             hashrate_raw_15m,
+            hashes_total: private.results.map(|r| r.hashes_total),
             ..std::mem::take(&mut *public)
         }
     }
+    // Request the per-thread stats and, if not known yet, the RandomX mode.
+    // Versions of XMRig without the [/2] API only miss this view, so failures are not shown in the console.
+    pub(crate) async fn update_backends(
+        public: &Arc<Mutex<Self>>,
+        client: &Client,
+        api_uri_backends: &str,
+        api_uri_config: &str,
+        token: &str,
+    ) {
+        match PrivXmrigBackend::request_backends(client, api_uri_backends, token).await {
+            Ok(backends) => {
+                public.lock().unwrap().backends =
+                    backends.into_iter().map(XmrigBackend::from).collect();
+            }
+            Err(err) => {
+                debug!(
+                    "XMRig Watchdog | Could not send HTTP API request to: {}\n{}",
+                    api_uri_backends, err
+                );
+                public.lock().unwrap().backends.clear();
+                return;
+            }
+        }
+        if public.lock().unwrap().randomx_mode.is_some() {
+            return;
+        }
+        let config = async {
+            client
+                .get(api_uri_config)
+                .header(AUTHORIZATION, ["Bearer ", token].concat())
+                .timeout(std::time::Duration::from_millis(5000))
+                .send()
+                .await?
+                .json::<serde_json::Value>()
+                .await
+                .map_err(anyhow::Error::from)
+        };
+        match config.await {
+            Ok(config) => {
+                public.lock().unwrap().randomx_mode = config
+                    .pointer("/randomx/mode")
+                    .and_then(|m| m.as_str())
+                    .map(str::to_string);
+            }
+            Err(err) => debug!(
+                "XMRig Watchdog | Could not send HTTP API request to: {}\n{}",
+                api_uri_config, err
+            ),
+        }
+    }
 }
 
 //---------------------------------------------------------------------------------------------------- Private XMRig API
@@ -986,6 +1367,9 @@ impl PubXmrigApi {
 pub struct PrivXmrigApi {
     worker_id: String,
     resources: Resources,
+    // absent from older XMRig
+    #[serde(default, skip_serializing)]
+    results: Option<Results>,
     connection: Connection,
     hashrate: Hashrate,
 }
@@ -993,7 +1377,7 @@ pub struct PrivXmrigApi {
 impl PrivXmrigApi {
     #[inline]
     // Send an HTTP request to XMRig's API, serialize it into [Self] and return it
-    async fn request_xmrig_api(
+    pub(crate) async fn request_xmrig_api(
         client: &Client,
         api_uri: &str,
         token: &str,
@@ -1015,13 +1399,221 @@ struct Resources {
     load_average: [Option<f32>; 3],
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+struct Results {
+    hashes_total: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct Connection {
+    // used by attach mode, where there is no console to know the pool and if it is connected.
+    // Older XMRig do not give them.
+    #[serde(default, skip_serializing)]
+    pool: Option<String>,
+    #[serde(default, skip_serializing)]
+    uptime: u64,
     diff: u128,
     accepted: u128,
     rejected: u128,
 }
 
+//---------------------------------------------------------------------------------------------------- Private XMRig backends API
+// One element of [wget -qO- localhost:18085/2/backends], for the cpu, opencl and cuda backends.
+// Disabled backends only have their type, the other fields default.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PrivXmrigBackend {
+    #[serde(rename = "type")]
+    kind: String,
+    enabled: bool,
+    algo: Option<String>,
+    profile: Option<String>,
+    hugepages: Option<BackendHugepages>,
+    memory: u64, // bytes used by the scratchpads of the threads
+    hashrate: [Option<f32>; 3],
+    threads: Vec<PrivXmrigThread>,
+}
+
+// [true] if every page is allocated, [false] if none, [allocated, total] otherwise.
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(untagged)]
+enum BackendHugepages {
+    All(bool),
+    Pages([u64; 2]),
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+struct PrivXmrigThread {
+    affinity: Option<i64>, // -1 if the thread is not pinned, absent for GPU
+    hashrate: [Option<f32>; 3],
+}
+
+impl PrivXmrigBackend {
+    pub(crate) async fn request_backends(
+        client: &Client,
+        api_uri: &str,
+        token: &str,
+    ) -> std::result::Result<Vec<Self>, anyhow::Error> {
+        let request = client
+            .get(api_uri)
+            .header(AUTHORIZATION, ["Bearer ", token].concat());
+        Ok(request
+            .timeout(std::time::Duration::from_millis(5000))
+            .send()
+            .await?
+            .json()
+            .await?)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Public XMRig backends
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmrigBackend {
+    pub kind: String,
+    pub enabled: bool,
+    pub algo: String,
+    pub profile: String,
+    pub hugepages: String,
+    pub memory_mb: u64,
+    pub hashrate: [Option<f32>; 3],
+    pub threads: Vec<XmrigThread>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmrigThread {
+    pub affinity: Option<u16>, // None if the thread is not pinned
+    pub hashrate: [Option<f32>; 3],
+    pub status: ThreadStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadStatus {
+    Ok,
+    // under 75% of the median of the other threads
+    Slow,
+    // no hashrate while the other threads hash
+    Stalled,
+    // pinned to the same CPU as another thread
+    SharedCpu,
+    // pinned to the SMT sibling of another thread while a physical core is left idle
+    SharedCore,
+}
+
+impl ThreadStatus {
+    pub fn is_ok(&self) -> bool {
+        *self == Self::Ok
+    }
+}
+
+impl std::fmt::Display for ThreadStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ok => write!(f, "OK"),
+            Self::Slow => write!(f, "Slow"),
+            Self::Stalled => write!(f, "Stalled"),
+            Self::SharedCpu => write!(f, "Same CPU as another thread"),
+            Self::SharedCore => write!(f, "Same core as another thread"),
+        }
+    }
+}
+
+impl From<PrivXmrigBackend> for XmrigBackend {
+    fn from(private: PrivXmrigBackend) -> Self {
+        let hugepages = match private.hugepages {
+            Some(BackendHugepages::All(true)) => "100%".to_string(),
+            Some(BackendHugepages::All(false)) => "0%".to_string(),
+            Some(BackendHugepages::Pages([allocated, total])) if total > 0 => {
+                format!("{}% ({allocated}/{total})", allocated * 100 / total)
+            }
+            _ => UNKNOWN_DATA.to_string(),
+        };
+        let threads = private
+            .threads
+            .iter()
+            .map(|t| (t.affinity.and_then(|a| u16::try_from(a).ok()), t.hashrate))
+            .collect::<Vec<_>>();
+        let statuses = thread_statuses(&threads, &CPU_TOPOLOGY);
+        Self {
+            kind: private.kind,
+            enabled: private.enabled,
+            algo: private.algo.unwrap_or_default(),
+            profile: private.profile.unwrap_or_default(),
+            hugepages,
+            memory_mb: private.memory / (1024 * 1024),
+            hashrate: private.hashrate,
+            threads: threads
+                .into_iter()
+                .zip(statuses)
+                .map(|((affinity, hashrate), status)| XmrigThread {
+                    affinity,
+                    hashrate,
+                    status,
+                })
+                .collect(),
+        }
+    }
+}
+
+// Find the threads slowing down the backend, from their affinity and 10 seconds hashrate.
+// Nothing is reported while XMRig is still measuring the hashrate of every thread.
+pub fn thread_statuses(
+    threads: &[(Option<u16>, [Option<f32>; 3])],
+    topology: &CpuTopology,
+) -> Vec<ThreadStatus> {
+    let hashrates = threads
+        .iter()
+        .map(|(_, h)| h[0].unwrap_or_default())
+        .collect::<Vec<_>>();
+    let measured = hashrates.iter().any(|h| *h > 0.0);
+    let core = |cpu: u16| topology.cpus.iter().find(|c| c.id == cpu).map(|c| c.core);
+    let pinned_cores = threads
+        .iter()
+        .filter_map(|(a, _)| a.and_then(core))
+        .collect::<Vec<_>>();
+    let idle_core = topology
+        .cpus
+        .iter()
+        .any(|c| !pinned_cores.contains(&c.core));
+    threads
+        .iter()
+        .enumerate()
+        .map(|(i, (affinity, _))| {
+            let others = hashrates
+                .iter()
+                .enumerate()
+                .filter(|(j, h)| *j != i && **h > 0.0)
+                .map(|(_, h)| *h)
+                .collect::<Vec<_>>();
+            let shared = |same: &dyn Fn(u16) -> bool| {
+                threads
+                    .iter()
+                    .enumerate()
+                    .any(|(j, (a, _))| j != i && a.is_some_and(same))
+            };
+            match affinity {
+                _ if measured && hashrates[i] <= 0.0 => ThreadStatus::Stalled,
+                Some(cpu) if shared(&|a| a == *cpu) => ThreadStatus::SharedCpu,
+                Some(cpu)
+                    if idle_core && core(*cpu).is_some_and(|c| shared(&|a| core(a) == Some(c))) =>
+                {
+                    ThreadStatus::SharedCore
+                }
+                _ if median(others).is_some_and(|m| hashrates[i] < m * 0.75) => ThreadStatus::Slow,
+                _ => ThreadStatus::Ok,
+            }
+        })
+        .collect()
+}
+
+fn median(mut values: Vec<f32>) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f32::total_cmp);
+    Some(values[values.len() / 2])
+}
+
 //  get the API port that would be used if xmrig was started with the current settings
 // pub fn get_xmrig_api_port(xmrig_state: &Xmrig) -> u16 {
 //     if xmrig_state.simple {
@@ -20,6 +20,7 @@ use log::{debug, error, info, warn};
 use reqwest::header::AUTHORIZATION;
 use reqwest_middleware::ClientWithMiddleware as Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt::Write;
 use std::time::Duration;
 use std::{
@@ -39,15 +40,18 @@ use crate::{
     GUPAX_VERSION_UNDERSCORE,
     helper::{
         Helper, Process, ProcessName, ProcessSignal, ProcessState, check_died, check_user_input,
-        signal_end, sleep_end_loop,
+        signal_end, signal_end_attached, sleep_end_loop,
         xrig::update_xmrig_config,
-        xvb::{PubXvbApi, nodes::Pool},
+        xvb::{PubXvbApi, endpoints::XvbEndpoints, nodes::Pool},
     },
     macros::{arc_mut, sleep},
     miscs::output_console,
     regex::{XMRIG_REGEX, contains_timeout, contains_usepool, detect_pool_xmrig},
 };
-use crate::{PROXY_API_PORT_DEFAULT, PROXY_PORT_DEFAULT, XMRIG_API_SUMMARY_ENDPOINT};
+use crate::{
+    PROXY_API_PORT_DEFAULT, PROXY_PORT_DEFAULT, XMRIG_API_SUMMARY_ENDPOINT,
+    XMRIG_PROXY_API_MINERS_ENDPOINT, XMRIG_PROXY_API_WORKERS_ENDPOINT,
+};
 
 use super::xmrig::{ImgXmrig, PubXmrigApi};
 impl Helper {
@@ -66,6 +70,7 @@ impl Helper {
         p2pool_state: &P2pool,
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         proxy_state: &XmrigProxy,
+        xvb_endpoints: &Arc<Mutex<XvbEndpoints>>,
     ) {
         use std::io::BufRead;
         let mut stdout = std::io::BufReader::new(reader).lines();
@@ -124,6 +129,7 @@ impl Helper {
                             process_p2pool.lock().unwrap().is_alive(),
                             &p2pool_img.lock().unwrap(),
                         ),
+                        &xvb_endpoints.lock().unwrap(),
                     );
                     if node.is_none() {
                         warn!(
@@ -262,8 +268,9 @@ impl Helper {
                             .parse()
                             .unwrap_or(PROXY_PORT_DEFAULT);
                     }
-                    "--http-host" => {
-                        proxy_image.api_port = last.parse().unwrap_or(PROXY_API_PORT_DEFAULT)
+                    "--http-host" => proxy_image.api_ip = state.api_ip(),
+                    "--http-port" => {
+                        proxy_image.api_port = arg.parse().unwrap_or(PROXY_API_PORT_DEFAULT)
                     }
                     l if l.contains("--http-access-token=") => {
                         proxy_image.token = l.split_once("=").unwrap().1.to_string();
@@ -274,6 +281,7 @@ impl Helper {
             }
         } else {
             *helper.lock().unwrap().img_proxy.lock().unwrap() = ImgProxy {
+                api_ip: state.api_ip(),
                 api_port: state.api_port.parse().unwrap_or(PROXY_API_PORT_DEFAULT),
                 port: state.port.parse().unwrap_or(PROXY_PORT_DEFAULT),
                 token: state.token.clone(),
@@ -328,6 +336,10 @@ impl Helper {
         path: &Path,
     ) {
         helper.lock().unwrap().xmrig_proxy.lock().unwrap().state = ProcessState::Middle;
+        if state_proxy.attached() {
+            Self::attach_xp(helper, state_proxy);
+            return;
+        }
 
         let mode = if state_proxy.simple {
             StartOptionsMode::Simple
@@ -363,6 +375,7 @@ impl Helper {
         let pub_api_xvb = Arc::clone(&helper.lock().unwrap().pub_api_xvb);
         let pub_api_xmrig = Arc::clone(&helper.lock().unwrap().pub_api_xmrig);
         let xmrig_img = Arc::clone(&helper.lock().unwrap().img_xmrig);
+        let xvb_endpoints = Arc::clone(&helper.lock().unwrap().xvb_endpoints);
         thread::spawn(move || {
             Self::spawn_xp_watchdog(
                 &process,
@@ -379,9 +392,132 @@ impl Helper {
                 process_p2pool,
                 &state_p2pool,
                 &p2pool_img,
+                &xvb_endpoints,
             );
         });
     }
+    // Monitor a XMRig-Proxy started outside of Gupaxx with its HTTP API only.
+    fn attach_xp(helper: &Arc<Mutex<Self>>, state_proxy: &XmrigProxy) {
+        Helper::mutate_img_proxy(helper, state_proxy);
+        let process = Arc::clone(&helper.lock().unwrap().xmrig_proxy);
+        let gui_api = Arc::clone(&helper.lock().unwrap().gui_api_xp);
+        let pub_api = Arc::clone(&helper.lock().unwrap().pub_api_xp);
+        let state = state_proxy.clone();
+        thread::spawn(move || {
+            Self::spawn_xp_attach_watchdog(&process, &gui_api, &pub_api, &state);
+        });
+    }
+    #[tokio::main]
+    #[allow(clippy::await_holding_lock)]
+    pub(crate) async fn spawn_xp_attach_watchdog(
+        process: &Arc<Mutex<Process>>,
+        gui_api: &Arc<Mutex<PubXmrigProxyApi>>,
+        pub_api: &Arc<Mutex<PubXmrigProxyApi>>,
+        state: &XmrigProxy,
+    ) {
+        let api_summary_xp = format!(
+            "http://{}:{}/{}",
+            state.api_ip(),
+            state.api_port(),
+            XMRIG_API_SUMMARY_ENDPOINT
+        );
+        let api_workers_xp = format!(
+            "http://{}:{}/{}",
+            state.api_ip(),
+            state.api_port(),
+            XMRIG_PROXY_API_WORKERS_ENDPOINT
+        );
+        let api_miners_xp = format!(
+            "http://{}:{}/{}",
+            state.api_ip(),
+            state.api_port(),
+            XMRIG_PROXY_API_MINERS_ENDPOINT
+        );
+        let mut alerts = WorkerAlerts::default();
+        let client = client(None);
+        let mut lock = process.lock().unwrap();
+        lock.state = ProcessState::NotMining;
+        lock.signal = ProcessSignal::None;
+        lock.start = Instant::now();
+        let start = lock.start;
+        drop(lock);
+        // reset stats
+        *pub_api.lock().unwrap() = PubXmrigProxyApi::new();
+        *gui_api.lock().unwrap() = PubXmrigProxyApi::new();
+        output_console(
+            &mut gui_api.lock().unwrap().output,
+            &format!(
+                "Attached to the XMRig-Proxy running outside of Gupaxx, monitoring it through its HTTP API at {api_summary_xp}"
+            ),
+            ProcessName::XmrigProxy,
+        );
+        let mut reachable = true;
+        info!("XMRig-Proxy | Entering attached watchdog mode... woof!");
+        loop {
+            let now = Instant::now();
+            debug!("XMRig-Proxy Watchdog | ----------- Start of loop -----------");
+            if signal_end_attached(
+                &mut process.lock().unwrap(),
+                &start,
+                &mut gui_api.lock().unwrap().output,
+            ) {
+                break;
+            }
+            pub_api.lock().unwrap().uptime = HumanTime::into_human(start.elapsed());
+            match PrivXmrigProxyApi::request_xp_api(&client, &api_summary_xp, &state.token).await {
+                Ok(priv_api) => {
+                    if !reachable {
+                        reachable = true;
+                        output_console(
+                            &mut gui_api.lock().unwrap().output,
+                            "The HTTP API of XMRig-Proxy is reachable again",
+                            ProcessName::XmrigProxy,
+                        );
+                    }
+                    // without console, the active upstreams tell if the proxy is connected to a pool.
+                    process.lock().unwrap().state = if priv_api.upstreams.active > 0 {
+                        ProcessState::Alive
+                    } else {
+                        ProcessState::NotMining
+                    };
+                    PubXmrigProxyApi::update_from_priv(pub_api, priv_api);
+                    for msg in PubXmrigProxyApi::update_workers(
+                        pub_api,
+                        &client,
+                        &api_workers_xp,
+                        &api_miners_xp,
+                        &state.token,
+                        &mut alerts,
+                    )
+                    .await
+                    {
+                        output_console(
+                            &mut gui_api.lock().unwrap().output,
+                            &msg,
+                            ProcessName::XmrigProxy,
+                        );
+                    }
+                }
+                Err(err) => {
+                    process.lock().unwrap().state = ProcessState::NotMining;
+                    if reachable {
+                        reachable = false;
+                        warn!(
+                            "XMRig-Proxy Watchdog | Could not send HTTP API request to: {}\n{}",
+                            api_summary_xp, err
+                        );
+                        output_console(
+                            &mut gui_api.lock().unwrap().output,
+                            &format!("The HTTP API of XMRig-Proxy is not reachable: {err}"),
+                            ProcessName::XmrigProxy,
+                        );
+                    }
+                }
+            }
+            sleep_end_loop(now, ProcessName::XmrigProxy).await;
+        }
+        info!("XMRig-Proxy Watchdog | Attached watchdog thread exiting... Goodbye!");
+    }
     #[tokio::main]
     #[allow(clippy::await_holding_lock)]
     #[allow(clippy::too_many_arguments)]
@@ -400,6 +536,7 @@ impl Helper {
         process_p2pool: Arc<Mutex<Process>>,
         p2pool_state: &P2pool,
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
+        xvb_endpoints: &Arc<Mutex<XvbEndpoints>>,
     ) {
         process.lock().unwrap().start = Instant::now();
         // spawn pty
@@ -419,8 +556,8 @@ impl Helper {
         let output_parse = Arc::clone(&process.lock().unwrap().output_parse);
         let output_pub = Arc::clone(&process.lock().unwrap().output_pub);
         spawn(
-            enc!((pub_api_xvb, output_parse, output_pub, process_p2pool, p2pool_state, p2pool_img,  state) async move {
-                Self::read_pty_xp(output_parse, output_pub, reader, process_xvb, &pub_api_xvb, process_p2pool, &p2pool_state, &p2pool_img, &state).await;
+            enc!((pub_api_xvb, output_parse, output_pub, process_p2pool, p2pool_state, p2pool_img,  state, xvb_endpoints) async move {
+                Self::read_pty_xp(output_parse, output_pub, reader, process_xvb, &pub_api_xvb, process_p2pool, &p2pool_state, &p2pool_img, &state, &xvb_endpoints).await;
             }),
         );
         // 1b. Create command
@@ -435,13 +572,27 @@ impl Helper {
         let mut stdin = pair.master.take_writer().unwrap();
         // to refactor to let user use his own ports
         let api_summary_xp = format!(
-            "http://127.0.0.1:{}/{}",
+            "http://{}:{}/{}",
+            state.api_ip(),
             state.api_port(),
             XMRIG_API_SUMMARY_ENDPOINT
         );
+        let api_workers_xp = format!(
+            "http://{}:{}/{}",
+            state.api_ip(),
+            state.api_port(),
+            XMRIG_PROXY_API_WORKERS_ENDPOINT
+        );
+        let api_miners_xp = format!(
+            "http://{}:{}/{}",
+            state.api_ip(),
+            state.api_port(),
+            XMRIG_PROXY_API_MINERS_ENDPOINT
+        );
+        let mut alerts = WorkerAlerts::default();
 
         // set state
-        let client = client();
+        let client = client(None);
         process.lock().unwrap().state = ProcessState::NotMining;
         process.lock().unwrap().signal = ProcessSignal::None;
         // reset stats
@@ -517,6 +668,7 @@ impl Helper {
                     p2pool_img,
                     p2pool_state,
                     state,
+                    &xvb_endpoints.lock().unwrap(),
                 );
                 drop(pub_api_lock);
                 drop(process_lock);
@@ -530,6 +682,22 @@ impl Helper {
                             "XMRig-Proxy Watchdog | HTTP API request OK, attempting [update_from_priv()]"
                         );
                         PubXmrigProxyApi::update_from_priv(pub_api, priv_api);
+                        for msg in PubXmrigProxyApi::update_workers(
+                            pub_api,
+                            &client,
+                            &api_workers_xp,
+                            &api_miners_xp,
+                            &state.token,
+                            &mut alerts,
+                        )
+                        .await
+                        {
+                            output_console(
+                                &mut gui_api.lock().unwrap().output,
+                                &msg,
+                                ProcessName::XmrigProxy,
+                            );
+                        }
                     }
                     Err(err) => {
                         warn!(
@@ -558,6 +726,7 @@ impl Helper {
                         &pool,
                         "",
                         GUPAX_VERSION_UNDERSCORE,
+                        None,
                     )
                     .await
                     {
@@ -591,6 +760,7 @@ impl Helper {
 //---------------------------------------------------------------------------------------------------- [ImgProxy]
 #[derive(Debug, Clone)]
 pub struct ImgProxy {
+    pub api_ip: String,
     pub api_port: u16,
     pub port: u16,
     pub token: String,
@@ -605,6 +775,7 @@ impl Default for ImgProxy {
 impl ImgProxy {
     pub fn new() -> Self {
         Self {
+            api_ip: "127.0.0.1".to_string(),
             api_port: PROXY_API_PORT_DEFAULT,
             port: PROXY_PORT_DEFAULT,
             token: String::new(),
@@ -624,8 +795,11 @@ pub struct PubXmrigProxyApi {
     pub hashrate_1h: f32,
     pub hashrate_12h: f32,
     pub hashrate_24h: f32,
+    // total difficulty of the accepted shares since the start of XMRig-Proxy
+    pub hashes_total: Option<u64>,
     pub miners: u16,
     pub pool: Option<Pool>,
+    pub workers: Vec<ProxyWorker>,
 }
 
 impl Default for PubXmrigProxyApi {
@@ -646,8 +820,10 @@ impl PubXmrigProxyApi {
             hashrate_1h: 0.0,
             hashrate_12h: 0.0,
             hashrate_24h: 0.0,
+            hashes_total: None,
             miners: 0,
             pool: None,
+            workers: Vec::new(),
         }
     }
     #[allow(clippy::too_many_arguments)]
@@ -661,6 +837,7 @@ impl PubXmrigProxyApi {
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         p2pool_state: &P2pool,
         state: &XmrigProxy,
+        endpoints: &XvbEndpoints,
     ) {
         // 1. Take the process's current output buffer and combine it with Pub (if not empty)
         let mut output_pub = output_pub.lock().unwrap();
@@ -685,6 +862,7 @@ impl PubXmrigProxyApi {
                 &output_parse,
                 state.bind_port(),
                 p2pool_state.current_port(process_p2pool.is_alive(), &p2pool_img.lock().unwrap()),
+                endpoints,
             ) {
                 public.pool = Some(name_pool);
             }
@@ -711,7 +889,7 @@ impl PubXmrigProxyApi {
             gui_api.output.push_str(&buf);
         }
     }
-    fn update_from_priv(public: &Arc<Mutex<Self>>, private: PrivXmrigProxyApi) {
+    pub(crate) fn update_from_priv(public: &Arc<Mutex<Self>>, private: PrivXmrigProxyApi) {
         let mut public = public.lock().unwrap();
         let mut total_hashrate = private
             .hashrate
@@ -729,10 +907,295 @@ impl PubXmrigProxyApi {
             hashrate_1h: private.hashrate.total[2],
             hashrate_12h: private.hashrate.total[3],
             hashrate_24h: private.hashrate.total[4],
+            hashes_total: private.results.hashes_total,
             miners: private.miners.now,
             ..std::mem::take(&mut *public)
         }
     }
+    // "3 online, 1 missing"
+    pub fn workers_summary(&self) -> String {
+        let count = |status| self.workers.iter().filter(|w| w.status == status).count();
+        let online = self.workers.iter().filter(|w| w.connections > 0).count();
+        let mut summary = format!("{online} online");
+        for (status, name) in [
+            (WorkerStatus::Missing, "missing"),
+            (WorkerStatus::Slow, "slow"),
+        ] {
+            let n = count(status);
+            if n > 0 {
+                write!(summary, ", {n} {name}").unwrap();
+            }
+        }
+        summary
+    }
+    // Request the workers and the miners connected to the proxy.
+    // Returns the alerts about workers that went missing or whose hashrate dropped.
+    pub(crate) async fn update_workers(
+        public: &Arc<Mutex<Self>>,
+        client: &Client,
+        api_uri_workers: &str,
+        api_uri_miners: &str,
+        token: &str,
+        alerts: &mut WorkerAlerts,
+    ) -> Vec<String> {
+        let workers = match PrivXmrigProxyWorkers::request(client, api_uri_workers, token).await {
+            Ok(workers) => workers,
+            Err(err) => {
+                debug!(
+                    "XMRig-Proxy Watchdog | Could not request workers from: {}\n{}",
+                    api_uri_workers, err
+                );
+                return Vec::new();
+            }
+        };
+        // the miners are only used for the agent of the workers, the API can restrict them.
+        let miners = PrivXmrigProxyMiners::request(client, api_uri_miners, token)
+            .await
+            .ok();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let mut workers = workers
+            .workers
+            .into_iter()
+            .map(|w| ProxyWorker::from_priv(w, now, miners.as_ref()))
+            .collect::<Vec<_>>();
+        let messages = alerts.check(&mut workers);
+        public.lock().unwrap().workers = workers;
+        messages
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Workers
+// The workers of XMRig-Proxy are grouped by rig id, user, password, agent or IP depending on the mode of the proxy.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProxyWorker {
+    pub name: String,
+    pub ip: String,
+    pub connections: u64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub invalid: u64,
+    // 1m/10m/1h/12h/24h in H/s
+    pub hashrate: [f32; 5],
+    // seconds since the last share, None if no share was sent
+    pub last_seen: Option<u64>,
+    pub agent: Option<String>,
+    pub status: WorkerStatus,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WorkerStatus {
+    #[default]
+    Ok,
+    Slow,
+    Missing,
+    // without connection since before it was watched
+    Offline,
+}
+
+impl std::fmt::Display for WorkerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ok => write!(f, "OK"),
+            Self::Slow => write!(f, "Hashrate dropped"),
+            Self::Missing => write!(f, "Missing"),
+            Self::Offline => write!(f, "Offline"),
+        }
+    }
+}
+
+impl ProxyWorker {
+    // [now] is in milliseconds since the UNIX epoch, like the last share of the worker.
+    pub fn from_priv(
+        private: PrivXmrigProxyWorker,
+        now: u64,
+        miners: Option<&PrivXmrigProxyMiners>,
+    ) -> Self {
+        let PrivXmrigProxyWorker(
+            name,
+            ip,
+            connections,
+            accepted,
+            rejected,
+            invalid,
+            _hashes,
+            last_hash,
+            h1m,
+            h10m,
+            h1h,
+            h12h,
+            h24h,
+        ) = private;
+        // every hashrate value of xmrig-proxy is in kH/s
+        let hashrate = [h1m, h10m, h1h, h12h, h24h].map(|h| (h * 1000.0) as f32);
+        Self {
+            agent: miners.and_then(|m| m.agent(&name, &ip)),
+            name,
+            ip,
+            connections,
+            accepted,
+            rejected,
+            invalid,
+            hashrate,
+            last_seen: (last_hash > 0).then(|| now.saturating_sub(last_hash) / 1000),
+            status: WorkerStatus::Ok,
+        }
+    }
+}
+
+// A worker is missing once its last connection is closed,
+// its hashrate dropped if the last minute is below half of the last hour.
+const WORKER_DROP: f32 = 0.5;
+// the hashrate is considered recovered above this part of the last hour.
+const WORKER_RECOVERED: f32 = 0.8;
+
+#[derive(Debug, Default)]
+pub struct WorkerAlerts {
+    connected: HashSet<String>,
+    missing: HashSet<String>,
+    slow: HashSet<String>,
+}
+
+impl WorkerAlerts {
+    // Set the status of the workers and return a message for every change.
+    pub fn check(&mut self, workers: &mut [ProxyWorker]) -> Vec<String> {
+        let mut messages = Vec::new();
+        let mut connected = HashSet::new();
+        for worker in workers.iter_mut() {
+            if worker.connections > 0 {
+                connected.insert(worker.name.clone());
+                if self.missing.remove(&worker.name) {
+                    messages.push(format!("Worker [{}] is connected again", worker.name));
+                }
+                let [now, _, hour, _, _] = worker.hashrate;
+                if self.slow.contains(&worker.name) {
+                    if now >= hour * WORKER_RECOVERED {
+                        self.slow.remove(&worker.name);
+                        messages.push(format!(
+                            "Hashrate of worker [{}] recovered: {:.0} H/s",
+                            worker.name, now
+                        ));
+                    }
+                } else if hour > 0.0 && now < hour * WORKER_DROP {
+                    self.slow.insert(worker.name.clone());
+                    messages.push(format!(
+                        "Hashrate of worker [{}] dropped to {:.0} H/s, the average of the last hour is {:.0} H/s",
+                        worker.name, now, hour
+                    ));
+                }
+            } else if self.connected.contains(&worker.name) {
+                self.missing.insert(worker.name.clone());
+                messages.push(missing_message(worker.name.as_str(), worker.last_seen));
+            }
+            worker.status = if self.missing.contains(&worker.name) {
+                WorkerStatus::Missing
+            } else if self.slow.contains(&worker.name) {
+                WorkerStatus::Slow
+            } else if worker.connections == 0 {
+                WorkerStatus::Offline
+            } else {
+                WorkerStatus::Ok
+            };
+        }
+        // workers removed from the list by the proxy
+        for name in self.connected.difference(&connected) {
+            if !workers.iter().any(|w| &w.name == name) && self.missing.insert(name.clone()) {
+                messages.push(missing_message(name, None));
+            }
+        }
+        self.slow.retain(|name| connected.contains(name));
+        self.connected = connected;
+        messages
+    }
+}
+
+fn missing_message(name: &str, last_seen: Option<u64>) -> String {
+    match last_seen {
+        Some(secs) => format!(
+            "Worker [{name}] is missing, its last share was {} ago",
+            HumanTime::into_human(Duration::from_secs(secs)).display(false)
+        ),
+        None => format!("Worker [{name}] is missing"),
+    }
+}
+
+// [name, ip, connections, accepted, rejected, invalid, hashes, last share (ms), hashrate 1m/10m/1h/12h/24h (kH/s)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PrivXmrigProxyWorker(
+    String,
+    String,
+    u64,
+    u64,
+    u64,
+    u64,
+    u64,
+    u64,
+    f64,
+    f64,
+    f64,
+    f64,
+    f64,
+);
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PrivXmrigProxyWorkers {
+    #[serde(default)]
+    pub mode: String,
+    pub workers: Vec<PrivXmrigProxyWorker>,
+}
+
+impl PrivXmrigProxyWorkers {
+    async fn request(client: &Client, api_uri: &str, token: &str) -> anyhow::Result<Self> {
+        Ok(client
+            .get(api_uri)
+            .header(AUTHORIZATION, ["Bearer ", token].concat())
+            .timeout(Duration::from_millis(5000))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+// Every miner is an array of values described by [format].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PrivXmrigProxyMiners {
+    pub format: Vec<String>,
+    pub miners: Vec<Vec<serde_json::Value>>,
+}
+
+impl PrivXmrigProxyMiners {
+    async fn request(client: &Client, api_uri: &str, token: &str) -> anyhow::Result<Self> {
+        Ok(client
+            .get(api_uri)
+            .header(AUTHORIZATION, ["Bearer ", token].concat())
+            .timeout(Duration::from_millis(5000))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+    fn field<'a>(&self, miner: &'a [serde_json::Value], field: &str) -> Option<&'a str> {
+        let index = self.format.iter().position(|f| f == field)?;
+        miner.get(index)?.as_str()
+    }
+    // Agent of the first miner of the worker, found by its name or else its IP.
+    pub fn agent(&self, name: &str, ip: &str) -> Option<String> {
+        let by_name = self.miners.iter().find(|m| {
+            ["rig_id", "user", "ip"]
+                .iter()
+                .any(|f| self.field(m, f) == Some(name))
+        });
+        by_name
+            .or_else(|| self.miners.iter().find(|m| self.field(m, "ip") == Some(ip)))
+            .and_then(|m| self.field(m, "agent"))
+            .filter(|a| !a.is_empty())
+            .map(str::to_string)
+    }
 }
 
 #[derive(Deserialize, Serialize)]
@@ -740,12 +1203,23 @@ pub struct PrivXmrigProxyApi {
     hashrate: HashrateProxy,
     miners: Miners,
     results: Results,
+    // used by attach mode, absent from older XMRig-Proxy
+    #[serde(default)]
+    upstreams: Upstreams,
+}
+
+#[derive(Deserialize, Serialize, Default)]
+struct Upstreams {
+    active: u32,
 }
 
 #[derive(Deserialize, Serialize)]
 struct Results {
     accepted: u32,
     rejected: u32,
+    // absent from older XMRig-Proxy
+    #[serde(default)]
+    hashes_total: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -761,7 +1235,7 @@ struct Miners {
 impl PrivXmrigProxyApi {
     #[inline]
     // Send an HTTP request to XMRig's API, serialize it into [Self] and return it
-    async fn request_xp_api(
+    pub(crate) async fn request_xp_api(
         client: &Client,
         api_uri: &str,
         token: &str,
@@ -17,13 +17,20 @@
 
 use crate::XVB_MIN_TIME_SEND;
 use crate::disk::state::P2poolChain;
+use crate::disk::xvb_samples::{XvbCycle, XvbSample, XvbSamples};
 use crate::helper::Process;
+use crate::helper::fleet::PubFleetApi;
 use crate::helper::p2pool::ImgP2pool;
 use crate::helper::xrig::current_api_url_xrig;
 use crate::helper::xrig::xmrig::ImgXmrig;
 use crate::helper::xrig::xmrig_proxy::ImgProxy;
 use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
+use crate::helper::xrig::{get_xmrig_pool, set_xmrig_pool};
+use crate::helper::xvb::current_accepted_difficulty;
 use crate::helper::xvb::current_controllable_hr;
+use crate::helper::xvb::ledger::{CycleLedger, Delivery};
+use crate::helper::xvb::miners::{ControlledMiner, RemotePool, plan_miners, remote_miners};
+use crate::helper::xvb::planner::PlannerInputs;
 use crate::miscs::output_console;
 use crate::miscs::output_console_without_time;
 use crate::utils::constants::BLOCK_PPLNS_WINDOW_NANO;
@@ -53,6 +60,9 @@ use crate::{
 
 use super::{PubXvbApi, SamplesAverageHour, priv_stats::RuntimeDonationLevel};
 
+// The progress of the cycle is saved at this interval in seconds.
+const XVB_SAMPLES_TICK: u32 = 60;
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn algorithm(
     client: &Client,
@@ -71,6 +81,9 @@ pub(crate) async fn algorithm(
     xmrig_img: &Arc<Mutex<ImgXmrig>>,
     p2pool_img: &Arc<Mutex<ImgP2pool>>,
     p2pool_process: &Arc<Mutex<Process>>,
+    samples: &Arc<Mutex<XvbSamples>>,
+    fleet: &Arc<Mutex<PubFleetApi>>,
+    controlled: &[String],
 ) {
     let token_xmrig = if xp_alive {
         proxy_img.lock().unwrap().token.clone()
@@ -95,7 +108,9 @@ pub(crate) async fn algorithm(
         xmrig_img,
         p2pool_img,
         p2pool_process,
-    );
+        samples,
+    )
+    .with_remote_miners(remote_miners(&fleet.lock().unwrap(), controlled));
     algorithm.run().await;
 }
 
@@ -115,6 +130,7 @@ pub struct Algorithm<'a> {
     pub stats: Stats,
     p2pool_img: &'a Arc<Mutex<ImgP2pool>>,
     p2pool_process: &'a Arc<Mutex<Process>>,
+    samples: &'a Arc<Mutex<XvbSamples>>,
 }
 
 #[derive(Debug)]
@@ -138,7 +154,11 @@ pub struct Stats {
     spareable_hashrate: f32,
     needed_time_xvb: u32,
     api_url: String,
+    // SOCKS5 proxy XMRig was started with, to reach XvB pools the same way.
+    socks5: Option<String>,
     msg_xmrig_or_xp: String,
+    // remote miners of the fleet controlled with the local one
+    remote_miners: Vec<ControlledMiner>,
 }
 
 impl<'a> Algorithm<'a> {
@@ -161,6 +181,7 @@ impl<'a> Algorithm<'a> {
         xmrig_img: &Arc<Mutex<ImgXmrig>>,
         p2pool_img: &'a Arc<Mutex<ImgP2pool>>,
         p2pool_process: &'a Arc<Mutex<Process>>,
+        samples: &'a Arc<Mutex<XvbSamples>>,
     ) -> Self {
         let use_sidechain_hr = gui_api_xvb.lock().unwrap().use_p2pool_sidechain_hr;
         let hashrate_xmrig = current_controllable_hr(xp_alive, gui_api_xp, gui_api_xmrig);
@@ -208,6 +229,12 @@ impl<'a> Algorithm<'a> {
             current_api_url_xrig(true, Some(&xmrig_img.lock().unwrap()), None)
         };
 
+        let socks5 = if xp_alive {
+            None
+        } else {
+            xmrig_img.lock().unwrap().socks5.clone()
+        };
+
         let msg_xmrig_or_xp = (if xp_alive { "XMRig-Proxy" } else { "XMRig" }).to_string();
         info!("xp alive: {:?}", xp_alive);
 
@@ -239,7 +266,9 @@ impl<'a> Algorithm<'a> {
             spareable_hashrate,
             needed_time_xvb: u32::default(),
             api_url,
+            socks5,
             msg_xmrig_or_xp,
+            remote_miners: Vec::new(),
         };
 
         let mut new_instance = Self {
@@ -257,18 +286,41 @@ impl<'a> Algorithm<'a> {
             stats,
             p2pool_img,
             p2pool_process,
+            samples,
         };
-        // external XvB HR is taken into account with get_target_donation_hashrate so the needed time is calculating how much time is needed from local sparable HR only
-        new_instance.stats.target_donation_hashrate =
-            new_instance.get_target_donation_hashrate().max(0.0);
-        new_instance.stats.needed_time_xvb = Self::get_needed_time_xvb(
-            new_instance.stats.target_donation_hashrate,
-            new_instance.stats.hashrate_xmrig,
-        );
+        new_instance.update_targets();
 
         new_instance
     }
 
+    // The hashrate of the remote miners is added to the one of the local miner,
+    // the donation target is shared between all of them.
+    pub fn with_remote_miners(mut self, miners: Vec<ControlledMiner>) -> Self {
+        if miners.is_empty() {
+            return self;
+        }
+        info!("Algorithm | controlling remote miners: {:?}", miners);
+        self.stats.hashrate_xmrig += miners.iter().map(|m| m.hashrate).sum::<f32>();
+        self.stats.spareable_hashrate = self.stats.hashrate_xmrig - self.stats.share_min_hashrate;
+        self.stats.remote_miners = miners;
+        self.update_targets();
+        self
+    }
+
+    fn update_targets(&mut self) {
+        // external XvB HR is taken into account with get_target_donation_hashrate so the needed time is calculating how much time is needed from local sparable HR only
+        self.stats.target_donation_hashrate = self.get_target_donation_hashrate().max(0.0);
+        self.stats.needed_time_xvb = Self::get_needed_time_xvb(
+            self.stats.target_donation_hashrate,
+            self.stats.hashrate_xmrig,
+        );
+        self.gui_api_xvb.lock().unwrap().planner = Some(PlannerInputs {
+            hashrate: self.stats.hashrate_xmrig,
+            share_min_hashrate: self.stats.share_min_hashrate,
+            xvb_external_hashrate: self.stats.xvb_external_hashrate,
+        });
+    }
+
     fn is_share_fulfilled(&self) -> bool {
         let is_criteria_fulfilled = self.stats.share > 0;
 
@@ -316,6 +368,7 @@ impl<'a> Algorithm<'a> {
                 &node,
                 &self.stats.address,
                 self.rig,
+                None,
             )
             .await
             {
@@ -369,6 +422,7 @@ impl<'a> Algorithm<'a> {
                 &pool,
                 &self.stats.address,
                 "",
+                self.stats.socks5.as_deref(),
             )
             .await
             {
@@ -401,20 +455,11 @@ impl<'a> Algorithm<'a> {
             "Algorithm | algo sleep for {} seconds while mining on P2pool",
             XVB_TIME_ALGO
         );
-        sleep(Duration::from_secs(XVB_TIME_ALGO.into())).await;
-        let hashrate = current_controllable_hr(self.xp_alive, self.gui_api_xp, self.gui_api_xmrig);
-        self.gui_api_xvb
-            .lock()
-            .unwrap()
-            .p2pool_sent_last_hour_samples
-            .0
-            .push_back(hashrate);
-        self.gui_api_xvb
-            .lock()
-            .unwrap()
-            .xvb_sent_last_hour_samples
-            .0
-            .push_back(0.0);
+        let p2pool = self.deliver(XVB_TIME_ALGO, false).await;
+        self.record_cycle(CycleLedger {
+            p2pool,
+            ..Default::default()
+        });
     }
 
     async fn send_all_xvb(&self) {
@@ -424,20 +469,11 @@ impl<'a> Algorithm<'a> {
             "Algorithm | algo sleep for {} seconds while mining on XvB",
             XVB_TIME_ALGO
         );
-        sleep(Duration::from_secs(XVB_TIME_ALGO.into())).await;
-        let hashrate = current_controllable_hr(self.xp_alive, self.gui_api_xp, self.gui_api_xmrig);
-        self.gui_api_xvb
-            .lock()
-            .unwrap()
-            .xvb_sent_last_hour_samples
-            .0
-            .push_back(hashrate);
-        self.gui_api_xvb
-            .lock()
-            .unwrap()
-            .p2pool_sent_last_hour_samples
-            .0
-            .push_back(0.0);
+        let xvb = self.deliver(XVB_TIME_ALGO, true).await;
+        self.record_cycle(CycleLedger {
+            xvb,
+            ..Default::default()
+        });
     }
 
     async fn sleep_then_update_node_xmrig(&self) {
@@ -445,10 +481,9 @@ impl<'a> Algorithm<'a> {
             "Algorithm | algo sleep for {} seconds while mining on P2pool",
             XVB_TIME_ALGO - self.stats.needed_time_xvb
         );
-        sleep(Duration::from_secs(
-            (XVB_TIME_ALGO - self.stats.needed_time_xvb).into(),
-        ))
-        .await;
+        let p2pool = self
+            .deliver(XVB_TIME_ALGO - self.stats.needed_time_xvb, false)
+            .await;
 
         // only update xmrig config if it is actually mining.
         info!("Algorithm | request xmrig to mine on XvB");
@@ -462,25 +497,241 @@ impl<'a> Algorithm<'a> {
             "Algorithm | algo sleep for {} seconds while mining on XvB",
             self.stats.needed_time_xvb
         );
-        sleep(Duration::from_secs(self.stats.needed_time_xvb.into())).await;
-        // HR could be not the same now as the avg sent the last 10mn, will be replaced later by a better history of HR
+        let xvb = self.deliver(self.stats.needed_time_xvb, true).await;
+        self.record_cycle(CycleLedger { p2pool, xvb });
+    }
+
+    // Mine on the current pool for [secs] and measure the difficulty of the shares it accepted.
+    // The progress is saved at every tick, so that a restart does not lose it.
+    async fn deliver(&self, secs: u32, on_xvb: bool) -> Delivery {
+        let start = current_accepted_difficulty(self.xp_alive, self.gui_api_xp, self.gui_api_xmrig);
+        let mut done = 0;
+        while done < secs {
+            let tick = (secs - done).min(XVB_SAMPLES_TICK);
+            sleep(Duration::from_secs(tick.into())).await;
+            done += tick;
+            self.save_progress(tick, on_xvb);
+        }
+        let end = current_accepted_difficulty(self.xp_alive, self.gui_api_xp, self.gui_api_xmrig);
         let hashrate = current_controllable_hr(self.xp_alive, self.gui_api_xp, self.gui_api_xmrig);
-        self.gui_api_xvb
-            .lock()
-            .unwrap()
+        Delivery::new(secs, start, end, hashrate)
+    }
+
+    fn save_progress(&self, secs: u32, on_xvb: bool) {
+        let hashrate = current_controllable_hr(self.xp_alive, self.gui_api_xp, self.gui_api_xmrig);
+        let mut samples = self.samples.lock().unwrap();
+        let now = chrono::Utc::now().timestamp();
+        let cycle = samples.cycle.get_or_insert(XvbCycle {
+            started: now - secs as i64,
+            ..Default::default()
+        });
+        if on_xvb {
+            cycle.xvb_secs += secs;
+        } else {
+            cycle.p2pool_secs += secs;
+        }
+        cycle.updated = now;
+        cycle.hashrate = hashrate;
+        if let Err(e) = samples.save() {
+            warn!("Algorithm | Could not save the samples: {}", e);
+        }
+    }
+
+    // The hashrate delivered to each pool is kept for the estimation of the external hashrate.
+    fn record_cycle(&self, ledger: CycleLedger) {
+        info!("Algorithm | {:?}", ledger);
+        let mut samples = self.samples.lock().unwrap();
+        samples.push(XvbSample {
+            timestamp: chrono::Utc::now().timestamp(),
+            p2pool: ledger.p2pool.cycle_hashrate(),
+            xvb: ledger.xvb.cycle_hashrate(),
+        });
+        if let Err(e) = samples.save() {
+            warn!("Algorithm | Could not save the samples: {}", e);
+        }
+        drop(samples);
+        let mut gui_api = self.gui_api_xvb.lock().unwrap();
+        gui_api
             .p2pool_sent_last_hour_samples
             .0
-            .push_back(
-                hashrate
-                    * ((XVB_TIME_ALGO as f32 - self.stats.needed_time_xvb as f32)
-                        / XVB_TIME_ALGO as f32),
-            );
-        self.gui_api_xvb
-            .lock()
-            .unwrap()
+            .push_back(ledger.p2pool.cycle_hashrate());
+        gui_api
             .xvb_sent_last_hour_samples
             .0
-            .push_back(hashrate * (self.stats.needed_time_xvb as f32 / XVB_TIME_ALGO as f32));
+            .push_back(ledger.xvb.cycle_hashrate());
+        output_console(
+            &mut gui_api.output,
+            &ledger.summary(),
+            crate::helper::ProcessName::Xvb,
+        );
+    }
+
+    // Seconds on XvB of each miner, the local one first. Miners dedicated to a pool stay on it
+    // for the whole cycle, the one that is switched goes to XvB at the end of the cycle.
+    async fn run_plan(&self, plan: &[u32]) {
+        *self.time_donated.lock().unwrap() = plan[0];
+        let mut lines = vec![format!(
+            "local {}: {}s on XvB",
+            self.stats.msg_xmrig_or_xp, plan[0]
+        )];
+        lines.extend(
+            self.stats
+                .remote_miners
+                .iter()
+                .zip(&plan[1..])
+                .map(|(m, secs)| format!("{}: {}s on XvB", m.name, secs)),
+        );
+        output_console(
+            &mut self.gui_api_xvb.lock().unwrap().output,
+            &format!("Sharing the cycle between the miners: {}", lines.join(", ")),
+            crate::helper::ProcessName::Xvb,
+        );
+        let switched = plan
+            .iter()
+            .position(|secs| *secs > 0 && *secs < XVB_TIME_ALGO);
+        let xvb_secs = switched.map_or(0, |i| plan[i]);
+        // first part of the cycle, every miner on the pool it is dedicated to
+        for (miner, secs) in self.stats.remote_miners.iter().zip(&plan[1..]) {
+            self.target_remote(miner, *secs == XVB_TIME_ALGO).await;
+        }
+        let local_on_xvb = plan[0] == XVB_TIME_ALGO;
+        if local_on_xvb {
+            self.target_xvb_node().await;
+        } else {
+            self.target_p2pool_node().await;
+        }
+        let first = self.deliver(XVB_TIME_ALGO - xvb_secs, local_on_xvb).await;
+        // end of the cycle, the switched miner goes to XvB
+        match switched {
+            Some(0) => self.target_xvb_node().await,
+            Some(i) => {
+                self.target_remote(&self.stats.remote_miners[i - 1], true)
+                    .await
+            }
+            None => (),
+        }
+        let second = self.deliver(xvb_secs, plan[0] > 0).await;
+        let mut ledger = CycleLedger::default();
+        for (delivery, on_xvb) in [(first, local_on_xvb), (second, plan[0] > 0)] {
+            if on_xvb {
+                ledger.xvb = ledger.xvb.merge(delivery);
+            } else {
+                ledger.p2pool = ledger.p2pool.merge(delivery);
+            }
+        }
+        // the remote miners are not measured, their hashrate is used for the time spent
+        for (miner, secs) in self.stats.remote_miners.iter().zip(&plan[1..]) {
+            let part = *secs as f32 / XVB_TIME_ALGO as f32;
+            ledger.xvb.remote += miner.hashrate * part;
+            ledger.p2pool.remote += miner.hashrate * (1.0 - part);
+        }
+        self.record_cycle(ledger);
+    }
+
+    // A remote miner is sent back to the pool it used before XvB, not to the local P2Pool.
+    // A miner already on XvB follows the pool chosen by the algorithm.
+    async fn target_remote(&self, miner: &ControlledMiner, xvb: bool) {
+        let result = if xvb {
+            let pool = self.gui_api_xvb.lock().unwrap().stats_priv.pool.clone();
+            let current = self
+                .gui_api_xvb
+                .lock()
+                .unwrap()
+                .remote_pools
+                .get(&miner.name)
+                .map(|r| (r.xvb.clone(), r.socks5().map(str::to_string)));
+            match current {
+                // already on the right pool
+                Some((current, _)) if current == pool => return,
+                Some((_, socks5)) => {
+                    info!("Algorithm | request {} to mine on {}", miner.name, pool);
+                    async {
+                        update_xmrig_config(
+                            self.client,
+                            &miner.api_url,
+                            &miner.token,
+                            &pool,
+                            &self.stats.address,
+                            "",
+                            socks5.as_deref(),
+                        )
+                        .await?;
+                        self.gui_api_xvb
+                            .lock()
+                            .unwrap()
+                            .remote_pools
+                            .set_xvb(&miner.name, pool.clone());
+                        anyhow::Ok(())
+                    }
+                    .await
+                }
+                None => {
+                    info!("Algorithm | request {} to mine on XvB", miner.name);
+                    async {
+                        let previous =
+                            get_xmrig_pool(self.client, &miner.api_url, &miner.token).await?;
+                        let remote = RemotePool {
+                            miner: miner.clone(),
+                            previous,
+                            xvb: pool.clone(),
+                        };
+                        // the proxy of the miner is kept, the one of Gupaxx is not reachable from it
+                        let socks5 = remote.socks5().map(str::to_string);
+                        // saved before the switch, so the miner is restored if XvB stops meanwhile
+                        self.gui_api_xvb
+                            .lock()
+                            .unwrap()
+                            .remote_pools
+                            .insert(miner.name.clone(), remote);
+                        let result = update_xmrig_config(
+                            self.client,
+                            &miner.api_url,
+                            &miner.token,
+                            &pool,
+                            &self.stats.address,
+                            "",
+                            socks5.as_deref(),
+                        )
+                        .await;
+                        if result.is_err() {
+                            self.gui_api_xvb
+                                .lock()
+                                .unwrap()
+                                .remote_pools
+                                .remove(&miner.name);
+                        }
+                        result
+                    }
+                    .await
+                }
+            }
+        } else {
+            let saved = self
+                .gui_api_xvb
+                .lock()
+                .unwrap()
+                .remote_pools
+                .remove(&miner.name);
+            match saved {
+                Some(RemotePool { previous, .. }) => {
+                    info!("Algorithm | request {} to mine on its pool", miner.name);
+                    set_xmrig_pool(self.client, &miner.api_url, &miner.token, previous).await
+                }
+                // already on its pool
+                None => return,
+            }
+        };
+        if let Err(err) = result {
+            warn!("Algorithm | Failed request HTTP API {}", miner.name);
+            output_console(
+                &mut self.gui_api_xvb.lock().unwrap().output,
+                &format!(
+                    "Failure to update {} config with HTTP API.\nError: {}",
+                    miner.name, err
+                ),
+                crate::helper::ProcessName::Xvb,
+            );
+        }
     }
 
     pub fn get_target_donation_hashrate(&self) -> f32 {
@@ -612,7 +863,12 @@ impl<'a> Algorithm<'a> {
 
         info!("Algorithm | There are no shares in p2pool. Sending all hashrate to p2pool!");
 
-        self.send_all_p2pool().await
+        if self.stats.remote_miners.is_empty() {
+            self.send_all_p2pool().await
+        } else {
+            self.run_plan(&vec![0; self.stats.remote_miners.len() + 1])
+                .await
+        }
     }
 
     async fn fulfill_xvb_24_avg(&self) {
@@ -626,7 +882,12 @@ impl<'a> Algorithm<'a> {
 
         *self.time_donated.lock().unwrap() = XVB_TIME_ALGO;
 
-        self.send_all_xvb().await
+        if self.stats.remote_miners.is_empty() {
+            self.send_all_xvb().await
+        } else {
+            self.run_plan(&vec![XVB_TIME_ALGO; self.stats.remote_miners.len() + 1])
+                .await
+        }
     }
 
     async fn fulfill_normal_cycles(&self) {
@@ -639,6 +900,16 @@ impl<'a> Algorithm<'a> {
             crate::helper::ProcessName::Xvb,
         );
 
+        if !self.stats.remote_miners.is_empty() {
+            let mut hashrates = vec![current_controllable_hr(
+                self.xp_alive,
+                self.gui_api_xp,
+                self.gui_api_xmrig,
+            )];
+            hashrates.extend(self.stats.remote_miners.iter().map(|m| m.hashrate));
+            let plan = plan_miners(&hashrates, self.stats.target_donation_hashrate);
+            return self.run_plan(&plan).await;
+        }
         *self.time_donated.lock().unwrap() = self.stats.needed_time_xvb;
         // do not switch pool for a few seconds, let's make 6 seconds minimum.
 
@@ -0,0 +1,179 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Endpoints used by the XvB process: public and private stats API and the list of pools.
+// The defaults are the XvB servers, they can be replaced by a TOML file given
+// with the [GUPAXX_XVB_ENDPOINTS] environment variable, to use a mock server for example.
+// The list of pools can also be given by an API, so that a new region does not need a new release.
+
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::bail;
+use log::{info, warn};
+use reqwest_middleware::ClientWithMiddleware as Client;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    XVB_NODE_EU, XVB_NODE_NA, XVB_NODE_PORT, XVB_NODE_RPC, XVB_URL, XVB_URL_PRIVATE_API,
+    XVB_URL_PUBLIC_API,
+};
+
+pub const XVB_ENDPOINTS_ENV: &str = "GUPAXX_XVB_ENDPOINTS";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct XvbEndpoints {
+    pub public_api: String,
+    // address and token are added as query parameters
+    pub private_api: String,
+    // pools to choose from, in order of preference when their ping is equal
+    pub nodes: Vec<XvbNode>,
+    // optional API giving the list of pools as a JSON array, it replaces [nodes] when valid
+    pub nodes_api: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct XvbNode {
+    pub name: String,
+    pub host: String,
+    // stratum port given to XMRig/XMRig-Proxy
+    pub port: String,
+    pub tls: bool,
+    // RPC port of the Monero node of the pool, to check that it is synchronized
+    #[serde(default)]
+    pub rpc_port: Option<String>,
+}
+
+impl XvbNode {
+    fn new(name: &str, host: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            host: host.to_string(),
+            port: XVB_NODE_PORT.to_string(),
+            tls: true,
+            rpc_port: Some(XVB_NODE_RPC.to_string()),
+        }
+    }
+    // host:port as written by XMRig
+    pub fn address(&self) -> String {
+        [&self.host, ":", &self.port].concat()
+    }
+}
+
+impl Default for XvbEndpoints {
+    fn default() -> Self {
+        Self {
+            public_api: XVB_URL_PUBLIC_API.to_string(),
+            private_api: [XVB_URL, XVB_URL_PRIVATE_API].concat(),
+            nodes: vec![
+                XvbNode::new("European", XVB_NODE_EU),
+                XvbNode::new("North America", XVB_NODE_NA),
+            ],
+            nodes_api: None,
+        }
+    }
+}
+
+impl XvbEndpoints {
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let endpoints: Self = toml::from_str(&std::fs::read_to_string(path)?)?;
+        validate_nodes(&endpoints.nodes)?;
+        Ok(endpoints)
+    }
+    // Endpoints from the file given by [XVB_ENDPOINTS_ENV], the defaults are used if it is invalid.
+    pub fn from_env() -> Self {
+        let Some(path) = std::env::var_os(XVB_ENDPOINTS_ENV) else {
+            return Self::default();
+        };
+        match Self::read(Path::new(&path)) {
+            Ok(endpoints) => {
+                info!("XvB | Using endpoints: {:?}", endpoints);
+                endpoints
+            }
+            Err(e) => {
+                warn!(
+                    "XvB | Could not read endpoints at [{}], using the default ones: {}",
+                    Path::new(&path).display(),
+                    e
+                );
+                Self::default()
+            }
+        }
+    }
+    // Endpoints with the pools given by [nodes_api], so that new pools are used without restarting.
+    // The pools are kept if the new list is not valid.
+    pub async fn with_listed_nodes(&self, client: &Client) -> Self {
+        let mut endpoints = self.clone();
+        if let Some(url) = &self.nodes_api {
+            match request_nodes(client, url).await {
+                Ok(nodes) => endpoints.nodes = nodes,
+                Err(e) => warn!("XvB | Could not refresh the pools from {}: {}", url, e),
+            }
+        }
+        endpoints
+    }
+    // Refresh the pools of the endpoints shared with the GUI, XMRig and XMRig-Proxy.
+    pub async fn refresh(shared: &Arc<Mutex<Self>>, client: &Client) -> Self {
+        let current = shared.lock().unwrap().clone();
+        let endpoints = current.with_listed_nodes(client).await;
+        if endpoints != current {
+            info!("XvB | Using endpoints: {:?}", endpoints);
+            *shared.lock().unwrap() = endpoints.clone();
+        }
+        endpoints
+    }
+}
+
+async fn request_nodes(client: &Client, url: &str) -> anyhow::Result<Vec<XvbNode>> {
+    let nodes = client
+        .get(url)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?
+        .json::<Vec<XvbNode>>()
+        .await?;
+    validate_nodes(&nodes)?;
+    Ok(nodes)
+}
+
+// A list of pools must not be empty and every pool must be joinable.
+pub fn validate_nodes(nodes: &[XvbNode]) -> anyhow::Result<()> {
+    if nodes.is_empty() {
+        bail!("the list of pools is empty");
+    }
+    for (i, node) in nodes.iter().enumerate() {
+        if node.name.trim().is_empty() || node.host.trim().is_empty() {
+            bail!("the pool at position {} has no name or host", i + 1);
+        }
+        if node.port.parse::<u16>().is_err()
+            || node
+                .rpc_port
+                .as_ref()
+                .is_some_and(|p| p.parse::<u16>().is_err())
+        {
+            bail!("the pool [{}] has an invalid port", node.name);
+        }
+        if nodes[..i].iter().any(|n| n.name == node.name) {
+            bail!("the pool [{}] is present twice", node.name);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,108 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Accounting of the hashrate really delivered to P2Pool and XvB during a cycle of the algorithm.
+// The difficulty of the shares accepted by each pool is read from XMRig or XMRig-Proxy
+// before and after the time spent on it. The hashrate of the miner is only used as an estimate
+// when the difficulty is not available.
+
+use crate::XVB_TIME_ALGO;
+
+// Time spent on one pool during a cycle.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Delivery {
+    pub secs: u32,
+    // difficulty of the shares accepted by the pool during this time, None if it could not be read
+    pub difficulty: Option<u64>,
+    // hashrate of the miner at the end of the time, in H/s
+    pub hashrate: f32,
+    // hashrate averaged over the cycle estimated for the remote miners, in H/s
+    pub remote: f32,
+}
+
+impl Delivery {
+    // Difficulty accepted between two readings of the counter of XMRig or XMRig-Proxy.
+    // The counter starts again from 0 if the miner was restarted in between.
+    pub fn new(secs: u32, start: Option<u64>, end: Option<u64>, hashrate: f32) -> Self {
+        let difficulty = match (start, end) {
+            (Some(start), Some(end)) if end >= start => Some(end - start),
+            (Some(_), Some(end)) => Some(end),
+            _ => None,
+        };
+        Self {
+            secs,
+            difficulty,
+            hashrate,
+            ..Default::default()
+        }
+    }
+    // Two parts of a cycle spent on the same pool.
+    pub fn merge(self, other: Self) -> Self {
+        let difficulty = match (self.secs, other.secs) {
+            (0, _) => other.difficulty,
+            (_, 0) => self.difficulty,
+            _ => self.difficulty.zip(other.difficulty).map(|(a, b)| a + b),
+        };
+        Self {
+            secs: self.secs + other.secs,
+            difficulty,
+            hashrate: other.hashrate,
+            remote: self.remote + other.remote,
+        }
+    }
+    // Hashrate delivered averaged over a whole cycle, in H/s.
+    pub fn cycle_hashrate(&self) -> f32 {
+        let local = match self.difficulty {
+            Some(difficulty) => difficulty as f32 / XVB_TIME_ALGO as f32,
+            None => self.hashrate * self.secs as f32 / XVB_TIME_ALGO as f32,
+        };
+        local + self.remote
+    }
+    fn describe(&self, pool: &str) -> String {
+        let source = match self.difficulty {
+            Some(difficulty) => format!("{difficulty} accepted difficulty"),
+            None => "estimated".to_string(),
+        };
+        let remote = if self.remote > 0.0 {
+            format!(", remote miners {:.3}kH/s", self.remote / 1000.0)
+        } else {
+            String::new()
+        };
+        format!(
+            "{pool} {}s, {:.3}kH/s ({source}{remote})",
+            self.secs,
+            self.cycle_hashrate() / 1000.0
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CycleLedger {
+    pub p2pool: Delivery,
+    pub xvb: Delivery,
+}
+
+impl CycleLedger {
+    // line shown in the console of XvB at the end of a cycle
+    pub fn summary(&self) -> String {
+        format!(
+            "Cycle delivered: {} | {}",
+            self.p2pool.describe("P2Pool"),
+            self.xvb.describe("XvB")
+        )
+    }
+}
@@ -0,0 +1,151 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Remote XMRig and XMRig-Proxy of the fleet controlled by the XvB algorithm, with the local miner.
+// Instead of switching every miner between P2Pool and XvB during a cycle, whole miners are
+// sent to XvB for the cycle and only one of them is switched, to reach the donation target
+// with the fewest pool switches.
+// A remote miner is sent back to the pool it was using before XvB, since P2Pool is local to Gupaxx.
+
+use std::sync::{Arc, Mutex};
+
+use log::{info, warn};
+use reqwest_middleware::ClientWithMiddleware as Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    XMRIG_API_CONFIG_ENDPOINT, XVB_MIN_TIME_SEND, XVB_TIME_ALGO,
+    disk::status::RemoteKind,
+    helper::{
+        fleet::{PubFleetApi, RemoteApi},
+        xrig::set_xmrig_pool,
+        xvb::nodes::Pool,
+    },
+};
+
+use super::PubXvbApi;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ControlledMiner {
+    pub name: String,
+    // config endpoint of the HTTP API
+    pub api_url: String,
+    pub token: String,
+    // H/s
+    pub hashrate: f32,
+}
+
+// Remote miner sent to XvB, with the pool it was using before.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemotePool {
+    pub miner: ControlledMiner,
+    // pool config of the miner before XvB
+    pub previous: Value,
+    // XvB pool the miner is mining on
+    pub xvb: Pool,
+}
+
+impl RemotePool {
+    // SOCKS5 proxy of the miner, kept while it mines on XvB.
+    pub fn socks5(&self) -> Option<&str> {
+        self.previous.get("socks5").and_then(Value::as_str)
+    }
+}
+
+// Miners of the fleet chosen in the [XvB] tab that responded to the last poll and are mining.
+pub fn remote_miners(fleet: &PubFleetApi, controlled: &[String]) -> Vec<ControlledMiner> {
+    fleet
+        .stats
+        .iter()
+        .filter(|s| s.error.is_none() && controlled.contains(&s.instance.name))
+        .filter_map(|s| {
+            let hashrate = match &s.api {
+                RemoteApi::Xmrig(api) => {
+                    [api.hashrate_raw_15m, api.hashrate_raw_1m, api.hashrate_raw]
+                        .into_iter()
+                        .find(|h| *h > 0.0)?
+                }
+                RemoteApi::XmrigProxy(api) => [api.hashrate_10m, api.hashrate_1m]
+                    .into_iter()
+                    .find(|h| *h > 0.0)?,
+                RemoteApi::Node(_) => return None,
+            };
+            let instance = &s.instance;
+            Some(ControlledMiner {
+                name: instance.name.clone(),
+                api_url: format!(
+                    "http://{}:{}/{}",
+                    instance.host, instance.port, XMRIG_API_CONFIG_ENDPOINT
+                ),
+                token: instance.token.clone(),
+                hashrate,
+            })
+        })
+        .collect()
+}
+
+// Remote kinds that can be controlled.
+pub fn is_controllable(kind: RemoteKind) -> bool {
+    matches!(kind, RemoteKind::Xmrig | RemoteKind::XmrigProxy)
+}
+
+// Seconds each miner spends on XvB during the cycle to send [target] H/s on average.
+// The biggest miners that fit in the target mine on XvB for the whole cycle,
+// the smallest of the others is switched for the rest of the target.
+pub fn plan_miners(hashrates: &[f32], target: f32) -> Vec<u32> {
+    let mut plan = vec![0; hashrates.len()];
+    let mut order = (0..hashrates.len())
+        .filter(|i| hashrates[*i] > 0.0)
+        .collect::<Vec<_>>();
+    order.sort_by(|a, b| hashrates[*b].total_cmp(&hashrates[*a]));
+    let mut remaining = target;
+    for &i in &order {
+        if hashrates[i] <= remaining {
+            plan[i] = XVB_TIME_ALGO;
+            remaining -= hashrates[i];
+        }
+    }
+    if remaining > 0.0 {
+        if let Some(&i) = order.iter().rev().find(|i| plan[**i] == 0) {
+            let secs = (remaining / hashrates[i] * XVB_TIME_ALGO as f32) as u32;
+            plan[i] = match secs {
+                x if x <= XVB_MIN_TIME_SEND => 0,
+                x if x >= XVB_TIME_ALGO - XVB_MIN_TIME_SEND => XVB_TIME_ALGO,
+                x => x,
+            };
+        }
+    }
+    plan
+}
+
+// Send the remote miners still on XvB back to their pool, when XvB stops.
+pub async fn restore_remote_miners(client: &Client, gui_api: &Arc<Mutex<PubXvbApi>>) {
+    let remote_pools = gui_api.lock().unwrap().remote_pools.take();
+    for RemotePool {
+        miner, previous, ..
+    } in remote_pools.into_values()
+    {
+        match set_xmrig_pool(client, &miner.api_url, &miner.token, previous).await {
+            Ok(_) => info!("XvB | {} sent back to its pool", miner.name),
+            Err(e) => warn!(
+                "XvB | Could not send {} back to its pool: {}",
+                miner.name, e
+            ),
+        }
+    }
+}
@@ -0,0 +1,370 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Mock of the XvB servers, only built for tests or with the [mock-xvb] feature.
+// One HTTP server answers the public and private stats and the list of pools from a script,
+// each pool answers the stratum logins of XMRig and of the stratum probe,
+// and the [get_info] RPC of its node on the same port.
+// The script can be modified while the servers are running to simulate
+// invalid tokens, outages of the API or of a node and new rounds.
+// The servers run on their own threads until the process exits.
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex, MutexGuard},
+    thread,
+    time::Duration,
+};
+
+use log::{debug, warn};
+use serde_json::{Value, json};
+
+use crate::XVB_URL_PRIVATE_API;
+
+use super::{
+    endpoints::{XvbEndpoints, XvbNode},
+    rounds::XvbRound,
+};
+
+// Set this environment variable to use the mock instead of the XvB servers.
+#[cfg(feature = "mock-xvb")]
+pub const XVB_MOCK_ENV: &str = "GUPAXX_XVB_MOCK";
+const PUBLIC_API_PATH: &str = "/p2pool/stats";
+const NODES_API_PATH: &str = "/p2pool/nodes";
+
+#[derive(Clone, Debug)]
+pub struct MockXvbScript {
+    // token accepted by the private API, any token is accepted if None
+    pub token: Option<String>,
+    pub public_online: bool,
+    pub private_online: bool,
+    // pools, in the order of [MockXvb::start_with_nodes]
+    pub nodes: Vec<MockNode>,
+    pub time_remain: u32,
+    pub winner: String,
+    pub round_type: XvbRound,
+    pub block_height: u64,
+    pub roll_winner: u64,
+    pub roll_round: u64,
+    pub fails: u8,
+    pub donor_1hr_avg: f32,
+    pub donor_24hr_avg: f32,
+    // logins received by the stratum of the nodes
+    pub logins: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MockNode {
+    pub node: XvbNode,
+    // given by the API of the list of pools
+    pub listed: bool,
+    pub online: bool,
+    // delay before answering the login
+    pub latency: Duration,
+    // the job is sent after this delay instead of in the answer of the login
+    pub job_delay: Option<Duration>,
+    // the login is answered with an error
+    pub refuse_login: bool,
+    // given by the [get_info] RPC of the node
+    pub synchronized: bool,
+}
+
+impl Default for MockXvbScript {
+    fn default() -> Self {
+        Self {
+            token: None,
+            public_online: true,
+            private_online: true,
+            nodes: Vec::new(),
+            time_remain: 60,
+            winner: String::from("4AAAAAAA...AAAAAA"),
+            round_type: XvbRound::Vip,
+            block_height: 3000000,
+            roll_winner: 10,
+            roll_round: 20,
+            fails: 0,
+            donor_1hr_avg: 0.0,
+            donor_24hr_avg: 0.0,
+            logins: Vec::new(),
+        }
+    }
+}
+
+pub struct MockXvb {
+    script: Arc<Mutex<MockXvbScript>>,
+    api: SocketAddr,
+}
+
+impl MockXvb {
+    // Bind the servers on random local ports and start them, with one pool per name.
+    pub fn start_with_nodes(names: &[&str]) -> io::Result<Self> {
+        let script = Arc::new(Mutex::new(MockXvbScript::default()));
+        let api = TcpListener::bind("127.0.0.1:0")?;
+        let mock = Self {
+            script: script.clone(),
+            api: api.local_addr()?,
+        };
+        serve(api, script.clone(), |stream, script| {
+            handle_api(stream, &script)
+        });
+        for (i, name) in names.iter().enumerate() {
+            let listener = TcpListener::bind("127.0.0.1:0")?;
+            let addr = listener.local_addr()?;
+            script.lock().unwrap().nodes.push(MockNode {
+                node: XvbNode {
+                    name: name.to_string(),
+                    host: addr.ip().to_string(),
+                    port: addr.port().to_string(),
+                    tls: false,
+                    rpc_port: Some(addr.port().to_string()),
+                },
+                listed: true,
+                online: true,
+                latency: Duration::ZERO,
+                job_delay: None,
+                refuse_login: false,
+                synchronized: true,
+            });
+            serve(listener, script.clone(), move |stream, script| {
+                handle_node(stream, &script, i)
+            });
+        }
+        Ok(mock)
+    }
+    // Endpoints to give to the XvB process, XMRig and XMRig-Proxy to use the mock.
+    pub fn endpoints(&self) -> XvbEndpoints {
+        XvbEndpoints {
+            public_api: format!("http://{}{}", self.api, PUBLIC_API_PATH),
+            private_api: format!("http://{}{}", self.api, XVB_URL_PRIVATE_API),
+            nodes: self.script().nodes.iter().map(|n| n.node.clone()).collect(),
+            nodes_api: Some(format!("http://{}{}", self.api, NODES_API_PATH)),
+        }
+    }
+    pub fn script(&self) -> MutexGuard<'_, MockXvbScript> {
+        self.script.lock().unwrap()
+    }
+    // Draw a new raffle, so that a new round begins.
+    pub fn next_round(&self, winner: &str) {
+        let mut script = self.script();
+        script.block_height += 1;
+        script.roll_winner += 1;
+        script.roll_round += 1;
+        script.time_remain = 60;
+        script.winner = winner.to_string();
+    }
+    // Draw a new raffle at every period, the mock is kept alive by the thread.
+    #[cfg(feature = "mock-xvb")]
+    pub fn run_rounds(self, period: Duration) {
+        thread::spawn(move || {
+            loop {
+                thread::sleep(period);
+                self.next_round("4MockXvB...Winner");
+            }
+        });
+    }
+}
+
+fn serve<F>(listener: TcpListener, script: Arc<Mutex<MockXvbScript>>, handle: F)
+where
+    F: Fn(TcpStream, Arc<Mutex<MockXvbScript>>) -> io::Result<()> + Copy + Send + 'static,
+{
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let script = script.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle(stream, script) {
+                    debug!("XvB mock | connection closed: {}", e);
+                }
+            });
+        }
+    });
+}
+
+// Read the request line and skip the headers and body, return the target.
+fn read_request(reader: &mut BufReader<TcpStream>) -> io::Result<String> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    read_body(reader)?;
+    Ok(line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .to_string())
+}
+
+// Skip the headers and the body of a request.
+fn read_body(reader: &mut BufReader<TcpStream>) -> io::Result<()> {
+    let mut len = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                len = value.trim().parse().unwrap_or_default();
+            }
+        }
+    }
+    reader.read_exact(&mut vec![0; len])
+}
+
+fn respond(mut stream: &TcpStream, status: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn handle_api(stream: TcpStream, script: &Mutex<MockXvbScript>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let target = read_request(&mut reader)?;
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let script = script.lock().unwrap().clone();
+    if path == PUBLIC_API_PATH {
+        if !script.public_online {
+            return respond(&stream, "503 Service Unavailable", "");
+        }
+        respond(&stream, "200 OK", &public_stats(&script).to_string())
+    } else if path == XVB_URL_PRIVATE_API {
+        if !script.private_online {
+            return respond(&stream, "503 Service Unavailable", "");
+        }
+        let token = query
+            .split('&')
+            .find_map(|p| p.strip_prefix("token="))
+            .unwrap_or_default();
+        if script.token.as_ref().is_some_and(|t| t != token) {
+            return respond(&stream, "422 Unprocessable Entity", "");
+        }
+        let body = json!({
+            "fails": script.fails,
+            "donor_1hr_avg": script.donor_1hr_avg,
+            "donor_24hr_avg": script.donor_24hr_avg,
+        });
+        respond(&stream, "200 OK", &body.to_string())
+    } else if path == NODES_API_PATH {
+        let nodes: Vec<_> = script
+            .nodes
+            .iter()
+            .filter(|n| n.listed)
+            .map(|n| &n.node)
+            .collect();
+        respond(&stream, "200 OK", &json!(nodes).to_string())
+    } else {
+        respond(&stream, "404 Not Found", "")
+    }
+}
+
+fn public_stats(script: &MockXvbScript) -> Value {
+    json!({
+        "time_remain": script.time_remain,
+        "bonus_hr": 100.0,
+        "donate_hr": 100000.0,
+        "donate_miners": 10,
+        "donate_workers": 20,
+        "players": 30,
+        "players_round": 15,
+        "winner": script.winner,
+        "share_effort": "50%",
+        "block_reward": "0.6",
+        "round_type": script.round_type,
+        "block_height": script.block_height,
+        "block_hash": "0".repeat(64),
+        "roll_winner": script.roll_winner,
+        "roll_round": script.roll_round,
+        "reward_yearly": [0.1, 0.2],
+    })
+}
+
+fn handle_node(stream: TcpStream, script: &Mutex<MockXvbScript>, index: usize) -> io::Result<()> {
+    let node = |s: &MockXvbScript| s.nodes[index].clone();
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 {
+        let state = node(&script.lock().unwrap());
+        // an offline node closes every connection
+        if !state.online {
+            break;
+        }
+        // RPC of the node
+        if line.starts_with("POST ") {
+            read_body(&mut reader)?;
+            let body = json!({
+                "id": "0",
+                "jsonrpc": "2.0",
+                "result": {"mainnet": true, "synchronized": state.synchronized},
+            });
+            return respond(&stream, "200 OK", &body.to_string());
+        }
+        let request: Value = match serde_json::from_str(&line) {
+            Ok(r) => r,
+            Err(e) => {
+                warn!("XvB mock | invalid stratum request: {}", e);
+                break;
+            }
+        };
+        line.clear();
+        let job = json!({
+            "blob": "0".repeat(152),
+            "job_id": "1",
+            "target": "b88d0600",
+            "algo": "rx/0",
+            "height": script.lock().unwrap().block_height,
+            "seed_hash": "0".repeat(64),
+        });
+        let method = request["method"].as_str().unwrap_or_default();
+        let result = match method {
+            "login" => {
+                let login = request["params"]["login"].as_str().unwrap_or_default();
+                script.lock().unwrap().logins.push(login.to_string());
+                thread::sleep(state.latency);
+                if state.refuse_login {
+                    let error = json!({"code": -1, "message": "Invalid payment address provided"});
+                    writeln!(
+                        &stream,
+                        "{}",
+                        json!({"id": request["id"], "jsonrpc": "2.0", "error": error, "result": null})
+                    )?;
+                    continue;
+                }
+                if state.job_delay.is_some() {
+                    json!({"id": "mock", "extensions": ["keepalive"], "status": "OK"})
+                } else {
+                    json!({"id": "mock", "job": job, "extensions": ["keepalive"], "status": "OK"})
+                }
+            }
+            "keepalived" => json!({"status": "KEEPALIVED"}),
+            _ => json!({"status": "OK"}),
+        };
+        let response =
+            json!({"id": request["id"], "jsonrpc": "2.0", "error": null, "result": result});
+        writeln!(&stream, "{response}")?;
+        if let Some(delay) = state.job_delay.filter(|_| method == "login") {
+            thread::sleep(delay);
+            writeln!(
+                &stream,
+                "{}",
+                json!({"jsonrpc": "2.0", "method": "job", "params": job})
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -15,8 +15,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::components::stratum::ProbeResult;
 use crate::helper::xrig::{current_api_url_xrig, update_xmrig_config};
 use crate::helper::xvb::algorithm::algorithm;
+use crate::helper::xvb::endpoints::XvbEndpoints;
+use crate::helper::xvb::miners::restore_remote_miners;
+use crate::helper::xvb::planner::PlannerInputs;
 use crate::helper::xvb::priv_stats::XvbPrivStats;
 use crate::helper::xvb::public_stats::XvbPubStats;
 use crate::helper::{ProcessName, sleep_end_loop};
@@ -36,27 +40,56 @@ use tokio::spawn;
 use tokio::task::JoinHandle;
 use tokio::time::{Instant, sleep};
 
-use crate::helper::xvb::rounds::round_type;
+use crate::disk::xvb_history::{XvbHistory, XvbRoundLog};
+use crate::disk::xvb_remote_pools::XvbRemotePools;
+use crate::disk::xvb_samples::XvbSamples;
+use crate::helper::xvb::rounds::{RoundTracker, round_type};
 use crate::utils::constants::{XVB_PUBLIC_ONLY, XVB_TIME_ALGO};
 use crate::{
     helper::{ProcessSignal, ProcessState},
     utils::macros::sleep,
 };
 
-use self::nodes::Pool;
+use self::endpoints::XvbNode;
+use self::nodes::{Pool, PoolFailures};
 
+use super::fleet::PubFleetApi;
 use super::p2pool::{ImgP2pool, PubP2poolApi};
 use super::xrig::xmrig::{ImgXmrig, PubXmrigApi};
 use super::xrig::xmrig_proxy::{ImgProxy, PubXmrigProxyApi};
 use super::{Helper, Process};
 
 pub mod algorithm;
+pub mod endpoints;
+pub mod ledger;
+pub mod miners;
+#[cfg(any(test, feature = "mock-xvb"))]
+pub mod mock;
 pub mod nodes;
+pub mod planner;
 pub mod priv_stats;
 pub mod public_stats;
 pub mod rounds;
 
 impl Helper {
+    // Gupaxx is exiting or started without XvB, the watchdog of XvB will not send the remote miners back to their pool.
+    #[tokio::main]
+    pub async fn restore_remote_miners_without_xvb(helper: &Arc<Mutex<Self>>) {
+        let gui_api = Arc::clone(&helper.lock().unwrap().gui_api_xvb);
+        if gui_api.lock().unwrap().remote_pools.is_empty() {
+            return;
+        }
+        info!("XvB | Sending the remote miners back to their pool without XvB");
+        // do not make the exit hang on a miner that does not answer.
+        let client = client(None);
+        let restore = restore_remote_miners(&client, &gui_api);
+        if tokio::time::timeout(Duration::from_secs(10), restore)
+            .await
+            .is_err()
+        {
+            warn!("XvB | Some remote miners could not be sent back to their pool");
+        }
+    }
     // Just sets some signals for the watchdog thread to pick up on.
     pub fn stop_xvb(helper: &Arc<Mutex<Self>>) {
         info!("XvB | Attempting to stop...");
@@ -69,6 +102,7 @@ impl Helper {
         state_p2pool: &crate::disk::state::P2pool,
         state_xmrig: &crate::disk::state::Xmrig,
         state_xp: &crate::disk::state::XmrigProxy,
+        socks5: &crate::disk::state::Socks5,
     ) {
         info!("XvB | Attempting to restart...");
         helper.lock().unwrap().xvb.lock().unwrap().signal = ProcessSignal::Restart;
@@ -78,6 +112,7 @@ impl Helper {
         let state_p2pool = state_p2pool.clone();
         let state_xmrig = state_xmrig.clone();
         let state_xp = state_xp.clone();
+        let socks5 = socks5.clone();
         // This thread lives to wait, start xmrig then die.
         thread::spawn(move || {
             while helper.lock().unwrap().xvb.lock().unwrap().state != ProcessState::Waiting {
@@ -86,7 +121,14 @@ impl Helper {
             }
             // Ok, process is not alive, start the new one!
             info!("XvB | Old process seems dead, starting new one!");
-            Self::start_xvb(&helper, &state_xvb, &state_p2pool, &state_xmrig, &state_xp);
+            Self::start_xvb(
+                &helper,
+                &state_xvb,
+                &state_p2pool,
+                &state_xmrig,
+                &state_xp,
+                &socks5,
+            );
         });
         info!("XMRig | Restart ... OK");
     }
@@ -96,6 +138,7 @@ impl Helper {
         state_p2pool: &crate::disk::state::P2pool,
         state_xmrig: &crate::disk::state::Xmrig,
         state_xp: &crate::disk::state::XmrigProxy,
+        socks5: &crate::disk::state::Socks5,
     ) {
         // 1. Clone Arc value from Helper
         // pub for writing new values that will show up on UI after helper thread update. (every seconds.)
@@ -119,6 +162,10 @@ impl Helper {
         let img_xmrig = Arc::clone(&helper.lock().unwrap().img_xmrig);
         let img_proxy = Arc::clone(&helper.lock().unwrap().img_proxy);
         let img_p2pool = Arc::clone(&helper.lock().unwrap().img_p2pool);
+        let history = Arc::clone(&helper.lock().unwrap().xvb_history);
+        let samples = Arc::clone(&helper.lock().unwrap().xvb_samples);
+        let fleet = Arc::clone(&helper.lock().unwrap().fleet);
+        let xvb_endpoints = Arc::clone(&helper.lock().unwrap().xvb_endpoints);
 
         // Reset before printing to output.
         // Need to reset because values of stats would stay otherwise which could bring confusion even if panel is with a disabled theme.
@@ -127,6 +174,8 @@ impl Helper {
             "XvB | resetting pub and gui but keep current node as it is updated by xmrig console."
         );
         reset_data_xvb(&pub_api, &gui_api);
+        // the hashrate sent before a restart of Gupaxx is still part of the last hour.
+        restore_samples(&gui_api, &samples);
         // we reset the console output because it is complete start.
         gui_api.lock().unwrap().output.clear();
         // 2. Set process state
@@ -142,6 +191,8 @@ impl Helper {
         }
         // verify if token and address are existent on XvB server
 
+        // stats, XvB pools ping and XMRig API requests share the same client.
+        let socks5 = socks5.gupaxx();
         info!("XvB | spawn watchdog");
         thread::spawn(
             enc!((state_xvb, state_p2pool, state_xmrig, state_xmrig,state_xp, img_xmrig, img_proxy, img_p2pool) move || {
@@ -163,7 +214,11 @@ impl Helper {
                     &img_xmrig,
                     &img_proxy,
                     &img_p2pool,
-
+                    &history,
+                    &samples,
+                    &fleet,
+                    &xvb_endpoints,
+                    socks5.as_deref(),
                 );
             }),
         );
@@ -190,9 +245,15 @@ impl Helper {
         xmrig_img: &Arc<Mutex<ImgXmrig>>,
         proxy_img: &Arc<Mutex<ImgProxy>>,
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
+        history: &Arc<Mutex<XvbHistory>>,
+        samples: &Arc<Mutex<XvbSamples>>,
+        fleet: &Arc<Mutex<PubFleetApi>>,
+        xvb_endpoints: &Arc<Mutex<XvbEndpoints>>,
+        socks5: Option<&str>,
     ) {
         // create uniq client that is going to be used for during the life of the thread.
-        let client = client();
+        let client = client(socks5);
+        let endpoints = xvb_endpoints.lock().unwrap().clone();
         // checks confition to start XvB, will set proper state of XvB.
         // if state is middle (everything fine here),set which xvb node could be used.
         // should wait for it, because algo needs to not be started if at least one node of XvB are not responsive.
@@ -202,6 +263,7 @@ impl Helper {
         // in that case, a spawn would retry and change state if they are available again.
         check_conditions_for_start(
             &client,
+            &endpoints,
             gui_api,
             process_p2pool,
             process_xmrig,
@@ -228,6 +290,8 @@ impl Helper {
         let handle_algo = Arc::new(Mutex::new(None));
         let handle_request = Arc::new(Mutex::new(None));
         let mut msg_retry_done = false;
+        // the rounds of XvB are logged with the time donated during each of them.
+        let mut round_tracker = RoundTracker::default();
 
         // let's create the memory of last hour average sent to p2pool and XvB
         // tuple (p2pool, xvb)
@@ -287,8 +351,13 @@ impl Helper {
                     xmrig_img,
                     proxy_img,
                     p2pool_img,
+                    xvb_endpoints,
+                    socks5,
                 ) {
                     info!("XvB Watchdog | Signal has stopped the loop");
+                    // the algorithm must not send a remote miner to XvB while they are restored.
+                    stop_algorithm(&handle_algo).await;
+                    restore_remote_miners(&client, gui_api).await;
                     break;
                 }
                 // let handle_algo_c = handle_algo.lock().unwrap();
@@ -326,10 +395,10 @@ impl Helper {
                     // first_loop is false here but could be changed to true under some conditions.
                     // will send a stop signal if public stats failed or update data with new one.
                     *handle_request.lock().unwrap() = Some(spawn(
-                        enc!((client, pub_api, gui_api, gui_api_p2pool, gui_api_xmrig, gui_api_xp,  state_xvb, state_p2pool, state_xmrig,  process, last_algorithm, retry, handle_algo, time_donated, last_request, proxy_img, xmrig_img, process_p2pool, p2pool_img) async move {
+                        enc!((client, endpoints, pub_api, gui_api, gui_api_p2pool, gui_api_xmrig, gui_api_xp,  state_xvb, state_p2pool, state_xmrig,  process, last_algorithm, retry, handle_algo, time_donated, last_request, proxy_img, xmrig_img, process_p2pool, p2pool_img, samples, fleet) async move {
                                 // needs to wait here for public stats to get private stats.
                                 if last_request_expired || first_loop || should_refresh_before_next_algo {
-                                XvbPubStats::update_stats(&client, &gui_api, &pub_api, &process).await;
+                                XvbPubStats::update_stats(&client, &endpoints, &gui_api, &pub_api, &process).await;
                                     *last_request.lock().unwrap() = Instant::now();
                                 }
                                 // private stats needs valid token and address.
@@ -344,7 +413,7 @@ impl Helper {
                                     debug!("XvB Watchdog | Attempting HTTP private API request...");
                                     // reload private stats, it send a signal if error that will be captured on the upper thread.
                                     XvbPrivStats::update_stats(
-                                        &client, &state_p2pool.address, &state_xvb.token, &pub_api, &gui_api, &process,
+                                        &client, &endpoints, &state_p2pool.address, &state_xvb.token, &pub_api, &gui_api, &process,
                                     )
                                     .await;
                                     *last_request.lock().unwrap() = Instant::now();
@@ -369,7 +438,7 @@ impl Helper {
                                         *retry.lock().unwrap() = false;
                                         // reset instant because algo will start.
                                         *last_algorithm.lock().unwrap() = Instant::now();
-                                        *handle_algo.lock().unwrap() = Some(spawn(enc!((client, gui_api,  gui_api_xmrig, gui_api_xp, state_xmrig,  time_donated, state_xvb, proxy_img, xmrig_img, p2pool_img, process_p2pool) async move {
+                                        *handle_algo.lock().unwrap() = Some(spawn(enc!((client, gui_api,  gui_api_xmrig, gui_api_xp, state_xmrig,  time_donated, state_xvb, proxy_img, xmrig_img, p2pool_img, process_p2pool, samples, fleet) async move {
                         let rig = if xp_alive {
                             ""
                         } else {
@@ -391,7 +460,10 @@ impl Helper {
                                                 &proxy_img,
                                                 &xmrig_img,
                                                 &p2pool_img,
-                                                &process_p2pool
+                                                &process_p2pool,
+                                                &samples,
+                                                &fleet,
+                                                &state_xvb.controlled,
                                             ).await;
                                         })));
                                     } else {
@@ -436,6 +508,25 @@ impl Helper {
                     *time_donated.lock().unwrap(),
                     &last_algorithm,
                 );
+                // log the round that just ended
+                let mining_on_xvb =
+                    matches!(pub_api.lock().unwrap().current_pool, Some(Pool::XvB(_)));
+                let hashrate = current_controllable_hr(xp_alive, gui_api_xp, gui_api_xmrig);
+                round_tracker.tick(mining_on_xvb, hashrate, std::time::Instant::now());
+                let api = pub_api.lock().unwrap();
+                let round = round_tracker.check(
+                    &api.stats_pub,
+                    &api.stats_priv,
+                    &Helper::head_tail_of_monero_address(&state_p2pool.address),
+                    chrono::Utc::now().timestamp(),
+                );
+                drop(api);
+                // without private stats, the round can not be accounted.
+                if let Some(round) =
+                    round.filter(|_| process.lock().unwrap().state == ProcessState::Alive)
+                {
+                    log_round(gui_api, history, round);
+                }
                 // first_loop is done, but maybe retry will allow the algorithm to retry again.
                 if first_loop {
                     first_loop = false;
@@ -463,6 +554,16 @@ pub struct PubXvbApi {
     // Instead of watching stratum data that will account for HR sent only on this p2pool node,
     // Take the value of estimated HR that will account for external miners mininf on the same address.
     pub use_p2pool_sidechain_hr: bool,
+    // stratum probes of the XvB pools by name, done when choosing the pool.
+    pub probes: Vec<(String, ProbeResult)>,
+    // remote miners sent to XvB by the algorithm, with the pool to send them back to.
+    pub remote_pools: XvbRemotePools,
+    // inputs of the round planner given by the last cycle of the algorithm.
+    pub planner: Option<PlannerInputs>,
+    // next XvB pools of the last ranking, used one after the other when the pool in use fails.
+    pub failover: Vec<XvbNode>,
+    // recent failures of the XvB pools, ranking them after the others.
+    pub failures: PoolFailures,
 }
 #[derive(Debug, Clone)]
 pub struct SamplesAverageHour(BoundedVecDeque<f32>);
@@ -509,6 +610,10 @@ impl PubXvbApi {
                 &mut gui_api.p2pool_sent_last_hour_samples,
             ),
             xvb_sent_last_hour_samples: std::mem::take(&mut gui_api.xvb_sent_last_hour_samples),
+            remote_pools: std::mem::take(&mut gui_api.remote_pools),
+            planner: std::mem::take(&mut gui_api.planner),
+            failover: std::mem::take(&mut gui_api.failover),
+            failures: std::mem::take(&mut gui_api.failures),
             use_p2pool_sidechain_hr: std::mem::take(&mut gui_api.use_p2pool_sidechain_hr),
             ..pub_api.clone()
         };
@@ -517,6 +622,7 @@ impl PubXvbApi {
 #[allow(clippy::too_many_arguments)]
 async fn check_conditions_for_start(
     client: &Client,
+    endpoints: &XvbEndpoints,
     gui_api: &Arc<Mutex<PubXvbApi>>,
     process_p2pool: &Arc<Mutex<Process>>,
     process_xmrig: &Arc<Mutex<Process>>,
@@ -526,7 +632,7 @@ async fn check_conditions_for_start(
     state_xvb: &crate::disk::state::Xvb,
 ) {
     let state = if let Err(err) =
-        XvbPrivStats::request_api(client, &state_p2pool.address, &state_xvb.token).await
+        XvbPrivStats::request_api(client, endpoints, &state_p2pool.address, &state_xvb.token).await
     {
         info!("XvB | verify address and token");
         // send to console: token non existent for address on XvB server
@@ -670,7 +776,8 @@ async fn check_state_outcauses_xvb(
                                         &token_xmrig,
                                         &pool,
                                         &address,
-                                        &rig
+                                        &rig,
+                                        None,
                                     )
                                     .await
                                             {
@@ -701,6 +808,14 @@ async fn check_state_outcauses_xvb(
             }
         }
     }
+    // without the algorithm, the remote miners are not followed anymore.
+    // They go back to their pool like the local miner, also if the algorithm was between two cycles.
+    if state != ProcessState::Alive && !gui_api.lock().unwrap().remote_pools.is_empty() {
+        spawn(enc!((client, gui_api, handle_algo) async move {
+            stop_algorithm(&handle_algo).await;
+            restore_remote_miners(&client, &gui_api).await;
+        }));
+    }
     // if state of Xmrig-Proxy changed, go back to first loop
     if xp_start_alive != xp_is_alive {
         *first_loop = true;
@@ -773,6 +888,8 @@ fn signal_interrupt(
     xmrig_img: &Arc<Mutex<ImgXmrig>>,
     proxy_img: &Arc<Mutex<ImgProxy>>,
     p2pool_img: &Arc<Mutex<ImgP2pool>>,
+    xvb_endpoints: &Arc<Mutex<XvbEndpoints>>,
+    socks5: Option<&str>,
 ) -> bool {
     // Check SIGNAL
     // check if STOP or RESTART Signal is given.
@@ -835,19 +952,24 @@ fn signal_interrupt(
                 // so it won't execute another signal of update pools if it is already doing it.
                 process.lock().unwrap().state = ProcessState::Waiting;
                 process.lock().unwrap().signal = ProcessSignal::None;
+                // the pools are probed through the proxy
+                let socks5 = socks5.map(str::to_string);
                 spawn(
-                    enc!((pool, process, client, gui_api, pub_api, was_alive, address, token_xmrig, process_xrig, xmrig_img, proxy_img, process_p2pool, state_p2pool, p2pool_img, state_xvb) async move {
+                    enc!((pool, process, client, xvb_endpoints, socks5, gui_api, pub_api, was_alive, address, token_xmrig, process_xrig, xmrig_img, proxy_img, process_p2pool, state_p2pool, p2pool_img, state_xvb) async move {
                     match pool {
-                        Pool::XvBNorthAmerica|Pool::XvBEurope if was_alive => {
-                            // a pool is failing. We need to first verify if a pool is available
-                        Pool::update_fastest_pool(&client, &gui_api, &pub_api, &process, &process_p2pool, &p2pool_img, &state_p2pool, &state_xvb).await;
+                        Pool::XvB(_) if was_alive => {
+                            // the pool in use has failed, so the next one of the ranking is chosen.
+                            // the pools are probed again only when the ranking is exhausted.
+                            if !Pool::failover(&gui_api, &process) {
+                        Pool::update_fastest_pool(&client, &XvbEndpoints::refresh(&xvb_endpoints, &client).await, &pub_api, &gui_api, &process, &process_p2pool, &p2pool_img, &state_p2pool, &state_xvb, socks5.as_deref()).await;
+                            }
                             if process.lock().unwrap().state == ProcessState::OfflinePoolsAll {
                                 // No available pools, so launch a process to verify periodically.
                     sleep(Duration::from_secs(10)).await;
                     warn!("pool fail, set spawn that will retry pools and update state.");
                     while process.lock().unwrap().state == ProcessState::OfflinePoolsAll {
                         // this spawn will stay alive until pools are joignable or XvB process is stopped or failed.
-                        Pool::update_fastest_pool(&client, &pub_api, &gui_api, &process, &process_p2pool, &p2pool_img, &state_p2pool, &state_xvb).await;
+                        Pool::update_fastest_pool(&client, &XvbEndpoints::refresh(&xvb_endpoints, &client).await, &pub_api, &gui_api, &process, &process_p2pool, &p2pool_img, &state_p2pool, &state_xvb, socks5.as_deref()).await;
                         sleep(Duration::from_secs(10)).await;
                     }
                                 
@@ -856,11 +978,11 @@ fn signal_interrupt(
 
                             
                         },
-                        Pool::XvBNorthAmerica|Pool::XvBEurope if !was_alive => {
+                        Pool::XvB(_) if !was_alive => {
                         // Probably a start. We don't consider XMRig using XvB pools without algo.
                         // can update xmrig and check status of state in the same time.
                         // update prefred pool
-                        Pool::update_fastest_pool(&client, &pub_api, &gui_api, &process, &process_p2pool, &p2pool_img, &state_p2pool, &state_xvb).await;
+                        Pool::update_fastest_pool(&client, &XvbEndpoints::refresh(&xvb_endpoints, &client).await, &pub_api, &gui_api, &process, &process_p2pool, &p2pool_img, &state_p2pool, &state_xvb, socks5.as_deref()).await;
                         // Need to set XMRig to P2Pool if it wasn't. XMRig should have populated this value at his start.
                         // but if xmrig didn't start, don't update it.
                 
@@ -881,7 +1003,8 @@ fn signal_interrupt(
                     &token_xmrig,
                     &p2pool_pool,
                     &address,
-                    &rig
+                    &rig,
+                    None,
                 )
                 .await {
                                 let msg_xmrig_or_proxy = if xp_alive {
@@ -910,6 +1033,20 @@ fn signal_interrupt(
 
     false
 }
+// Stop the algorithm and wait for its end, a request to a miner can be running.
+async fn stop_algorithm(handle_algo: &Arc<Mutex<Option<JoinHandle<()>>>>) {
+    if let Some(handle) = handle_algo.lock().unwrap().as_ref() {
+        handle.abort();
+    }
+    while handle_algo
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|h| !h.is_finished())
+    {
+        sleep(Duration::from_millis(100)).await;
+    }
+}
 fn reset_data_xvb(pub_api: &Arc<Mutex<PubXvbApi>>, gui_api: &Arc<Mutex<PubXvbApi>>) {
     let current_pool = mem::take(&mut pub_api.lock().unwrap().current_pool.clone());
     // even if it is a restart, we want to keep set values by the user without the need from him to click on save button.
@@ -918,6 +1055,8 @@ fn reset_data_xvb(pub_api: &Arc<Mutex<PubXvbApi>>, gui_api: &Arc<Mutex<PubXvbApi
         mem::take(&mut gui_api.lock().unwrap().stats_priv.runtime_manual_amount);
     let use_sidechain_hr = mem::take(&mut gui_api.lock().unwrap().use_p2pool_sidechain_hr);
     let pool = mem::take(&mut gui_api.lock().unwrap().stats_priv.pool);
+    let remote_pools = mem::take(&mut gui_api.lock().unwrap().remote_pools);
+    let failures = mem::take(&mut gui_api.lock().unwrap().failures);
     // let output = mem::take(&mut gui_api.lock().unwrap().output);
     *pub_api.lock().unwrap() = PubXvbApi::new();
     *gui_api.lock().unwrap() = PubXvbApi::new();
@@ -932,6 +1071,48 @@ fn reset_data_xvb(pub_api: &Arc<Mutex<PubXvbApi>>, gui_api: &Arc<Mutex<PubXvbApi
     // pub_api.lock().unwrap().output = output;
     // to not lose information about the use of sidechain hr
     gui_api.lock().unwrap().use_p2pool_sidechain_hr = use_sidechain_hr;
+    // remote miners on XvB must still be sent back to their pool
+    gui_api.lock().unwrap().remote_pools = remote_pools;
+    // a pool which failed before the restart is still ranked after the others
+    gui_api.lock().unwrap().failures = failures;
+}
+fn restore_samples(gui_api: &Arc<Mutex<PubXvbApi>>, samples: &Arc<Mutex<XvbSamples>>) {
+    let mut samples = samples.lock().unwrap();
+    let now = chrono::Utc::now().timestamp();
+    samples.close_cycle(now);
+    if let Err(e) = samples.save() {
+        warn!("XvB | Could not save the samples: {}", e);
+    }
+    let slots = samples.slots(now);
+    let mut gui_api = gui_api.lock().unwrap();
+    for (p2pool, xvb) in slots {
+        gui_api.p2pool_sent_last_hour_samples.0.push_back(p2pool);
+        gui_api.xvb_sent_last_hour_samples.0.push_back(xvb);
+    }
+}
+fn log_round(
+    gui_api: &Arc<Mutex<PubXvbApi>>,
+    history: &Arc<Mutex<XvbHistory>>,
+    round: XvbRoundLog,
+) {
+    let msg = match (&round.round, round.won) {
+        (_, true) => format!(
+            "Round ended, you won the raffle at block {}!",
+            round.block_height
+        ),
+        (Some(r), false) => format!(
+            "Round ended, you participated as {} and the winner is {}",
+            r, round.winner
+        ),
+        (None, false) => format!(
+            "Round ended without your participation, the winner is {}",
+            round.winner
+        ),
+    };
+    output_console(&mut gui_api.lock().unwrap().output, &msg, ProcessName::Xvb);
+    if let Err(e) = history.lock().unwrap().append(round) {
+        warn!("XvB | Could not save the round: {}", e);
+    }
 }
 // print date time to console output in same format than xmrig
 fn update_indicator_algo(
@@ -998,3 +1179,16 @@ fn current_controllable_hr(
         gui_api_xmrig.lock().unwrap().hashrate_raw
     }
 }
+
+// difficulty of the shares accepted since the start of XMRig or XMRig-Proxy, if it gives it.
+fn current_accepted_difficulty(
+    xp_alive: bool,
+    gui_api_xp: &Arc<Mutex<PubXmrigProxyApi>>,
+    gui_api_xmrig: &Arc<Mutex<PubXmrigApi>>,
+) -> Option<u64> {
+    if xp_alive {
+        gui_api_xp.lock().unwrap().hashes_total
+    } else {
+        gui_api_xmrig.lock().unwrap().hashes_total
+    }
+}
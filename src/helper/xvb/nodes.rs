@@ -16,6 +16,7 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use std::{
+    collections::HashMap,
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
@@ -23,24 +24,34 @@ use std::{
 use derive_more::Display;
 use log::{error, info, warn};
 use reqwest_middleware::ClientWithMiddleware as Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tokio::spawn;
 
 use crate::{
-    GUPAX_VERSION_UNDERSCORE, XVB_NODE_EU, XVB_NODE_NA, XVB_NODE_PORT, XVB_NODE_RPC,
-    components::node::{GetInfo, TIMEOUT_NODE_PING},
+    GUPAX_VERSION_UNDERSCORE,
+    components::{
+        node::GetInfo,
+        stratum::{PROBE_TIMEOUT, probe_stratum},
+    },
     disk::state::{P2pool, Xvb},
     helper::{Process, ProcessName, ProcessState, p2pool::ImgP2pool, xvb::output_console},
 };
 
-use super::PubXvbApi;
-#[derive(Clone, Debug, Default, PartialEq, Display, Deserialize)]
+use super::{
+    PubXvbApi,
+    endpoints::{XvbEndpoints, XvbNode},
+};
+// A pool which failed is ranked after the others for this duration.
+pub const XVB_POOL_FAILURE_WINDOW: Duration = Duration::from_secs(3600);
+// latency added to the score of the probe for each failure in the window, in ms.
+// A probe never scores more than its timeout, so a pool which failed is never picked before
+// one which answered.
+pub const XVB_POOL_FAILURE_PENALTY: u128 = PROBE_TIMEOUT.as_millis();
+
+#[derive(Clone, Debug, PartialEq, Display, Serialize, Deserialize)]
 pub enum Pool {
-    #[display("XvB North America Pool")]
-    XvBNorthAmerica,
-    #[default]
-    #[display("XvB European Pool")]
-    XvBEurope,
+    #[display("XvB {} Pool", _0.name)]
+    XvB(Box<XvbNode>),
     #[display("Local P2pool")]
     P2pool(u16),
     #[display("Xmrig Proxy")]
@@ -50,11 +61,25 @@ pub enum Pool {
     #[display("Not connected to any pool")]
     Unknown,
 }
+// The preferred XvB pool, before any ping.
+impl Default for Pool {
+    fn default() -> Self {
+        Self::xvb(
+            XvbEndpoints::default()
+                .nodes
+                .into_iter()
+                .next()
+                .unwrap_or_default(),
+        )
+    }
+}
 impl Pool {
+    pub fn xvb(node: XvbNode) -> Self {
+        Self::XvB(Box::new(node))
+    }
     pub fn url(&self) -> String {
         match self {
-            Self::XvBNorthAmerica => String::from(XVB_NODE_NA),
-            Self::XvBEurope => String::from(XVB_NODE_EU),
+            Self::XvB(node) => node.host.clone(),
             Self::P2pool(_) => String::from("127.0.0.1"),
             Self::XmrigProxy(_) => String::from("127.0.0.1"),
             Self::Custom(url, _) => url.clone(),
@@ -63,7 +88,7 @@ impl Pool {
     }
     pub fn port(&self) -> String {
         match self {
-            Self::XvBNorthAmerica | Self::XvBEurope => String::from(XVB_NODE_PORT),
+            Self::XvB(node) => node.port.clone(),
             Self::P2pool(port) => port.to_string(),
             Self::XmrigProxy(port) => port.to_string(),
             Self::Custom(_, port) => port.to_string(),
@@ -72,15 +97,13 @@ impl Pool {
     }
     pub fn user(&self, address: &str) -> String {
         match self {
-            Self::XvBNorthAmerica => address.chars().take(8).collect(),
-            Self::XvBEurope => address.chars().take(8).collect(),
+            Self::XvB(_) => address.chars().take(8).collect(),
             _ => GUPAX_VERSION_UNDERSCORE.to_string(),
         }
     }
     pub fn tls(&self) -> bool {
         match self {
-            Self::XvBNorthAmerica => true,
-            Self::XvBEurope => true,
+            Self::XvB(node) => node.tls,
             Self::P2pool(_) => false,
             Self::XmrigProxy(_) => false,
             Self::Custom(_, _) => false,
@@ -89,18 +112,62 @@ impl Pool {
     }
     pub fn keepalive(&self) -> bool {
         match self {
-            Self::XvBNorthAmerica => true,
-            Self::XvBEurope => true,
+            Self::XvB(_) => true,
             Self::P2pool(_) => false,
             Self::XmrigProxy(_) => false,
             Self::Custom(_, _) => false,
             _ => false,
         }
     }
+    pub fn is_xvb(&self) -> bool {
+        matches!(self, Self::XvB(_))
+    }
+    // The XvB pool of the list with this address.
+    pub fn xvb_from_address(address: &str, endpoints: &XvbEndpoints) -> Option<Self> {
+        endpoints
+            .nodes
+            .iter()
+            .find(|n| n.address() == address)
+            .cloned()
+            .map(Self::xvb)
+    }
+    // Remember that a XvB pool failed, so that it is ranked after the others.
+    pub fn record_failure(&self, failures: &mut PoolFailures) {
+        if let Self::XvB(node) = self {
+            warn!("XvB | {} has failed", self);
+            failures.record(node, Instant::now());
+        }
+    }
+    // The pool in use has failed, the next one of the last ranking is used.
+    // Returns false if there is no next pool, they must be probed again.
+    pub fn failover(
+        gui_api_xvb: &Arc<Mutex<PubXvbApi>>,
+        process_xvb: &Arc<Mutex<Process>>,
+    ) -> bool {
+        let mut api = gui_api_xvb.lock().unwrap();
+        let failed = api.stats_priv.pool.clone();
+        failed.record_failure(&mut api.failures);
+        if api.failover.is_empty() {
+            return false;
+        }
+        let next = Pool::xvb(api.failover.remove(0));
+        info!("XvB | {} has failed, switching to {}", failed, next);
+        output_console(
+            &mut api.output,
+            &format!("{} has failed, switching to {}", failed, next),
+            ProcessName::Xvb,
+        );
+        api.stats_priv.pool = next;
+        drop(api);
+        // next iteration of the loop of XvB process will verify if all conditions are met to be alive.
+        process_xvb.lock().unwrap().state = ProcessState::Syncing;
+        true
+    }
 
     #[allow(clippy::too_many_arguments)]
     pub async fn update_fastest_pool(
         client: &Client,
+        endpoints: &XvbEndpoints,
         pub_api_xvb: &Arc<Mutex<PubXvbApi>>,
         gui_api_xvb: &Arc<Mutex<PubXvbApi>>,
         process_xvb: &Arc<Mutex<Process>>,
@@ -108,79 +175,95 @@ impl Pool {
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         p2pool_state: &P2pool,
         xvb_state: &Xvb,
+        socks5: Option<&str>,
     ) {
-        if xvb_state.manual_pool_enabled {
-            let manual_pool = if xvb_state.manual_pool_eu {
-                Pool::XvBEurope
-            } else {
-                Pool::XvBNorthAmerica
-            };
+        let manual_pool = endpoints
+            .nodes
+            .iter()
+            .find(|n| n.name == xvb_state.manual_pool)
+            .cloned()
+            .map(Pool::xvb);
+        if xvb_state.manual_pool_enabled && manual_pool.is_none() {
+            warn!(
+                "XvB | manually chosen pool {} is not in the list, choosing the fastest",
+                xvb_state.manual_pool
+            );
+        }
+        if let Some(manual_pool) = manual_pool.filter(|_| xvb_state.manual_pool_enabled) {
             info!("XvB node {} has been chosen manually", manual_pool.url());
             output_console(
                 &mut gui_api_xvb.lock().unwrap().output,
                 &format!("XvB node {} has been chosen manually", manual_pool),
                 ProcessName::Xvb,
             );
-            gui_api_xvb.lock().unwrap().stats_priv.pool = manual_pool;
+            let mut api = gui_api_xvb.lock().unwrap();
+            api.stats_priv.pool = manual_pool;
+            // the manual pool is probed again if it fails
+            api.failover.clear();
+            drop(api);
             if process_xvb.lock().unwrap().state != ProcessState::Syncing {
                 process_xvb.lock().unwrap().state = ProcessState::Syncing;
             }
             return;
         }
-        let client_eu = client.clone();
-        let client_na = client.clone();
-        // two spawn to ping the two nodes in parallel and not one after the other.
-        let ms_eu = spawn(async move {
-            info!("Node | ping XvBEuropean XvB Node");
-            Pool::ping(&Pool::XvBEurope.url(), &client_eu).await
-        });
-        let ms_na = spawn(async move {
-            info!("Node | ping North America Node");
-            Pool::ping(&Pool::XvBNorthAmerica.url(), &client_na).await
-        });
-        let pool = if let Ok(ms_eu) = ms_eu.await {
-            if let Ok(ms_na) = ms_na.await {
-                // if two nodes are up, compare ping latency and return fastest.
-                if ms_na != TIMEOUT_NODE_PING && ms_eu != TIMEOUT_NODE_PING {
-                    if ms_na < ms_eu {
-                        Pool::XvBNorthAmerica
-                    } else {
-                        Pool::XvBEurope
-                    }
-                } else if ms_na != TIMEOUT_NODE_PING && ms_eu == TIMEOUT_NODE_PING {
-                    // if only na is online, return it.
-                    Pool::XvBNorthAmerica
-                } else if ms_na == TIMEOUT_NODE_PING && ms_eu != TIMEOUT_NODE_PING {
-                    // if only eu is online, return it.
-                    Pool::XvBEurope
-                } else {
-                    // if P2pool is returned, it means none of the two nodes are available.
-                    Pool::P2pool(p2pool_state.current_port(
-                        process_p2pool.lock().unwrap().is_alive(),
-                        &p2pool_img.lock().unwrap(),
-                    ))
-                }
-            } else {
-                error!("ping has failed !");
-                Pool::P2pool(p2pool_state.current_port(
-                    process_p2pool.lock().unwrap().is_alive(),
-                    &p2pool_img.lock().unwrap(),
-                ))
+        // one spawn per node to probe them in parallel and not one after the other.
+        let handles: Vec<_> = endpoints
+            .nodes
+            .iter()
+            .cloned()
+            .map(|node| {
+                let client = client.clone();
+                let socks5 = socks5.map(str::to_string);
+                spawn(async move {
+                    info!("Node | stratum probe of {} XvB Node", node.name);
+                    let probe =
+                        match probe_stratum(&node.host, &node.port, node.tls, socks5.as_deref())
+                            .await
+                        {
+                            // a pool whose node is not synchronized can not give valid jobs
+                            Ok(probe) => Pool::synchronized(&node, &client).await.map(|_| probe),
+                            Err(e) => Err(e.to_string()),
+                        };
+                    (node, probe)
+                })
+            })
+            .collect();
+        let mut probes = Vec::with_capacity(handles.len());
+        for handle in handles {
+            match handle.await {
+                Ok(probe) => probes.push(probe),
+                Err(e) => error!("stratum probe has failed ! {e}"),
             }
-        } else {
-            error!("ping has failed !");
-            Pool::P2pool(p2pool_state.current_port(
-                process_p2pool.lock().unwrap().is_alive(),
-                &p2pool_img.lock().unwrap(),
-            ))
+        }
+        let results: Vec<_> = probes
+            .iter()
+            .map(|(node, probe)| (node.name.clone(), probe.clone()))
+            .collect();
+        pub_api_xvb.lock().unwrap().probes.clone_from(&results);
+        gui_api_xvb.lock().unwrap().probes = results;
+        let now = Instant::now();
+        let ranked = {
+            let failures = &mut gui_api_xvb.lock().unwrap().failures;
+            let scores: Vec<_> = probes
+                .into_iter()
+                .map(|(node, probe)| {
+                    if let Err(e) = &probe {
+                        warn!("XvB | stratum probe of {} failed: {}", node.name, e);
+                        failures.record(&node, now);
+                    }
+                    (node, probe.ok().map(|p| p.score()))
+                })
+                .collect();
+            rank_nodes(scores, failures, now)
         };
-        if pool
-            == Pool::P2pool(p2pool_state.current_port(
-                process_p2pool.lock().unwrap().is_alive(),
-                &p2pool_img.lock().unwrap(),
-            ))
-        {
-            // if both nodes are dead, then the state of the process must be NodesOffline
+        let p2pool_pool = Pool::P2pool(p2pool_state.current_port(
+            process_p2pool.lock().unwrap().is_alive(),
+            &p2pool_img.lock().unwrap(),
+        ));
+        // the first pool of the ranking is used, the next ones are used if it fails.
+        let pool = ranked.first().cloned().map_or(p2pool_pool, Pool::xvb);
+        if !pool.is_xvb() {
+            // if all nodes are dead, then the state of the process must be NodesOffline
             info!("XvB node ping, all offline or ping failed, switching back to local p2pool",);
             output_console(
                 &mut gui_api_xvb.lock().unwrap().output,
@@ -190,10 +273,22 @@ impl Pool {
             process_xvb.lock().unwrap().state = ProcessState::OfflinePoolsAll;
         } else {
             // if node is up and because update_fastest is used only if token/address is valid, it means XvB process is Alive.
-            info!("XvB node ping, both online and best is {}", pool.url());
+            info!(
+                "XvB node ping, {} online and best is {}",
+                ranked.len(),
+                pool.url()
+            );
             output_console(
                 &mut gui_api_xvb.lock().unwrap().output,
-                &format!("XvB Pool ping, {} is selected as the fastest.", pool),
+                &format!(
+                    "XvB Pool ping, {} is selected as the fastest.\nOrder of failover: {}",
+                    pool,
+                    ranked
+                        .iter()
+                        .map(|n| n.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
                 ProcessName::Xvb,
             );
             info!("ProcessState to Syncing after finding joinable node");
@@ -204,51 +299,79 @@ impl Pool {
                 process_xvb.lock().unwrap().state = ProcessState::Syncing;
             }
         }
-        pub_api_xvb.lock().unwrap().stats_priv.pool = pool;
+        // the pool is kept by the GUI API, which is not overwritten by the public one.
+        let mut api = gui_api_xvb.lock().unwrap();
+        api.stats_priv.pool = pool;
+        api.failover = ranked.into_iter().skip(1).collect();
     }
-    async fn ping(ip: &str, client: &Client) -> u128 {
+}
+
+impl Pool {
+    // Check with the RPC of its node that the pool is on mainnet and synchronized.
+    async fn synchronized(node: &XvbNode, client: &Client) -> Result<(), String> {
+        let Some(rpc_port) = &node.rpc_port else {
+            return Ok(());
+        };
+        let host = if node.host.contains(':') && !node.host.starts_with('[') {
+            format!("[{}]", node.host)
+        } else {
+            node.host.clone()
+        };
         let request = client
-            .post("http://".to_string() + ip + ":" + XVB_NODE_RPC + "/json_rpc")
-            .body(r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#);
-        let mut vec_ms = vec![];
-        for _ in 0..6 {
-            // clone request
-            let req = request
-                .try_clone()
-                .expect("should be able to clone a str body");
-            // begin timer
-            let now_req = Instant::now();
-            // get and store time of request
-            vec_ms.push(match tokio::time::timeout(Duration::from_millis(TIMEOUT_NODE_PING as u64), req.send()).await {
-            Ok(Ok(json_rpc)) => {
-                // Attempt to convert to JSON-RPC.
-                match json_rpc.bytes().await {
-                    Ok(b) => match serde_json::from_slice::<GetInfo<'_>>(&b) {
-                        Ok(rpc) => {
-                            if rpc.result.mainnet && rpc.result.synchronized {
-                                now_req.elapsed().as_millis()
-                            } else {
-                                warn!("Ping | {ip} responded with valid get_info but is not in sync, remove this node!");
-                                TIMEOUT_NODE_PING
-                            }
-                        }
-                        _ => {
-                            warn!("Ping | {ip} responded but with invalid get_info, remove this node!");
-                            TIMEOUT_NODE_PING
-                        }
-                    },
-                    _ => TIMEOUT_NODE_PING,
-                }
+            .post(["http://", &host, ":", rpc_port, "/json_rpc"].concat())
+            .body(r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#)
+            .send();
+        let bytes = match tokio::time::timeout(PROBE_TIMEOUT, request).await {
+            Ok(Ok(response)) => response.bytes().await.map_err(|e| e.to_string())?,
+            Ok(Err(e)) => return Err(format!("no answer of the node: {e}")),
+            Err(_) => return Err("no answer of the node".to_string()),
+        };
+        match serde_json::from_slice::<GetInfo<'_>>(&bytes) {
+            Ok(rpc) if rpc.result.mainnet && rpc.result.synchronized => Ok(()),
+            Ok(_) => {
+                warn!("XvB | the node of {} is not synchronized", node.name);
+                Err("node not synchronized".to_string())
             }
-            _ => TIMEOUT_NODE_PING,
-        });
+            Err(_) => Err("invalid answer of the node".to_string()),
         }
-        let ms = *vec_ms
-            .iter()
-            .min()
-            .expect("at least the value of timeout should be present");
-        info!("Ping | {ms}ms ... {ip}");
-        info!("{:?}", vec_ms);
-        ms
     }
 }
+
+// Failures of the XvB pools, by name.
+#[derive(Clone, Debug, Default)]
+pub struct PoolFailures(HashMap<String, Vec<Instant>>);
+
+impl PoolFailures {
+    pub fn record(&mut self, node: &XvbNode, now: Instant) {
+        let failures = self.0.entry(node.name.clone()).or_default();
+        failures.retain(|t| now.duration_since(*t) < XVB_POOL_FAILURE_WINDOW);
+        failures.push(now);
+    }
+    // number of failures in the window
+    pub fn recent(&self, node: &XvbNode, now: Instant) -> usize {
+        self.0.get(&node.name).map_or(0, |f| {
+            f.iter()
+                .filter(|t| now.duration_since(**t) < XVB_POOL_FAILURE_WINDOW)
+                .count()
+        })
+    }
+}
+
+// Order of the online pools by the score of their stratum probe (None if offline),
+// each recent failure adding a penalty. Pools with the same score keep the order of the list.
+pub fn rank_nodes(
+    scores: Vec<(XvbNode, Option<u128>)>,
+    failures: &PoolFailures,
+    now: Instant,
+) -> Vec<XvbNode> {
+    let mut online: Vec<_> = scores
+        .into_iter()
+        .filter_map(|(node, ms)| ms.map(|ms| (node, ms)))
+        .map(|(node, ms)| {
+            let score = ms + failures.recent(&node, now) as u128 * XVB_POOL_FAILURE_PENALTY;
+            (node, score)
+        })
+        .collect();
+    online.sort_by_key(|(_, score)| *score);
+    online.into_iter().map(|(node, _)| node).collect()
+}
@@ -0,0 +1,114 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Hashrate needed to reach each round type of XvB.
+// The inputs are published by the algorithm at every cycle, so the donation and the
+// hashrate kept for the P2Pool share are the ones Auto mode uses.
+// The time to reach a round type supposes the averages of XvB are moving averages and that
+// all the spareable hashrate is sent to XvB until the 24h average is reached, like Auto mode does.
+
+use crate::{
+    XVB_ROUND_DONOR_MEGA_MIN_HR, XVB_ROUND_DONOR_MIN_HR, XVB_ROUND_DONOR_VIP_MIN_HR,
+    XVB_ROUND_DONOR_WHALE_MIN_HR, XVB_SIDE_MARGIN_1H,
+};
+
+use super::rounds::XvbRound;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PlannerInputs {
+    // hashrate controlled by the algorithm, remote miners included, in H/s
+    pub hashrate: f32,
+    // minimum hashrate to keep a share in the PPLNS window
+    pub share_min_hashrate: f32,
+    // hashrate donated to XvB by other miners of the address
+    pub xvb_external_hashrate: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TierPlan {
+    pub round: XvbRound,
+    // minimum average hashrate of the round type, in H/s
+    pub tier_hashrate: f32,
+    // hashrate to send to XvB on average, in H/s
+    pub donation: f32,
+    // part of the hashrate sent to XvB, None if there is no hashrate
+    pub fraction: Option<f32>,
+    // hashrate left to P2Pool divided by the minimum to keep a share, None if no hashrate is needed
+    pub coverage: Option<f32>,
+    // hours before the round type is given by XvB, None if it can not be reached
+    pub hours: Option<f32>,
+}
+
+impl TierPlan {
+    // The donation does not make the share in the PPLNS window lost.
+    pub fn keeps_share(&self) -> bool {
+        self.coverage.is_none_or(|c| c >= 1.0)
+    }
+}
+
+const PLANNED_ROUNDS: [(XvbRound, u32); 4] = [
+    (XvbRound::Donor, XVB_ROUND_DONOR_MIN_HR),
+    (XvbRound::DonorVip, XVB_ROUND_DONOR_VIP_MIN_HR),
+    (XvbRound::DonorWhale, XVB_ROUND_DONOR_WHALE_MIN_HR),
+    (XvbRound::DonorMega, XVB_ROUND_DONOR_MEGA_MIN_HR),
+];
+
+// Averages of XvB are given in H/s.
+pub fn plan_tiers(inputs: &PlannerInputs, xvb_1h_avg: f32, xvb_24h_avg: f32) -> Vec<TierPlan> {
+    // hashrate sent to XvB while the 24h average is not reached
+    let max_rate =
+        (inputs.hashrate - inputs.share_min_hashrate).max(0.0) + inputs.xvb_external_hashrate;
+    PLANNED_ROUNDS
+        .iter()
+        .map(|(round, min_hr)| {
+            let tier_hashrate = *min_hr as f32;
+            // same as the target of Auto mode, external hashrate already counts
+            let donation = (tier_hashrate - inputs.xvb_external_hashrate).max(0.0);
+            let fraction = (inputs.hashrate > 0.0).then(|| donation / inputs.hashrate);
+            let coverage = (inputs.share_min_hashrate > 0.0)
+                .then(|| (inputs.hashrate - donation).max(0.0) / inputs.share_min_hashrate);
+            // the 1h average is given a margin by XvB
+            let hours =
+                hours_to_reach(xvb_24h_avg, max_rate, tier_hashrate, 24.0).zip(hours_to_reach(
+                    xvb_1h_avg,
+                    max_rate,
+                    tier_hashrate / XVB_SIDE_MARGIN_1H,
+                    1.0,
+                ));
+            TierPlan {
+                round: round.clone(),
+                tier_hashrate,
+                donation,
+                fraction,
+                coverage,
+                hours: hours.map(|(h24, h1)| h24.max(h1)),
+            }
+        })
+        .collect()
+}
+
+// Hours for an average over [window] hours to reach [target] when [rate] is sent,
+// the hashrate sent before is supposed to be constant at the current average.
+fn hours_to_reach(avg: f32, rate: f32, target: f32, window: f32) -> Option<f32> {
+    if avg >= target {
+        Some(0.0)
+    } else if rate < target {
+        None
+    } else {
+        Some(window * (target - avg) / (rate - avg))
+    }
+}
@@ -31,12 +31,11 @@ use crate::{
     XVB_ROUND_DONOR_WHALE_MIN_HR, disk::state::XvbMode,
 };
 use crate::{
-    XVB_URL,
     disk::state::ManualDonationLevel,
     helper::{Process, ProcessName, ProcessState, xvb::output_console},
 };
 
-use super::{PubXvbApi, nodes::Pool, rounds::XvbRound};
+use super::{PubXvbApi, endpoints::XvbEndpoints, nodes::Pool, rounds::XvbRound};
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Default)]
 pub enum RuntimeMode {
@@ -95,12 +94,17 @@ pub struct XvbPrivStats {
 }
 
 impl XvbPrivStats {
-    pub async fn request_api(client: &Client, address: &str, token: &str) -> anyhow::Result<Self> {
+    pub async fn request_api(
+        client: &Client,
+        endpoints: &XvbEndpoints,
+        address: &str,
+        token: &str,
+    ) -> anyhow::Result<Self> {
         let resp = client
             .get(
                 [
-                    XVB_URL,
-                    "/cgi-bin/p2pool_bonus_history_gupaxx_api.cgi?address=",
+                    &endpoints.private_api,
+                    "?address=",
                     address,
                     "&token=",
                     token,
@@ -132,13 +136,14 @@ impl XvbPrivStats {
     }
     pub async fn update_stats(
         client: &Client,
+        endpoints: &XvbEndpoints,
         address: &str,
         token: &str,
         pub_api: &Arc<Mutex<PubXvbApi>>,
         gui_api: &Arc<Mutex<PubXvbApi>>,
         process: &Arc<Mutex<Process>>,
     ) {
-        match XvbPrivStats::request_api(client, address, token).await {
+        match XvbPrivStats::request_api(client, endpoints, address, token).await {
             Ok(new_data) => {
                 debug!("XvB Watchdog | HTTP API request OK");
                 pub_api.lock().unwrap().stats_priv.fails = new_data.fails;
@@ -159,7 +164,7 @@ impl XvbPrivStats {
             Err(err) => {
                 warn!(
                     "XvB Watchdog | Could not send HTTP private API request to: {}\n:{}",
-                    XVB_URL, err
+                    endpoints.private_api, err
                 );
                 if process.lock().unwrap().state != ProcessState::Failed {
                     output_console(
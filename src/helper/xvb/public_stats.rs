@@ -25,12 +25,9 @@ use reqwest_middleware::ClientWithMiddleware as Client;
 use serde::Deserialize;
 use serde_this_or_that::as_u64;
 
-use crate::{
-    XVB_URL_PUBLIC_API,
-    helper::{Process, ProcessName, ProcessState, xvb::output_console},
-};
+use crate::helper::{Process, ProcessName, ProcessState, xvb::output_console};
 
-use super::{PubXvbApi, rounds::XvbRound};
+use super::{PubXvbApi, endpoints::XvbEndpoints, rounds::XvbRound};
 
 #[allow(dead_code)] // because deserialize doesn't use all the fields
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -60,9 +57,10 @@ impl XvbPubStats {
     // Send an HTTP request to XvB's API, serialize it into [Self] and return it
     pub(in crate::helper) async fn request_api(
         client: &Client,
+        endpoints: &XvbEndpoints,
     ) -> std::result::Result<Self, anyhow::Error> {
         Ok(client
-            .get(XVB_URL_PUBLIC_API)
+            .get(&endpoints.public_api)
             .timeout(Duration::from_secs(10))
             .send()
             .await?
@@ -71,12 +69,13 @@ impl XvbPubStats {
     }
     pub async fn update_stats(
         client: &Client,
+        endpoints: &XvbEndpoints,
         gui_api: &Arc<Mutex<PubXvbApi>>,
         pub_api: &Arc<Mutex<PubXvbApi>>,
         process: &Arc<Mutex<Process>>,
     ) {
         debug!("XvB Watchdog | Attempting HTTP public API request...");
-        match XvbPubStats::request_api(client).await {
+        match XvbPubStats::request_api(client, endpoints).await {
             Ok(new_data) => {
                 debug!("XvB Watchdog | HTTP API request OK");
                 pub_api.lock().unwrap().stats_pub = new_data;
@@ -94,7 +93,7 @@ impl XvbPubStats {
             Err(err) => {
                 warn!(
                     "XvB Watchdog | Could not send HTTP API request to: {} even after multiples tries\n:{}",
-                    XVB_URL_PUBLIC_API, err
+                    endpoints.public_api, err
                 );
                 // output the error to console
                 // if error already present, no need to print it multiple times.
@@ -102,7 +101,7 @@ impl XvbPubStats {
                     &mut gui_api.lock().unwrap().output,
                     &format!(
                         "Failure to retrieve public stats from {}\nWill retry shortly...",
-                        XVB_URL_PUBLIC_API
+                        endpoints.public_api
                     ),
                     ProcessName::Xvb,
                 );
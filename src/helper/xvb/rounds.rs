@@ -15,18 +15,23 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::sync::{Arc, Mutex};
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use derive_more::Display;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     XVB_ROUND_DONOR_MEGA_MIN_HR, XVB_ROUND_DONOR_MIN_HR, XVB_ROUND_DONOR_VIP_MIN_HR,
     XVB_ROUND_DONOR_WHALE_MIN_HR, XVB_SIDE_MARGIN_1H,
 };
 
-use super::PubXvbApi;
-#[derive(Debug, Clone, Default, Display, Deserialize, PartialEq)]
+use crate::disk::xvb_history::XvbRoundLog;
+
+use super::{PubXvbApi, priv_stats::XvbPrivStats, public_stats::XvbPubStats};
+#[derive(Debug, Clone, Default, Display, Serialize, Deserialize, PartialEq)]
 pub enum XvbRound {
     #[default]
     #[display("VIP")]
@@ -70,3 +75,65 @@ pub(crate) fn round_type(share: u32, pub_api: &Arc<Mutex<PubXvbApi>>) -> Option<
         None
     }
 }
+
+// Watch the end of the XvB rounds and account the hashrate donated during each of them.
+// A round ends when the public API gives a new raffle (block height and rolls).
+#[derive(Debug, Default)]
+pub struct RoundTracker {
+    last_raffle: Option<(u64, u64, u64)>,
+    last_tick: Option<Instant>,
+    donated_secs: f64,
+    donated_hashes: f64,
+}
+
+impl RoundTracker {
+    // Called every loop of the watchdog with the pool currently mined and the controllable hashrate.
+    pub fn tick(&mut self, mining_on_xvb: bool, hashrate: f32, now: Instant) {
+        // a long pause of the watchdog must not be counted as donated time
+        let elapsed = self
+            .last_tick
+            .map_or(0.0, |t| now.duration_since(t).as_secs_f64().min(10.0));
+        self.last_tick = Some(now);
+        if mining_on_xvb {
+            self.donated_secs += elapsed;
+            self.donated_hashes += elapsed * hashrate as f64;
+        }
+    }
+    // The log of the round that just ended, if the raffle changed.
+    // [address] is the shortened address given by the public API for the winner.
+    pub fn check(
+        &mut self,
+        stats_pub: &XvbPubStats,
+        stats_priv: &XvbPrivStats,
+        address: &str,
+        timestamp: i64,
+    ) -> Option<XvbRoundLog> {
+        // no public stats yet
+        if stats_pub.block_height == 0 {
+            return None;
+        }
+        let raffle = (
+            stats_pub.block_height,
+            stats_pub.roll_winner,
+            stats_pub.roll_round,
+        );
+        let previous = self.last_raffle.replace(raffle)?;
+        if previous == raffle {
+            return None;
+        }
+        let log = XvbRoundLog {
+            timestamp,
+            block_height: stats_pub.block_height,
+            round: stats_priv.round_participate.clone(),
+            donor_1hr_avg: stats_priv.donor_1hr_avg,
+            donor_24hr_avg: stats_priv.donor_24hr_avg,
+            donated_secs: self.donated_secs as u32,
+            donated_hr_hours: self.donated_hashes / 3600.0,
+            winner: stats_pub.winner.clone(),
+            won: stats_pub.winner == address,
+        };
+        self.donated_secs = 0.0;
+        self.donated_hashes = 0.0;
+        Some(log)
+    }
+}
@@ -2,6 +2,7 @@
 use crate::components::update::Update;
 use crate::components::update::check_binary_path;
 use crate::errors::process_running;
+use crate::helper::xvb::endpoints::XvbEndpoints;
 use crate::helper::{Helper, ProcessName, ProcessSignal};
 use crate::utils::constants::{
     APP_MAX_HEIGHT, APP_MAX_WIDTH, APP_MIN_HEIGHT, APP_MIN_WIDTH, BYTES_ICON,
@@ -117,6 +118,26 @@ pub fn init_options(initial_window_size: Option<Vec2>) -> NativeOptions {
     options
 }
 
+#[cold]
+#[inline(never)]
+// Endpoints of XvB, replaced by the ones of a mock server if built
+// with [mock-xvb] and the [GUPAXX_XVB_MOCK] environment variable is set.
+pub fn init_xvb_endpoints() -> XvbEndpoints {
+    #[cfg(feature = "mock-xvb")]
+    if std::env::var_os(crate::helper::xvb::mock::XVB_MOCK_ENV).is_some() {
+        match crate::helper::xvb::mock::MockXvb::start_with_nodes(&["European", "North America"]) {
+            Ok(mock) => {
+                warn!("XvB | Using the mock server instead of XvB");
+                let endpoints = mock.endpoints();
+                mock.run_rounds(std::time::Duration::from_secs(600));
+                return endpoints;
+            }
+            Err(e) => warn!("XvB | Could not start the mock server: {}", e),
+        }
+    }
+    XvbEndpoints::from_env()
+}
+
 #[cold]
 #[inline(never)]
 pub fn init_auto(app: &mut App) {
@@ -154,7 +175,7 @@ pub fn init_auto(app: &mut App) {
 
     // [Auto-Ping]
     if app.state.p2pool.auto_ping && app.state.p2pool.simple {
-        Ping::spawn_thread(&app.ping)
+        Ping::spawn_thread(&app.ping, app.state.gupax.socks5.gupaxx())
     } else {
         info!("Skipping auto-ping...");
     }
@@ -166,11 +187,13 @@ pub fn init_auto(app: &mut App) {
         .auto
         .is_enabled(&AutoStart::Process(ProcessName::Node))
     {
-        if !Gupax::path_is_file(&app.state.gupax.node_path) {
+        if !app.state.node.attached() && !Gupax::path_is_file(&app.state.gupax.node_path) {
             warn!("Gupaxx | Node path is not a file! Skipping auto-node...");
-        } else if !check_binary_path(&app.state.gupax.node_path, ProcessName::Node) {
+        } else if !app.state.node.attached()
+            && !check_binary_path(&app.state.gupax.node_path, ProcessName::Node)
+        {
             warn!("Gupaxx | Node path is not valid! Skipping auto-node...");
-        } else if process_running(ProcessName::Node) {
+        } else if !app.state.node.attached() && process_running(ProcessName::Node) {
             warn!(
                 "Gupaxx | Node instance is already running outside of Gupaxx ! Skipping auto-node..."
             );
@@ -181,6 +204,7 @@ pub fn init_auto(app: &mut App) {
                 &app.helper,
                 &app.state.node,
                 &app.state.gupax.absolute_node_path,
+                &app.state.gupax.socks5,
             );
         }
     } else {
@@ -195,11 +219,16 @@ pub fn init_auto(app: &mut App) {
     {
         if !Regexes::addr_ok(&app.state.p2pool.address) {
             warn!("Gupaxx | P2Pool address is not valid! Skipping auto-p2pool...");
-        } else if !Gupax::path_is_file(&app.state.gupax.p2pool_path) {
+        } else if !app.state.p2pool.attached() && !Gupax::path_is_file(&app.state.gupax.p2pool_path)
+        {
             warn!("Gupaxx | P2Pool path is not a file! Skipping auto-p2pool...");
-        } else if !check_binary_path(&app.state.gupax.p2pool_path, ProcessName::P2pool) {
+        } else if !app.state.p2pool.attached()
+            && !check_binary_path(&app.state.gupax.p2pool_path, ProcessName::P2pool)
+        {
             warn!("Gupaxx | P2Pool path is not valid! Skipping auto-p2pool...");
-        } else if process_running(crate::helper::ProcessName::P2pool) {
+        } else if !app.state.p2pool.attached()
+            && process_running(crate::helper::ProcessName::P2pool)
+        {
             warn!(
                 "Gupaxx | P2pool instance is already running outside of Gupaxx ! Skipping auto-node..."
             );
@@ -211,6 +240,7 @@ pub fn init_auto(app: &mut App) {
                 &app.state.gupax.absolute_p2pool_path,
                 app.backup_hosts.clone(),
                 false,
+                &app.state.gupax.socks5,
             );
         }
     } else {
@@ -224,15 +254,21 @@ pub fn init_auto(app: &mut App) {
         .auto
         .is_enabled(&AutoStart::Process(ProcessName::Xmrig))
     {
-        if !Gupax::path_is_file(&app.state.gupax.xmrig_path) {
+        if !app.state.xmrig.attached() && !Gupax::path_is_file(&app.state.gupax.xmrig_path) {
             warn!("Gupaxx | XMRig path is not an executable! Skipping auto-xmrig...");
-        } else if !check_binary_path(&app.state.gupax.xmrig_path, ProcessName::Xmrig) {
+        } else if !app.state.xmrig.attached()
+            && !check_binary_path(&app.state.gupax.xmrig_path, ProcessName::Xmrig)
+        {
             warn!("Gupaxx | XMRig path is not valid! Skipping auto-xmrig...");
-        } else if process_running(crate::helper::ProcessName::Xmrig) {
+        } else if !app.state.xmrig.attached() && process_running(crate::helper::ProcessName::Xmrig)
+        {
             warn!(
                 "Gupaxx | Xmrig instance is already running outside of Gupaxx ! Skipping auto-node..."
             );
-        } else if cfg!(windows) || !Helper::password_needed() {
+        } else if !Helper::xmrig_password_needed(
+            &app.state.xmrig,
+            &app.state.gupax.absolute_xmrig_path,
+        ) {
             Helper::start_xmrig(
                 &app.helper,
                 &app.state.xmrig,
@@ -240,6 +276,7 @@ pub fn init_auto(app: &mut App) {
                 &app.state.xmrig_proxy,
                 &app.state.gupax.absolute_xmrig_path,
                 Arc::clone(&app.sudo),
+                &app.state.gupax.socks5,
             );
         } else {
             app.sudo.lock().unwrap().signal = ProcessSignal::Start;
@@ -255,11 +292,17 @@ pub fn init_auto(app: &mut App) {
         .auto
         .is_enabled(&AutoStart::Process(ProcessName::XmrigProxy))
     {
-        if !Gupax::path_is_file(&app.state.gupax.xmrig_proxy_path) {
+        if !app.state.xmrig_proxy.attached()
+            && !Gupax::path_is_file(&app.state.gupax.xmrig_proxy_path)
+        {
             warn!("Gupaxx | Xmrig-Proxy path is not a file! Skipping auto-xmrig_proxy...");
-        } else if !check_binary_path(&app.state.gupax.xmrig_proxy_path, ProcessName::XmrigProxy) {
+        } else if !app.state.xmrig_proxy.attached()
+            && !check_binary_path(&app.state.gupax.xmrig_proxy_path, ProcessName::XmrigProxy)
+        {
             warn!("Gupaxx | Xmrig-Proxy path is not valid! Skipping auto-xmrig_proxy...");
-        } else if process_running(crate::helper::ProcessName::XmrigProxy) {
+        } else if !app.state.xmrig_proxy.attached()
+            && process_running(crate::helper::ProcessName::XmrigProxy)
+        {
             warn!(
                 "Gupaxx | Xmrig-Proxy instance is already running outside of Gupaxx ! Skipping auto-node..."
             );
@@ -287,8 +330,12 @@ pub fn init_auto(app: &mut App) {
             &app.state.p2pool,
             &app.state.xmrig,
             &app.state.xmrig_proxy,
+            &app.state.gupax.socks5,
         );
     } else {
         info!("Skipping auto-xvb...");
+        // the remote miners left on XvB by the last exit
+        let helper = Arc::clone(&app.helper);
+        std::thread::spawn(move || Helper::restore_remote_miners_without_xvb(&helper));
     }
 }
@@ -29,7 +29,7 @@ use crate::app::App;
 use crate::cli::Cli;
 //---------------------------------------------------------------------------------------------------- Imports
 use crate::constants::*;
-use crate::inits::{init_auto, init_logger, init_options};
+use crate::inits::{init_auto, init_logger, init_options, init_xvb_endpoints};
 use crate::miscs::clean_dir;
 use crate::utils::*;
 use clap::Parser;
@@ -61,7 +61,9 @@ fn main() {
 
     // Init logger.
     init_logger(now, args.logfile);
+    let xvb_endpoints = init_xvb_endpoints();
     let mut app = App::new(now, args);
+    *app.xvb_endpoints.lock().unwrap() = xvb_endpoints;
     init_auto(&mut app);
 
     // Init GUI stuff.
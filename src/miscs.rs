@@ -192,8 +192,26 @@ fn datetimeonsole() -> String {
     format!("[{}]  ", Local::now().format("%Y-%m-%d %H:%M:%S%.3f"))
 }
 
-pub fn client() -> ClientWithMiddleware {
-    reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+// Plain HTTP client, routed through the SOCKS5 proxy if one is given.
+// Requests to the local machine (XMRig/Proxy/Node APIs) never go through the proxy.
+pub fn http_client(socks5: Option<&str>) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder();
+    if let Some(addr) = socks5 {
+        match reqwest::Proxy::all(["socks5h://", addr].concat()) {
+            Ok(proxy) => {
+                builder = builder
+                    .proxy(proxy.no_proxy(reqwest::NoProxy::from_string("127.0.0.1,localhost")))
+            }
+            Err(e) => warn!("HTTP client | invalid SOCKS5 proxy {addr}, not using it: {e}"),
+        }
+    }
+    builder.build().unwrap_or_else(|e| {
+        error!("HTTP client | could not build client with SOCKS5 proxy, using default: {e}");
+        reqwest::Client::new()
+    })
+}
+pub fn client(socks5: Option<&str>) -> ClientWithMiddleware {
+    reqwest_middleware::ClientBuilder::new(http_client(socks5))
         .with(reqwest_retry::RetryTransientMiddleware::new_with_policy(
             reqwest_retry::policies::ExponentialBackoff::builder()
                 .retry_bounds(Duration::from_secs(1), Duration::from_secs(5))
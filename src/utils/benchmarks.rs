@@ -0,0 +1,97 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Dataset of the XMRig CPU benchmarks shown in the [Benchmarks] submenu of the [Status] tab.
+// A copy is embedded in the binary, a newer one downloaded by the updater
+// is saved in the data directory and is used instead if it is valid.
+
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use log::{info, warn};
+
+use crate::{app::Benchmark, disk::consts::CPU_BENCHMARKS_JSON, miscs::cmp_f64};
+
+pub const EMBEDDED_BENCHMARKS: &[u8] = include_bytes!("../../assets/cpu.json");
+
+// Parse a dataset and check that it can be displayed.
+pub fn parse_benchmarks(bytes: &[u8]) -> Result<Vec<Benchmark>> {
+    let benchmarks: Vec<Benchmark> = serde_json::from_slice(bytes)?;
+    if benchmarks.is_empty() {
+        return Err(anyhow!("the dataset is empty"));
+    }
+    for b in &benchmarks {
+        if b.cpu.trim().is_empty() {
+            return Err(anyhow!("CPU at rank {} has no name", b.rank));
+        }
+        let valid = |v: f32| v.is_finite() && v >= 0.0;
+        if !valid(b.percent)
+            || !valid(b.average)
+            || !valid(b.high)
+            || !valid(b.low)
+            || b.low > b.high
+        {
+            return Err(anyhow!("CPU [{}] has invalid hashrates", b.cpu));
+        }
+    }
+    Ok(benchmarks)
+}
+
+// The dataset of the data directory, or the embedded one if it is missing or invalid.
+pub fn load_benchmarks(data_path: Option<&Path>) -> Vec<Benchmark> {
+    if let Some(path) = data_path.map(|p| p.join(CPU_BENCHMARKS_JSON)) {
+        if path.exists() {
+            match std::fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|b| parse_benchmarks(&b))
+            {
+                Ok(benchmarks) => {
+                    info!("Benchmarks | Using dataset at [{}]", path.display());
+                    return benchmarks;
+                }
+                Err(e) => warn!(
+                    "Benchmarks | Dataset at [{}] is invalid, using the embedded one: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+    }
+    parse_benchmarks(EMBEDDED_BENCHMARKS).expect("embedded cpu.json must be valid")
+}
+
+// Sort the benchmarks so that the closest CPU name is first.
+pub fn detect_benchmark_cpu(benchmarks: &mut [Benchmark], cpu: &str) {
+    let cpu = normalize_cpu_name(cpu);
+    let score = |b: &Benchmark| strsim::jaro_winkler(&normalize_cpu_name(&b.cpu), &cpu);
+    benchmarks.sort_by(|a, b| cmp_f64(score(b), score(a)));
+}
+
+// The name reported by the OS is not always the one of the benchmarks:
+// "Intel(R) Core(TM) i7-8700 CPU @ 3.20GHz" is "Intel Core i7-8700".
+pub fn normalize_cpu_name(name: &str) -> String {
+    let name = name.to_lowercase().replace("(r)", " ").replace("(tm)", " ");
+    let name = name.split('@').next().unwrap_or_default();
+    name.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|w| {
+            !w.is_empty()
+                && !matches!(*w, "cpu" | "processor" | "with" | "radeon" | "graphics")
+                && !w.ends_with("-core")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -95,6 +95,10 @@ pub const P2POOL_API_PATH_POOL: &str = "pool/stats";
 pub const P2POOL_API_PATH_P2P: &str = "local/p2p";
 pub const XMRIG_API_SUMMARY_ENDPOINT: &str = "1/summary"; // The default relative URI of XMRig's API summary
 pub const XMRIG_API_CONFIG_ENDPOINT: &str = "1/config"; // The default relative URI of XMRig's API config
+pub const XMRIG_API_BACKENDS_ENDPOINT: &str = "2/backends"; // The default relative URI of XMRig's API backends (per-thread stats)
+pub const XMRIG_API_JSON_RPC_ENDPOINT: &str = "json_rpc"; // The default relative URI of XMRig's JSON-RPC API (pause/resume)
+pub const XMRIG_PROXY_API_WORKERS_ENDPOINT: &str = "1/workers"; // The default relative URI of XMRig-Proxy's API workers
+pub const XMRIG_PROXY_API_MINERS_ENDPOINT: &str = "1/miners"; // The default relative URI of XMRig-Proxy's API miners (one per connection)
 
 // Process state tooltips (online, offline, etc)
 pub const P2POOL_ALIVE: &str = "P2Pool is online and fully synchronized";
@@ -122,6 +126,13 @@ pub const XMRIG_PROXY_FAILED: &str = "XMRig-Proxy is offline and failed when exi
 pub const XMRIG_PROXY_MIDDLE: &str = "XMRig-Proxy is in the middle of (re)starting/stopping";
 pub const XMRIG_PROXY_NOT_MINING: &str = "XMRig-Proxy is online, but not mining to any pool";
 pub const XMRIG_PROXY_REDIRECT: &str = "point local xmrig instance on this proxy instead of the p2pool instance (recommended if using XvB)";
+pub const XMRIG_PROXY_ATTACH: &str = "Do not start XMRig-Proxy but monitor one already running outside of Gupaxx, through its HTTP API at the port and token set below. Stopping only detaches Gupaxx from it.";
+pub const XMRIG_PROXY_WORKERS: &str = "Workers of XMRig-Proxy, grouped by the mode of the proxy (rig id by default). An alert is written in the console when a worker loses its last connection or when its hashrate of the last minute is below half of its hashrate of the last hour.";
+pub const XMRIG_PROXY_WORKERS_HASHRATE: &str =
+    "Hashrate of the worker for the last 1 minute, 10 minutes and 1 hour";
+pub const XMRIG_PROXY_WORKERS_SHARES: &str =
+    "Shares accepted by the pool and shares rejected by the proxy as invalid";
+pub const XMRIG_PROXY_WORKERS_LAST_SHARE: &str = "Time since the worker sent its last share";
 pub const XMRIG_PROXY_INPUT: &str = "Send a command to XMRig-Proxy";
 pub const XMRIG_PROXY_SIMPLE: &str = r#"Use simple XMRig-Proxy settings:
   - Mine to local P2Pool (localhost:3333)
@@ -141,6 +152,7 @@ pub const XMRIG_PROXY_PATH_EMPTY: &str = "XMRig-Proxy PATH is empty! To fix: got
 pub const STATUS_XMRIG_PROXY_UPTIME: &str = "How long XMRig-Proxy has been online";
 pub const STATUS_XMRIG_PROXY_POOL: &str = "The pool XMRig-Proxy is currently mining to";
 pub const STATUS_XMRIG_PROXY_HASHRATE: &str = "The average hashrate of XMRig-Proxy";
+pub const STATUS_XMRIG_PROXY_WORKERS: &str = "The workers of XMRig-Proxy with at least one connection, and the ones that went missing or whose hashrate dropped";
 
 pub const XVB_ALIVE: &str =
     "XvB process is configured and distributing hashrate, XvB pool is online";
@@ -156,6 +168,8 @@ If unchecked (default):\n
 The algorithm will watch the HR estimated by the stratum data of the p2pool node, which is more accurate but will only take into account the miners that are using your P2Pool node.
 ";
 pub const XVB_MANUAL_POOL: &str = "Enable this to force the algorithm to connect to a specific XvB pool, without using the considered fastest";
+pub const XVB_CONTROLLED_MINERS: &str = "Remote XMRig and XMRig-Proxy of the fleet controlled by the algorithm with the local miner. The donation is shared between them: whole miners are sent to XvB for the cycle and only one is switched, so there are fewer pool switches. A remote miner is sent back to the pool it was using, so it should mine on your P2Pool node.";
+pub const XVB_CONTROLLED_MINER: &str = "Control this miner with the XvB algorithm. Its HTTP API must not be restricted, since its pool is modified.";
 pub const XVB_P2POOL_BUFFER: &str = "Set the % amount of additional HR to send to p2pool. Will reduce (if positive) or augment (if negative) the chances to miss the p2pool window.\n\n- In Auto or Hero mode, the algorithm will keep enough HR on the p2pool side to conform to the buffer\n\n- In Manual modes, the algorithm will ignore the p2pool buffer";
 
 pub const START_OPTIONS_HOVER: &str = "Start the process with theses options.\nThe \"Reset to simple/advanced options\" are arguments constructed from the settings.\nYou can replace them with your own";
@@ -223,6 +237,8 @@ pub const STATUS_GUPAX_CPU_USAGE: &str = "How much CPU Gupaxx is currently using
 pub const STATUS_GUPAX_MEMORY_USAGE: &str =
     "How much memory Gupaxx is currently using in Megabytes";
 pub const STATUS_GUPAX_SYSTEM_CPU_USAGE: &str = "How much CPU your entire system is currently using. This accounts for all your threads (it is out of 100%)";
+pub const STATUS_GUPAX_SYSTEM_CPU_TEMP: &str =
+    "Temperature of the hottest sensor of your CPU, used by the temperature throttling of XMRig";
 pub const STATUS_GUPAX_SYSTEM_MEMORY: &str =
     "How much memory your entire system has (including swap) and is currently using in Gigabytes";
 pub const STATUS_GUPAX_SYSTEM_CPU_MODEL: &str =
@@ -268,6 +284,8 @@ pub const STATUS_NODE_FREESPACE: &str = "Free space left on the partition storin
 pub const STATUS_NODE_NETTYPE: &str = "Type of network (mainnet, stagenet, testnet)";
 pub const STATUS_NODE_OUT: &str = "Current number of active outbound connections";
 pub const STATUS_NODE_IN: &str = "Current number of active incoming connections";
+pub const STATUS_NODE_ANONYMOUS: &str =
+    "Current number of connections through I2P or Tor (anonymous inbound and transactions relay)";
 pub const STATUS_NODE_SYNC: &str = "Does the node is synchronized with the network ?";
 pub const STATUS_NODE_STATUS: &str = "General status of the node";
 // Status Submenus
@@ -275,6 +293,10 @@ pub const STATUS_SUBMENU_PROCESSES: &str =
     "View the status of process related data for [Gupaxx|P2Pool|XMRig]";
 pub const STATUS_SUBMENU_P2POOL: &str = "View P2Pool specific data";
 pub const STATUS_SUBMENU_HASHRATE: &str = "Compare your CPU hashrate with others";
+pub const STATUS_SUBMENU_THREADS: &str =
+    "View the hashrate and affinity of every thread of XMRig to find the slow or mis-pinned ones";
+pub const STATUS_SUBMENU_FLEET: &str =
+    "View the XMRig, XMRig-Proxy and Node instances running on other hosts";
 //-- P2Pool
 pub const STATUS_SUBMENU_PAYOUT: &str = "The total amount of payouts received via P2Pool across all time. This includes all payouts you have ever received using Gupaxx and P2Pool.";
 pub const STATUS_SUBMENU_XMR: &str = "The total of XMR mined via P2Pool across all time. This includes all the XMR you have ever mined using Gupaxx and P2Pool.";
@@ -328,6 +350,40 @@ pub const STATUS_SUBMENU_OTHER_LOW: &str = "Lowest hashrate record";
 pub const STATUS_SUBMENU_OTHER_RANK: &str = "The rank of this CPU out of [1567] (lower is better)";
 pub const STATUS_SUBMENU_OTHER_BENCHMARKS: &str =
     "How many benchmarks this CPU has had posted to [https://xmrig.com/benchmark]";
+//-- Fleet
+//-- Local benchmarks
+pub const STATUS_BENCH_LOCAL: &str = "Benchmarks run on this computer with [xmrig --bench], compared to the average hashrate of the same CPU on xmrig.com";
+pub const STATUS_BENCH_SIZE: &str =
+    "Number of hashes to compute. 10M takes ten times longer but gives a more precise hashrate.";
+pub const STATUS_BENCH_HUGEPAGES: &str = "Let XMRig use huge pages (and 1GB pages if they are reserved). The result tells if they were actually allocated.";
+pub const STATUS_BENCH_THREADS: &str = "Number of CPU threads used by the benchmark";
+pub const STATUS_BENCH_RUN: &str = "Start XMRig to compute the hashes offline, without mining";
+pub const STATUS_BENCH_DISABLED: &str =
+    "XMRig must be stopped and found at the PATH of the [Gupaxx Advanced] tab to run a benchmark";
+pub const STATUS_BENCH_STOP: &str = "Stop the benchmark, nothing is recorded";
+pub const STATUS_BENCH_DELETE: &str = "Remove this benchmark";
+//-- Threads
+pub const STATUS_THREADS_BACKEND: &str =
+    "Algorithm, CPU profile and memory used by the scratchpads of the threads of this backend";
+pub const STATUS_THREADS_HUGEPAGES: &str =
+    "Huge pages allocated for the scratchpads of the threads of this backend";
+pub const STATUS_THREADS_DATASET: &str =
+    "RandomX mode of XMRig and huge pages allocated for the dataset";
+pub const STATUS_THREADS_INDEX: &str = "Index of the thread in XMRig";
+pub const STATUS_THREADS_AFFINITY: &str = "CPU the thread is pinned to, with its physical core and NUMA node. A thread which is not pinned can be moved by the system to any CPU.";
+pub const STATUS_THREADS_HASHRATE: &str =
+    "Hashrate of the thread over 10 seconds, 1 minute and 15 minutes";
+pub const STATUS_THREADS_STATUS: &str = "Slow: under 75% of the median of the other threads. Stalled: no hashrate while the other threads hash. Same CPU/core: pinned to the same CPU, or to the SMT sibling of another thread while a physical core is idle. Fix the pinning in the thread layout of the XMRig tab.";
+pub const STATUS_FLEET_NAME: &str = "Name to identify this instance; Only [A-Za-z0-9-_.] and spaces allowed; Max length = 30 characters";
+pub const STATUS_FLEET_KIND: &str = "Software running on the remote host";
+pub const STATUS_FLEET_HOST: &str = "IP or domain of the remote host. The API of the instance must listen on an address reachable from this computer";
+pub const STATUS_FLEET_PORT: &str =
+    "Port of the HTTP API for XMRig and XMRig-Proxy, of the RPC for the Node; [1-65535]";
+pub const STATUS_FLEET_TOKEN: &str =
+    "Access token of the HTTP API of XMRig and XMRig-Proxy, not used for the Node";
+pub const STATUS_FLEET_ADD: &str =
+    "Add this instance to the fleet, it will be polled every 5 seconds";
+pub const STATUS_FLEET_DELETE: &str = "Remove this instance from the fleet";
 
 // Gupaxx
 pub const GUPAX_UPDATE: &str = "Check for updates on Gupaxx and bundled versions of P2Pool and XMRig via GitHub's API and upgrade automatically";
@@ -380,6 +436,50 @@ pub const GUPAX_SELECT: &str = "Open a file explorer to select a file";
 pub const GUPAX_PATH_P2POOL: &str = "The location of the P2Pool binary: Both absolute and relative paths are accepted; A red [X] will appear if there is no file found at the given path";
 pub const GUPAX_PATH_XMRIG: &str = "The location of the XMRig binary: Both absolute and relative paths are accepted; A red [X] will appear if there is no file found at the given path";
 pub const GUPAX_PATH_XMRIG_PROXY: &str = "The location of the XMRig-Proxy binary: Both absolute and relative paths are accepted; A red [X] will appear if there is no file found at the given path";
+pub const GUPAX_SOCKS5: &str = "Route the traffic leaving this computer through a SOCKS5 proxy (Tor daemon, i2pd...). Processes need to be restarted to apply the change.";
+pub const GUPAX_SOCKS5_IP: &str = "IP of the SOCKS5 proxy";
+pub const GUPAX_SOCKS5_TOR: &str = "The proxy is the Tor daemon: the Node also broadcasts its transactions to onion peers through it. Disable it for another proxy (i2pd, ...)";
+pub const GUPAX_SOCKS5_NODE_IP: &str =
+    "The Node only accepts an IP for its SOCKS5 proxy, not a domain";
+pub const GUPAX_SOCKS5_PORT: &str =
+    "Port of the SOCKS5 proxy (9050 for the Tor daemon, 9150 for the Tor Browser)";
+pub const GUPAX_SOCKS5_NODE: &str =
+    "The Node will connect to its peers and broadcast transactions through the proxy";
+pub const GUPAX_SOCKS5_P2POOL: &str =
+    "P2Pool will connect to its peers and to remote nodes through the proxy";
+pub const GUPAX_SOCKS5_XMRIG: &str = "XMRig will connect to remote pools (including XvB pools) through the proxy. A local P2Pool or XMRig-Proxy is always joined directly.";
+pub const GUPAX_SOCKS5_GUPAXX: &str = "Gupaxx will check for updates, request XvB stats and ping remote nodes/XvB pools through the proxy";
+pub const GUPAX_NOTIFY: &str =
+    "Notify the events selected below outside of the window, while Gupaxx is running";
+pub const GUPAX_NOTIFY_DESKTOP: &str =
+    "Show a desktop notification with notify-send (freedesktop notifications)";
+pub const GUPAX_NOTIFY_WEBHOOK: &str = "Send each notification as JSON with a POST request to this URL. The body has the fields [event], [title], [message] and [timestamp]. The SOCKS5 proxy is used if it is enabled for Gupaxx";
+pub const GUPAX_NOTIFY_PAYOUT: &str = "A payout from P2Pool was received";
+pub const GUPAX_NOTIFY_SHARE: &str = "P2Pool found a share";
+pub const GUPAX_NOTIFY_CRASH: &str = "A process stopped with an error";
+pub const GUPAX_NOTIFY_XVB_WIN: &str = "You are the winner of the current XvB round";
+pub const GUPAX_NOTIFY_NODE_SYNC: &str = "The Node was synchronized and is not anymore";
+pub const GUPAX_NOTIFY_XVB_OFFLINE: &str =
+    "The XvB pools can not be reached, the hashrate is sent to P2Pool only";
+pub const GUPAX_NOTIFY_UPDATE: &str = "A check for updates found a newer version of Gupaxx";
+pub const GUPAX_MQTT: &str = "Publish the state of the processes, hashrate, shares, payouts and XvB round to a MQTT broker, for home automation. The values are retained under [prefix/process/...] and [prefix/status] is [online] while Gupaxx is connected";
+pub const GUPAX_MQTT_HOST: &str = "IP or domain of the MQTT broker";
+pub const GUPAX_MQTT_PORT: &str = "Port of the MQTT broker, 1883 by default";
+pub const GUPAX_MQTT_USERNAME: &str =
+    "Username for the MQTT broker, leave empty if it does not require authentication";
+pub const GUPAX_MQTT_PASSWORD: &str = "Password for the MQTT broker";
+pub const GUPAX_MQTT_PREFIX: &str =
+    "Prefix of the topics, use a different one for each rig publishing to the same broker";
+pub const GUPAX_MQTT_COMMANDS: &str = "Accept commands: [start], [stop] or [restart] on [prefix/process/set] (node, p2pool, xmrig, xmrig_proxy, xvb) and a number of threads on [prefix/xmrig/threads/set]. Anyone allowed to publish on the broker can then control the processes. Commands are run by the window of Gupaxx, which is woken up when one is received. The threads given by a command are not saved and are used until Gupaxx is restarted or the threads are changed in the [XMRig] tab. Retained messages are ignored, so a command is never run again when reconnecting";
+pub const GUPAX_SOCKS5_LATENCY: &str = "Warning: a proxy like Tor adds latency. Shares and new jobs will take longer to arrive, which increases stale shares and lowers your effective hashrate. Remote nodes and pools will also appear slower when pinged.";
+pub const GUPAX_XMRIG_PRIVILEGED: &str = "Run a one-time setup with sudo reserving the hugepages needed by XMRig, so XMRig does not need sudo anymore. The reservation persists across reboots. XMRig started without sudo can not apply the MSR mod, keep starting it with sudo to use it.";
+pub const GUPAX_XMRIG_PRIVILEGED_WARNING: &str = "The setup runs as root. It reserves the hugepages in /etc/sysctl.d and /etc/tmpfiles.d, and removes what the older setups gave to XMRig: the MSR devices writable by a group, the msr module options, the capabilities and the setgid bit of XMRig. XMRig will then run without sudo and without the MSR mod, which lowers the hashrate by up to 15%.";
+pub const GUPAX_XMRIG_PRIVILEGED_YES: &str =
+    "The hugepages are reserved, XMRig will be started without sudo and without the MSR mod";
+pub const GUPAX_XMRIG_PRIVILEGED_NO: &str =
+    "XMRig will be started with sudo, the password will be asked if needed";
+pub const GUPAX_XMRIG_PRIVILEGED_SETUP: &str =
+    "Enter the sudo password to run the privileged setup for XMRig.\nNo process will be started.";
 
 // P2Pool
 pub const P2POOL_PORT_DEFAULT: u16 = 3333;
@@ -401,6 +501,8 @@ pub const P2POOL_BACKUP_HOST_ADVANCED: &str =
     "Automatically switch to the other nodes in your list if the current one is down.";
 pub const P2POOL_AUTOSWITCH_LOCAL_NODE: &str =
     "Automatically switch to the local node when it will be ready to be used.";
+pub const P2POOL_ATTACH: &str = "Do not start P2Pool but monitor one already running outside of Gupaxx, through the files of its --data-api directory. Stopping only detaches Gupaxx from it.";
+pub const P2POOL_ATTACH_DATA_API: &str = "Directory given to the external P2Pool with --data-api; If empty, the directory used by Gupaxx";
 pub const P2POOL_SELECT_FASTEST: &str = "Select the fastest remote Monero node";
 pub const P2POOL_SELECT_RANDOM: &str = "Select a random remote Monero node";
 pub const P2POOL_SELECT_LAST: &str = "Select the previous remote Monero node";
@@ -485,6 +587,18 @@ pub const NODE_API_PORT: &str = "RPC API listen port";
 pub const NODE_ZMQ_BIND: &str = "bind address of ZMQ API";
 pub const NODE_ZMQ_PORT: &str = "ZMQ API listen port";
 pub const NODE_FULL_MEM: &str = "Use 2GB of RAM insteal of 256MB for faster block verification";
+pub const NODE_I2P: &str = "Broadcast your transactions through a local I2P router (i2pd, Java I2P) instead of clearnet. Its SOCKS proxy must be enabled.";
+pub const NODE_I2P_IP: &str = "IP of the SOCKS proxy of the I2P router";
+pub const NODE_I2P_PORT: &str =
+    "Port of the SOCKS proxy of the I2P router (4447 by default for i2pd)";
+pub const NODE_I2P_INBOUND: &str = "Accept anonymous peers coming from I2P. The I2P router needs a server tunnel forwarding to the local port below.";
+pub const NODE_I2P_INBOUND_ADDRESS: &str =
+    "b32 address of the I2P server tunnel, shared with the peers (xxx.b32.i2p)";
+pub const NODE_I2P_INBOUND_PORT: &str =
+    "Local port the I2P server tunnel forwards to, the Node will listen on it";
+pub const NODE_I2P_INBOUND_MAX: &str = "Maximum number of anonymous inbound connections";
+pub const NODE_I2P_P2POOL: &str = "P2Pool will connect to its peers and remote nodes through the same proxy. The I2P router must have an outproxy to reach clearnet peers. A SOCKS5 proxy set in the Gupaxx tab for P2Pool takes precedence.";
+pub const NODE_ATTACH: &str = "Do not start a Node but monitor one already running outside of Gupaxx, through its RPC at the IP and port set below. Stopping only detaches Gupaxx from it.";
 // XMRig
 pub const XMRIG_API_PORT_DEFAULT: u16 = 18088;
 pub const XMRIG_SIMPLE: &str = r#"Use simple XMRig settings:
@@ -516,6 +630,41 @@ pub const XMRIG_API_IP: &str =
 pub const XMRIG_API_PORT: &str =
     "Specify which port to bind to for XMRig's HTTP API; If empty: [18088]";
 pub const XMRIG_API_TOKEN: &str = "Specify the token to authenticate on the HTTP API";
+pub const XMRIG_ATTACH: &str = "Do not start XMRig but monitor one already running outside of Gupaxx, through its HTTP API at the IP, port and token set below. XvB can still switch its pool. Stopping only detaches Gupaxx from it.";
+pub const XMRIG_LAYOUT: &str = "Pin the threads of XMRig to the selected CPUs and set its priority and RandomX mode. The threads slider is used if no CPU is selected.";
+pub const XMRIG_LAYOUT_ALL: &str = "Select every CPU";
+pub const XMRIG_LAYOUT_NONE: &str = "Unselect every CPU, the threads slider will be used";
+pub const XMRIG_LAYOUT_L3: &str =
+    "Select as many CPUs as the L3 caches can hold (2MB per thread), one per physical core first";
+pub const XMRIG_LAYOUT_NODE: &str = "Select only the CPUs of this NUMA node";
+pub const XMRIG_LAYOUT_MAX_CPU: &str = "XMRig can only pin threads to the first 64 CPUs";
+pub const XMRIG_CPU_PRIORITY: &str =
+    "Priority of the threads of XMRig, from 0 (idle) to 5 (highest). 2 is the normal priority.";
+pub const XMRIG_RANDOMX_MODE: &str = "Auto: fast mode if there is enough memory. Fast: uses the 2080MB dataset for the best hashrate. Light: uses only 256MB but is much slower.";
+pub const XMRIG_FAILOVER: &str = "Pools XMRig switches to, in this order, when the pool above is down. The pool in use is shown in green.";
+pub const XMRIG_FAILOVER_ADD: &str =
+    "Add the pool selected in the list above at the end of the failover pools";
+pub const XMRIG_FAILOVER_USER: &str = "User sent to this pool, the payout address is used if empty";
+pub const XMRIG_FAILOVER_UP: &str = "Try this pool earlier";
+pub const XMRIG_FAILOVER_DOWN: &str = "Try this pool later";
+pub const XMRIG_FAILOVER_REMOVE: &str = "Remove this pool from the failover pools";
+pub const XMRIG_FAILOVER_PROBE: &str = "Open a stratum connection to the primary and failover pools and log in with a throwaway worker, to measure the time to get a job";
+pub const XMRIG_FAILOVER_PROBE_RESULT: &str =
+    "Result of the last probe: time to get the answer to the login and the first job, or the error";
+pub const XMRIG_FAILOVER_SORT: &str = "Sort the failover pools by the time to get a job of the last probe, the pools that could not be probed are put last";
+pub const XMRIG_CONFIG_FILE: &str = "Start XMRig with a config file generated from these settings instead of command line flags. The file is [xmrig.json] in the Gupaxx data directory: options added to it by hand (donate-level, cpu profiles, other pools...) are kept, the settings of this tab always replace their own keys.";
+pub const XMRIG_HUGEPAGES: &str = "Huge pages needed by RandomX on this computer and what XMRig could allocate at startup. The pages are reserved by the privileged setup of the Gupaxx tab.";
+pub const HUGEPAGES_UNSUPPORTED: &str = "XMRig can not use huge pages. Run the privileged setup in the [Gupaxx Advanced] tab or start XMRig with sudo, then restart XMRig.";
+pub const HUGEPAGES_NOT_RESERVED: &str = "Not every huge page could be allocated. Run the privileged setup in the [Gupaxx Advanced] tab to reserve them, then restart XMRig. If it still fails, the memory is too fragmented: reboot.";
+pub const HUGEPAGES_NOT_ENOUGH_MEMORY: &str = "Not enough free memory to reserve the huge pages needed by the RandomX dataset. Close some programs or lower the threads, then run the privileged setup again.";
+pub const HUGEPAGES_1GB_FRAGMENTED: &str = "The 1GB pages could not be reserved, the memory is too fragmented to do it while the system is running. Add [hugepagesz=1G hugepages=N] to the kernel command line (N = 1GB pages needed) and reboot.";
+pub const XMRIG_THROTTLE: &str = "Watch the temperature of the CPU while XMRig is mining. Above the soft threshold, half of the threads are stopped. Above the hard threshold, XMRig is paused. The threads are restored once the temperature stayed below the threshold for the cooldown.";
+pub const XMRIG_THROTTLE_NO_SENSOR: &str =
+    "No temperature sensor of the CPU was found, XMRig will not be throttled";
+pub const XMRIG_TEMP_SOFT: &str = "Temperature in °C above which half of the threads are stopped";
+pub const XMRIG_TEMP_HARD: &str = "Temperature in °C above which XMRig is paused";
+pub const XMRIG_THROTTLE_COOLDOWN: &str =
+    "Seconds the temperature must stay below a threshold before the threads are restored";
 pub const XMRIG_TLS: &str = "Enable SSL/TLS connections (needs pool support)";
 pub const XMRIG_KEEPALIVE: &str = "Send keepalive packets to prevent timeout (needs pool support)";
 pub const XMRIG_THREADS: &str = "Number of CPU threads to use for mining";
@@ -545,6 +694,7 @@ pub const XVB_ADVANCED: &str = r#"Use advanced XvB settings:
       Round
   - P2Pool Buffer"#;
 pub const XVB_URL_PUBLIC_API: &str = "https://xmrvsbeast.com/p2pool/stats";
+pub const XVB_URL_PRIVATE_API: &str = "/cgi-bin/p2pool_bonus_history_gupaxx_api.cgi";
 pub const XVB_NODE_PORT: &str = "4247";
 pub const XVB_NODE_EU: &str = "eu.xmrvsbeast.com";
 pub const XVB_NODE_NA: &str = "na.xmrvsbeast.com";
@@ -560,6 +710,13 @@ pub const XVB_FAILURE_FIELD: &str = "Failures";
 pub const XVB_DONATED_1H_FIELD: &str = "Donated last hour";
 pub const XVB_DONATED_24H_FIELD: &str = "Donated last 24 hours";
 pub const XVB_ROUND_TYPE_FIELD: &str = "Round";
+pub const XVB_HISTORY_HELP: &str = "Rounds of XvB seen while the XvB process was running. A round ends when a new raffle is drawn. The history is saved in the Gupaxx data directory.";
+pub const XVB_HISTORY_ROUNDS: &str = "Rounds seen in the period, rounds where you had a share in the PPLNS window and qualified for a round type, rounds won and hashrate donated to XvB in kH/s multiplied by hours";
+pub const XVB_HISTORY_LAST_ROUNDS: &str = "The last 10 rounds, with the round type you qualified for, your averages of donated hashrate given by XvB and the time Gupaxx mined on XvB during the round";
+pub const XVB_PROBES_HELP: &str = "Each XvB pool is probed like XMRig would use it: a stratum connection is opened and a throwaway worker logs in. The pool giving a job the fastest is chosen, a pool that can not be joined is tried last.";
+pub const XVB_PROBES_COLUMNS: &str = "Time to open the connection (with TLS), to get the answer to the login and to get the first job";
+pub const XVB_PLANNER_HELP: &str = "Hashrate needed to qualify for each round type, computed from the last cycle of the algorithm. The hashrate kept for P2Pool is the minimum Auto mode keeps to have a share in the PPLNS window.";
+pub const XVB_PLANNER_COLUMNS: &str = "Average hashrate to donate to XvB in addition to the external donation of your address, the part of your hashrate it takes, the hashrate left to P2Pool compared to the minimum needed to keep a share and the time before XvB gives the round type if all the spareable hashrate is donated";
 pub const XVB_WINNER_FIELD: &str = "Win";
 pub const XVB_MINING_ON_FIELD: &str = "Currently Mining on";
 
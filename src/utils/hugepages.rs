@@ -0,0 +1,340 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Huge pages for RandomX on Linux.
+// The memory layout of the host is detected to calculate how many 2MB and 1GB pages
+// XMRig and monerod need, the reservation itself is done by the privileged setup of [sudo.rs].
+// The startup output of XMRig is then parsed to verify the pages were really used.
+
+use crate::constants::*;
+use crate::regex::XMRIG_REGEX;
+use once_cell::sync::Lazy;
+
+// 2MB pages of the RandomX dataset (2080MB), allocated once per NUMA node by XMRig.
+const DATASET_PAGES_2MB: u64 = 1040;
+// 1GB pages covering the RandomX dataset, per NUMA node.
+const DATASET_PAGES_1GB: u64 = 3;
+// 2MB pages of the RandomX cache (256MB).
+const CACHE_PAGES_2MB: u64 = 128;
+// monerod keeps the caches of the current and of the next seed.
+const MONEROD_PAGES_2MB: u64 = 2 * CACHE_PAGES_2MB;
+const PAGE_2MB_KB: u64 = 2 * 1024;
+const PAGE_1GB_KB: u64 = 1024 * 1024;
+// sysfs file of the 1GB pages reservation
+pub const SYSFS_1GB_PAGES: &str = "/sys/kernel/mm/hugepages/hugepages-1048576kB/nr_hugepages";
+
+// The CPU flags and the NUMA layout do not change while Gupaxx is running.
+pub static CPU_LAYOUT: Lazy<CpuLayout> = Lazy::new(CpuLayout::detect);
+
+//---------------------------------------------------------------------------------------------------- Layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuLayout {
+    pub pdpe1gb: bool,   // Does the CPU support 1GB pages?
+    pub numa_nodes: u64, // XMRig allocates a dataset for each NUMA node
+}
+
+impl CpuLayout {
+    #[cfg(target_os = "linux")]
+    fn detect() -> Self {
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+        let numa_nodes = std::fs::read_dir("/sys/devices/system/node")
+            .map(|dir| {
+                dir.filter_map(Result::ok)
+                    .filter(|e| Self::is_numa_node(&e.file_name().to_string_lossy()))
+                    .count() as u64
+            })
+            .unwrap_or_default();
+        Self {
+            pdpe1gb: Self::parse_pdpe1gb(&cpuinfo),
+            numa_nodes: numa_nodes.max(1),
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    fn detect() -> Self {
+        Self {
+            pdpe1gb: false,
+            numa_nodes: 1,
+        }
+    }
+    fn parse_pdpe1gb(cpuinfo: &str) -> bool {
+        cpuinfo
+            .lines()
+            .find(|l| l.starts_with("flags"))
+            .is_some_and(|l| l.split_whitespace().any(|f| f == "pdpe1gb"))
+    }
+    // "node0", "node1"... but not "online" or "has_cpu"
+    fn is_numa_node(name: &str) -> bool {
+        name.strip_prefix("node")
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryState {
+    pub available_kb: u64, // MemAvailable, the reserved huge pages are not part of it
+    pub pages_2mb: u64,    // 2MB pages currently reserved
+    pub pages_1gb: u64,    // 1GB pages currently reserved
+}
+
+impl MemoryState {
+    #[cfg(target_os = "linux")]
+    pub fn detect() -> Self {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").unwrap_or_default();
+        let pages_1gb = std::fs::read_to_string(SYSFS_1GB_PAGES)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or_default();
+        Self::parse_meminfo(&meminfo, pages_1gb)
+    }
+    #[cfg(not(target_os = "linux"))]
+    pub fn detect() -> Self {
+        Self::default()
+    }
+    // [HugePages_Total] only counts the pages of the default size (2MB).
+    fn parse_meminfo(meminfo: &str, pages_1gb: u64) -> Self {
+        let field = |name: &str| {
+            meminfo
+                .lines()
+                .find_map(|l| l.strip_prefix(name))
+                .and_then(|v| v.split_whitespace().next())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_default()
+        };
+        Self {
+            available_kb: field("MemAvailable:"),
+            pages_2mb: field("HugePages_Total:"),
+            pages_1gb,
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HugepagesPlan {
+    pub pages_2mb: u64,
+    pub pages_1gb: u64,
+    pub enough_memory: bool, // Can the pages be reserved with the memory currently available?
+}
+
+impl HugepagesPlan {
+    // If the CPU supports 1GB pages, the datasets use them and only the cache,
+    // the scratchpads (one 2MB page per thread) and monerod use 2MB pages.
+    pub fn new(cpu: &CpuLayout, memory: &MemoryState, threads: u64, monerod: bool) -> Self {
+        let mut pages_2mb = CACHE_PAGES_2MB + threads;
+        if monerod {
+            pages_2mb += MONEROD_PAGES_2MB;
+        }
+        let pages_1gb = if cpu.pdpe1gb {
+            DATASET_PAGES_1GB * cpu.numa_nodes
+        } else {
+            pages_2mb += DATASET_PAGES_2MB * cpu.numa_nodes;
+            0
+        };
+        // pages already reserved are not part of the available memory
+        let needed_kb = pages_2mb.saturating_sub(memory.pages_2mb) * PAGE_2MB_KB
+            + pages_1gb.saturating_sub(memory.pages_1gb) * PAGE_1GB_KB;
+        Self {
+            pages_2mb,
+            pages_1gb,
+            enough_memory: needed_kb <= memory.available_kb,
+        }
+    }
+    pub fn detect(threads: u64, monerod: bool) -> Self {
+        Self::new(&CPU_LAYOUT, &MemoryState::detect(), threads, monerod)
+    }
+    pub fn applied(&self, memory: &MemoryState) -> bool {
+        memory.pages_2mb >= self.pages_2mb && memory.pages_1gb >= self.pages_1gb
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- XMRig report
+// What XMRig printed about huge pages at startup.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HugepagesReport {
+    pub huge_pages: Option<String>,  // " * HUGE PAGES   supported"
+    pub pages_1gb: Option<String>,   // " * 1GB PAGES    disabled"
+    pub dataset: Option<Allocation>, // "randomx  allocated 2336 MB (2080+256) huge pages 100% 1168/1168"
+    pub threads: Option<Allocation>, // "cpu      READY threads 8/8 (8) huge pages 100% 8/8"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Allocation {
+    pub percent: u8,
+    pub allocated: u64,
+    pub total: u64,
+}
+
+impl Allocation {
+    fn from_captures(c: &regex::Captures) -> Option<Self> {
+        Some(Self {
+            percent: c.get(1)?.as_str().parse().ok()?,
+            allocated: c.get(2)?.as_str().parse().ok()?,
+            total: c.get(3)?.as_str().parse().ok()?,
+        })
+    }
+    pub fn complete(&self) -> bool {
+        self.allocated >= self.total
+    }
+}
+
+impl HugepagesReport {
+    pub fn update_from_output(&mut self, output: &str) {
+        for line in output.lines() {
+            if let Some(c) = XMRIG_REGEX.huge_pages.captures(line) {
+                self.huge_pages = Some(c[1].trim().to_string());
+            } else if let Some(c) = XMRIG_REGEX.pages_1gb.captures(line) {
+                self.pages_1gb = Some(c[1].trim().to_string());
+            } else if let Some(c) = XMRIG_REGEX.dataset_pages.captures(line) {
+                self.dataset = Allocation::from_captures(&c);
+            } else if let Some(c) = XMRIG_REGEX.threads_pages.captures(line) {
+                self.threads = Allocation::from_captures(&c);
+            }
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+    // Was everything allocated in huge pages, 1GB pages included if they are planned?
+    pub fn complete(&self, plan: &HugepagesPlan) -> bool {
+        self.dataset.is_some_and(|a| a.complete())
+            && self.threads.is_none_or(|a| a.complete())
+            && (plan.pages_1gb == 0 || self.pages_1gb.as_deref() == Some("supported"))
+    }
+    // What the user can do to fix a failed allocation, [None] if nothing failed.
+    pub fn remediation(&self, plan: &HugepagesPlan, memory: &MemoryState) -> Option<&'static str> {
+        if self.huge_pages.as_deref().is_some_and(|h| h != "supported") {
+            return Some(HUGEPAGES_UNSUPPORTED);
+        }
+        if self.complete(plan) {
+            return None;
+        }
+        if !plan.enough_memory {
+            return Some(HUGEPAGES_NOT_ENOUGH_MEMORY);
+        }
+        if plan.pages_1gb > memory.pages_1gb && memory.pages_2mb >= plan.pages_2mb {
+            // the 2MB pages are there, only the 1GB pages could not be reserved at runtime
+            return Some(HUGEPAGES_1GB_FRAGMENTED);
+        }
+        if self.dataset.is_some() || self.threads.is_some() {
+            return Some(HUGEPAGES_NOT_RESERVED);
+        }
+        None
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TEST
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detect_layout() {
+        assert!(CpuLayout::parse_pdpe1gb(
+            "processor\t: 0\nflags\t\t: fpu vme pse pdpe1gb rdtscp lm\n"
+        ));
+        assert!(!CpuLayout::parse_pdpe1gb(
+            "flags\t\t: fpu vme pse rdtscp lm\n"
+        ));
+        assert!(CpuLayout::is_numa_node("node12"));
+        assert!(!CpuLayout::is_numa_node("node"));
+        assert!(!CpuLayout::is_numa_node("online"));
+        let memory = MemoryState::parse_meminfo(
+            "MemTotal:       32658928 kB\nMemAvailable:   20000000 kB\nHugePages_Total:    1168\nHugePages_Free:        0\n",
+            3,
+        );
+        assert_eq!(
+            memory,
+            MemoryState {
+                available_kb: 20000000,
+                pages_2mb: 1168,
+                pages_1gb: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn calculate_plan() {
+        let memory = MemoryState {
+            available_kb: 8 * 1024 * 1024,
+            pages_2mb: 0,
+            pages_1gb: 0,
+        };
+        let cpu = CpuLayout {
+            pdpe1gb: false,
+            numa_nodes: 2,
+        };
+        let plan = HugepagesPlan::new(&cpu, &memory, 16, false);
+        assert_eq!(plan.pages_2mb, 2 * 1040 + 128 + 16);
+        assert_eq!(plan.pages_1gb, 0);
+        assert!(plan.enough_memory);
+        let cpu = CpuLayout {
+            pdpe1gb: true,
+            numa_nodes: 1,
+        };
+        let plan = HugepagesPlan::new(&cpu, &memory, 16, true);
+        assert_eq!(plan.pages_2mb, 128 + 16 + 256);
+        assert_eq!(plan.pages_1gb, 3);
+        assert!(plan.enough_memory);
+        let memory = MemoryState {
+            available_kb: 1024 * 1024,
+            ..memory
+        };
+        assert!(!HugepagesPlan::new(&cpu, &memory, 16, true).enough_memory);
+    }
+
+    #[test]
+    fn parse_xmrig_report() {
+        let output = r#" * ABOUT        XMRig/6.22.2 gcc/13.2.1 (built for Linux x86-64, 64 bit)
+ * HUGE PAGES   supported
+ * 1GB PAGES    disabled
+[2025-01-01 00:00:00.000]  randomx  allocated 2336 MB (2080+256) huge pages 50% 584/1168 +JIT (1 ms)
+[2025-01-01 00:00:01.000]  cpu      READY threads 8/8 (8) huge pages 100% 8/8 memory 16384 KB (5 ms)"#;
+        let mut report = HugepagesReport::default();
+        report.update_from_output(output);
+        assert_eq!(report.huge_pages.as_deref(), Some("supported"));
+        assert_eq!(report.pages_1gb.as_deref(), Some("disabled"));
+        assert_eq!(
+            report.dataset,
+            Some(Allocation {
+                percent: 50,
+                allocated: 584,
+                total: 1168
+            })
+        );
+        assert!(report.threads.is_some_and(|a| a.complete()));
+        let plan = HugepagesPlan {
+            pages_2mb: 1176,
+            pages_1gb: 0,
+            enough_memory: true,
+        };
+        let memory = MemoryState {
+            available_kb: 0,
+            pages_2mb: 584,
+            pages_1gb: 0,
+        };
+        assert!(!report.complete(&plan));
+        assert_eq!(
+            report.remediation(&plan, &memory),
+            Some(HUGEPAGES_NOT_RESERVED)
+        );
+        report.update_from_output(
+            "[2025-01-01 00:10:00.000]  randomx  allocated 2336 MB (2080+256) huge pages 100% 1168/1168 +JIT (1 ms)",
+        );
+        assert!(report.complete(&plan));
+        assert_eq!(report.remediation(&plan, &memory), None);
+    }
+}
@@ -15,13 +15,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod benchmarks;
 pub mod constants;
 pub mod errors;
 pub mod ferris;
+pub mod hugepages;
 pub mod human;
 pub mod macros;
 pub mod panic;
 pub mod regex;
 pub mod resets;
 pub mod sudo;
+pub mod topology;
 pub mod xmr;
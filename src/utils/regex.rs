@@ -17,7 +17,7 @@
 
 // Some regexes used throughout Gupax.
 
-use crate::helper::xvb::nodes::Pool;
+use crate::helper::xvb::{endpoints::XvbEndpoints, nodes::Pool};
 use log::{error, warn};
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -115,6 +115,11 @@ pub struct XmrigRegex {
     pub valid_conn: Regex,
     pub invalid_conn: Regex,
     pub error: Regex,
+    pub huge_pages: Regex,
+    pub pages_1gb: Regex,
+    pub dataset_pages: Regex,
+    pub threads_pages: Regex,
+    pub bench_finished: Regex,
 }
 
 impl XmrigRegex {
@@ -127,6 +132,12 @@ impl XmrigRegex {
             invalid_conn: Regex::new("error: 1").unwrap(),
             // we don't want to include connections status from xmrig-proxy that show the number of errors
             error: Regex::new(r"error: \D").unwrap(),
+            huge_pages: Regex::new(r"\* HUGE PAGES\s+(.+)$").unwrap(),
+            pages_1gb: Regex::new(r"\* 1GB PAGES\s+(.+)$").unwrap(),
+            dataset_pages: Regex::new(r"randomx\s+allocated .* huge pages (\d+)% (\d+)/(\d+)")
+                .unwrap(),
+            threads_pages: Regex::new(r"READY threads .* huge pages (\d+)% (\d+)/(\d+)").unwrap(),
+            bench_finished: Regex::new(r"benchmark finished in ([0-9.]+) seconds").unwrap(),
         }
     }
 }
@@ -156,37 +167,18 @@ pub fn nb_current_shares(s: &str) -> Option<u32> {
     }
     None
 }
-pub fn detect_pool_xmrig(s: &str, proxy_port: u16, p2pool_port: u16) -> Option<Pool> {
+pub fn detect_pool_xmrig(
+    s: &str,
+    proxy_port: u16,
+    p2pool_port: u16,
+    endpoints: &XvbEndpoints,
+) -> Option<Pool> {
     static CURRENT_SHARE: Lazy<Regex> =
         Lazy::new(|| Regex::new(r"(use pool|new job from) (?P<pool>.*:\d{1,5})(| diff)").unwrap());
     if let Some(c) = CURRENT_SHARE.captures(s) {
         if let Some(m) = c.name("pool") {
-            match m.as_str() {
-                // if user change address of local p2pool, it could create issue
-                x if x.contains("127.0.0.1") => {
-                    let port = x.split_once(":").unwrap_or_default().1.parse::<u16>();
-                    if let Ok(port) = port {
-                        if port == proxy_port {
-                            return Some(Pool::XmrigProxy(port));
-                        }
-                        if port == p2pool_port {
-                            return Some(Pool::P2pool(port));
-                        }
-                        return Some(Pool::Custom("127.0.0.1".to_string(), port));
-                    }
-                }
-                "eu.xmrvsbeast.com:4247" => {
-                    return Some(Pool::XvBEurope);
-                }
-                "na.xmrvsbeast.com:4247" => {
-                    return Some(Pool::XvBNorthAmerica);
-                }
-                x => {
-                    let (ip, port) = x.split_once(":").unwrap_or_default();
-                    if let Ok(port) = port.parse() {
-                        return Some(Pool::Custom(ip.to_string(), port));
-                    }
-                }
+            if let Some(pool) = pool_from_address(m.as_str(), proxy_port, p2pool_port, endpoints) {
+                return Some(pool);
             }
         }
     }
@@ -195,6 +187,40 @@ pub fn detect_pool_xmrig(s: &str, proxy_port: u16, p2pool_port: u16) -> Option<P
     );
     None
 }
+// get the pool from its address ip:port, as given by the xmrig console or HTTP API.
+pub fn pool_from_address(
+    address: &str,
+    proxy_port: u16,
+    p2pool_port: u16,
+    endpoints: &XvbEndpoints,
+) -> Option<Pool> {
+    // a XvB pool can be local when using a mock server.
+    if let Some(pool) = Pool::xvb_from_address(address, endpoints) {
+        return Some(pool);
+    }
+    match address {
+        // if user change address of local p2pool, it could create issue
+        x if x.contains("127.0.0.1") || x.starts_with("localhost:") => {
+            let port = x.split_once(":").unwrap_or_default().1.parse::<u16>();
+            if let Ok(port) = port {
+                if port == proxy_port {
+                    return Some(Pool::XmrigProxy(port));
+                }
+                if port == p2pool_port {
+                    return Some(Pool::P2pool(port));
+                }
+                return Some(Pool::Custom("127.0.0.1".to_string(), port));
+            }
+        }
+        x => {
+            let (ip, port) = x.split_once(":").unwrap_or_default();
+            if let Ok(port) = port.parse() {
+                return Some(Pool::Custom(ip.to_string(), port));
+            }
+        }
+    }
+    None
+}
 pub fn estimated_hr(s: &str) -> Option<f32> {
     static CURRENT_SHARE: Lazy<Regex> = Lazy::new(|| {
         Regex::new(r"(?P<nb>[-+]?[0-9]*\.?[0-9]+([eE][-+]?[0-9]+)?) (?P<unit>.*)H/s").unwrap()
@@ -337,4 +363,26 @@ mod test {
         );
         assert_eq!(r.new_job.find(text2).unwrap().as_str(), "new job");
     }
+
+    #[test]
+    fn pool_from_xmrig_address() {
+        let endpoints = XvbEndpoints::default();
+        assert_eq!(
+            pool_from_address("localhost:3333", 3355, 3333, &endpoints),
+            Some(Pool::P2pool(3333))
+        );
+        assert_eq!(
+            pool_from_address("127.0.0.1:3355", 3355, 3333, &endpoints),
+            Some(Pool::XmrigProxy(3355))
+        );
+        assert_eq!(
+            pool_from_address("na.xmrvsbeast.com:4247", 3355, 3333, &endpoints),
+            Some(Pool::xvb(endpoints.nodes[1].clone()))
+        );
+        assert_eq!(
+            pool_from_address("192.168.1.2:3333", 3355, 3333, &endpoints),
+            Some(Pool::Custom("192.168.1.2".to_string(), 3333))
+        );
+        assert_eq!(pool_from_address("", 3355, 3333, &endpoints), None);
+    }
 }
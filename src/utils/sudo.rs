@@ -21,20 +21,30 @@
 
 use crate::{
     constants::*,
-    disk::state::{P2pool, Xmrig, XmrigProxy},
+    disk::state::{P2pool, Socks5, Xmrig, XmrigProxy},
     helper::{Helper, ProcessSignal},
+    utils::hugepages::{HugepagesPlan, SYSFS_1GB_PAGES},
 };
 use enclose::enc;
 use log::*;
 use std::{
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
     process::*,
     sync::{Arc, Mutex},
     thread,
 };
 use zeroize::Zeroize;
 
+// written by the privileged setup, XMRig is started without sudo if it exists.
+const HUGEPAGES_SYSCTL_CONF: &str = "/etc/sysctl.d/60-gupaxx-hugepages.conf";
+// files of the older setups giving the MSR devices to XMRig, removed by the setup.
+const MSR_FILES: [&str; 3] = [
+    "/etc/udev/rules.d/60-gupaxx-msr.rules",
+    "/etc/modules-load.d/gupaxx-msr.conf",
+    "/etc/modprobe.d/gupaxx-msr.conf",
+];
+
 #[allow(dead_code)] // for dummy value windows
 #[derive(Debug, Clone)]
 pub struct SudoState {
@@ -45,6 +55,10 @@ pub struct SudoState {
     pub msg: String,   // The message shown to the user if unsuccessful
     pub pass: String,  // The actual password wrapped in a [SecretVec]
     pub signal: ProcessSignal, // Main GUI will set this depending on if we want [Start] or [Restart]
+    pub privileged_setup: bool, // Is the password asked for the privileged setup instead of starting XMRig?
+    pub privileged: Option<(PathBuf, bool)>, // Can XMRig at this path run without sudo? [None] if not checked yet.
+    pub setup_msg: String,                   // Result of the last privileged setup
+    pub setup_warning: bool, // Is the warning shown before running the privileged setup?
 }
 
 impl Default for SudoState {
@@ -66,6 +80,10 @@ impl SudoState {
             msg: String::new(),
             pass: String::new(),
             signal: ProcessSignal::None,
+            privileged_setup: false,
+            privileged: None,
+            setup_msg: String::new(),
+            setup_warning: false,
         }
     }
     #[cold]
@@ -80,6 +98,10 @@ impl SudoState {
             msg: "".to_string(),
             pass: String::with_capacity(256),
             signal: ProcessSignal::None,
+            privileged_setup: false,
+            privileged: None,
+            setup_msg: String::new(),
+            setup_warning: false,
         }
     }
 
@@ -91,6 +113,7 @@ impl SudoState {
         let mut state = state.lock().unwrap();
         state.testing = false;
         state.success = false;
+        state.privileged_setup = false;
         //		state.signal = ProcessSignal::None;
     }
 
@@ -120,9 +143,10 @@ impl SudoState {
         p2pool: &P2pool,
         proxy: &XmrigProxy,
         path: &Path,
+        socks5: &Socks5,
     ) {
         let path = path.to_path_buf();
-        thread::spawn(enc!((helper, xmrig, p2pool, proxy)  move || {
+        thread::spawn(enc!((helper, xmrig, p2pool, proxy, socks5)  move || {
             // Set to testing
             state.lock().unwrap().testing = true;
 
@@ -188,7 +212,10 @@ impl SudoState {
             if let Err(e) = sudo.kill() {
                 warn!("Sudo | Kill error (it probably already exited): {}", e);
             }
-            if state.lock().unwrap().success {
+            let setup = state.lock().unwrap().privileged_setup;
+            if state.lock().unwrap().success && setup {
+                Self::privileged_setup(&state, &path);
+            } else if state.lock().unwrap().success {
                 match state.lock().unwrap().signal {
                     ProcessSignal::Restart => crate::helper::Helper::restart_xmrig(
                         &helper,
@@ -197,6 +224,7 @@ impl SudoState {
                         &proxy,
                         &path,
                         Arc::clone(&state),
+                        &socks5,
                     ),
                     ProcessSignal::Stop => crate::helper::Helper::stop_xmrig(&helper),
                     _ => crate::helper::Helper::start_xmrig(
@@ -206,6 +234,7 @@ impl SudoState {
                         &proxy,
                         &path,
                         Arc::clone(&state),
+                        &socks5,
                     ),
                 }
             } else {
@@ -213,7 +242,140 @@ impl SudoState {
                 Self::wipe(&state);
             }
             state.lock().unwrap().signal = ProcessSignal::None;
+            state.lock().unwrap().privileged_setup = false;
             state.lock().unwrap().testing = false;
         }));
     }
+
+    #[cold]
+    #[inline(never)]
+    // One-time setup reserving the hugepages so XMRig can run as the normal user.
+    // The MSRs can only be configured by XMRig running as root, XMRig started without sudo does not apply the MSR mod.
+    // The reservation persists across reboots.
+    // Must be called after a successful [test_sudo()], the cached credentials of sudo are used.
+    #[cfg(target_os = "linux")]
+    fn privileged_setup(state: &Arc<Mutex<Self>>, path: &Path) {
+        // enough pages for every thread and for a Node started by Gupaxx
+        let plan = HugepagesPlan::detect(benri::threads!() as u64, true);
+        if !plan.enough_memory {
+            warn!("Sudo | Not enough available memory to reserve all the huge pages");
+        }
+        let script = Self::privileged_setup_script(&plan);
+        info!(
+            "Sudo | Running the privileged setup for [{}]",
+            path.display()
+        );
+        let output = Command::new("sudo")
+            .args(["--non-interactive", "sh", "-c", &script, "sh"])
+            .arg(path)
+            .output();
+        Self::wipe(state);
+        let mut state = state.lock().unwrap();
+        match output {
+            Ok(o) if o.status.success() => {
+                info!("Sudo | Privileged setup ... OK");
+                state.setup_msg =
+                    "Privileged setup done, XMRig will run without sudo and without the MSR mod"
+                        .to_string();
+            }
+            Ok(o) => {
+                let err = String::from_utf8_lossy(&o.stderr);
+                error!("Sudo | Privileged setup ... FAIL ... {}", err);
+                state.setup_msg = format!("Privileged setup failed: {}", err.trim());
+            }
+            Err(e) => {
+                error!("Sudo | Privileged setup ... FAIL ... {}", e);
+                state.setup_msg = format!("Privileged setup failed: {}", e);
+            }
+        }
+        state.privileged = Some((path.to_path_buf(), Self::xmrig_privileged(path)));
+    }
+    #[cfg(not(target_os = "linux"))]
+    fn privileged_setup(state: &Arc<Mutex<Self>>, _path: &Path) {
+        Self::wipe(state);
+        state.lock().unwrap().setup_msg =
+            "The privileged setup is only available on Linux".to_string();
+    }
+
+    // Shell script of the privileged setup, the path of XMRig is given as the first argument.
+    // 1GB pages can not be set with sysctl, they are persisted with a systemd-tmpfiles entry.
+    // Nothing is given to XMRig: it reads a configuration writable by the user, so any privilege
+    // given to it would be given to the user. What the older setups gave to XMRig is removed.
+    pub fn privileged_setup_script(plan: &HugepagesPlan) -> String {
+        let mut script = vec![
+            "set -e".to_string(),
+            format!("sysctl -w vm.nr_hugepages={}", plan.pages_2mb),
+            format!(
+                "echo vm.nr_hugepages={} > {HUGEPAGES_SYSCTL_CONF}",
+                plan.pages_2mb
+            ),
+        ];
+        if plan.pages_1gb > 0 {
+            script.push(format!("echo {} > {SYSFS_1GB_PAGES}", plan.pages_1gb));
+            script.push(format!(
+                "echo 'w {SYSFS_1GB_PAGES} - - - - {}' > /etc/tmpfiles.d/gupaxx-hugepages.conf",
+                plan.pages_1gb
+            ));
+        }
+        // the MSR devices go back to root only, they exist only if the msr module is loaded.
+        script.push(format!("rm -f {}", MSR_FILES.join(" ")));
+        script.push("chown root:root /dev/cpu/*/msr 2>/dev/null || true".to_string());
+        script.push("chmod 0600 /dev/cpu/*/msr 2>/dev/null || true".to_string());
+        // setcap fails if XMRig has no capabilities
+        script.push("setcap -r \"$1\" 2>/dev/null || true".to_string());
+        script.push("chmod g-s \"$1\"".to_string());
+        script.join("\n")
+    }
+
+    // Were the hugepages reserved by the privileged setup?
+    // If so, XMRig is started without sudo.
+    // XMRig set as setgid by an older setup still has privileges, the setup must be run again.
+    #[cfg(target_os = "linux")]
+    pub fn xmrig_privileged(path: &Path) -> bool {
+        use std::os::unix::fs::PermissionsExt;
+        let setgid = match std::fs::metadata(path) {
+            Ok(m) => m.permissions().mode() & 0o2000 != 0,
+            Err(e) => {
+                debug!("Sudo | XMRig metadata error: {}", e);
+                return false;
+            }
+        };
+        !setgid && Path::new(HUGEPAGES_SYSCTL_CONF).exists()
+    }
+    #[cfg(not(target_os = "linux"))]
+    pub fn xmrig_privileged(_path: &Path) -> bool {
+        false
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TEST
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn privileged_setup_script() {
+        let mut plan = HugepagesPlan {
+            pages_2mb: 1184,
+            pages_1gb: 0,
+            enough_memory: true,
+        };
+        let script = SudoState::privileged_setup_script(&plan);
+        assert!(script.starts_with("set -e\n"));
+        assert!(script.contains("sysctl -w vm.nr_hugepages=1184\n"));
+        assert!(script.contains("vm.nr_hugepages=1184 > /etc/sysctl.d/"));
+        assert!(!script.contains(SYSFS_1GB_PAGES));
+        plan.pages_1gb = 3;
+        let script = SudoState::privileged_setup_script(&plan);
+        assert!(script.contains(&format!("echo 3 > {SYSFS_1GB_PAGES}\n")));
+        // nothing is given to XMRig, what the older setups gave is removed
+        assert!(!script.contains("modprobe msr"));
+        assert!(!script.contains("setcap cap_"));
+        assert!(!script.contains("g+s"));
+        assert!(script.contains(&format!("rm -f {}\n", MSR_FILES.join(" "))));
+        assert!(script.contains("chmod 0600 /dev/cpu/*/msr"));
+        assert!(script.contains("setcap -r \"$1\""));
+        // the path is never interpolated in the script
+        assert!(script.ends_with("chmod g-s \"$1\""));
+    }
 }
@@ -0,0 +1,269 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// CPU topology used by the thread layout editor of XMRig.
+// The logical CPUs come from [sysinfo], their NUMA node and L3 cache from [/sys] on Linux.
+// On other OS, every CPU is put in the same node and cache.
+
+use once_cell::sync::Lazy;
+
+// a RandomX thread needs 2MB of L3 cache to not slow down the others.
+const L3_PER_THREAD_KB: u64 = 2048;
+// [--cpu-affinity] of XMRig is a 64 bits mask.
+pub const MAX_AFFINITY_CPU: u16 = 64;
+
+pub static CPU_TOPOLOGY: Lazy<CpuTopology> = Lazy::new(CpuTopology::detect);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpuTopology {
+    pub cpus: Vec<LogicalCpu>,
+    pub l3_caches: Vec<L3Cache>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogicalCpu {
+    pub id: u16,
+    pub core: u32, // physical core, shared by the SMT siblings
+    pub node: u16, // NUMA node
+    pub l3: usize, // index in [l3_caches]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L3Cache {
+    pub size_kb: u64,
+    pub cpus: Vec<u16>,
+}
+
+impl CpuTopology {
+    fn detect() -> Self {
+        let system = sysinfo::System::new_with_specifics(
+            sysinfo::RefreshKind::nothing().with_cpu(sysinfo::CpuRefreshKind::nothing()),
+        );
+        let count = (system.cpus().len() as u16).max(1);
+        #[cfg(target_os = "linux")]
+        {
+            Self::detect_sys(count)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Self::flat(count, 0)
+        }
+    }
+    // every CPU in one node and one cache of unknown size
+    fn flat(count: u16, size_kb: u64) -> Self {
+        Self {
+            cpus: (0..count)
+                .map(|id| LogicalCpu {
+                    id,
+                    core: id as u32,
+                    node: 0,
+                    l3: 0,
+                })
+                .collect(),
+            l3_caches: vec![L3Cache {
+                size_kb,
+                cpus: (0..count).collect(),
+            }],
+        }
+    }
+    #[cfg(target_os = "linux")]
+    fn detect_sys(count: u16) -> Self {
+        let read = |path: String| std::fs::read_to_string(path).unwrap_or_default();
+        let mut nodes = vec![];
+        if let Ok(dir) = std::fs::read_dir("/sys/devices/system/node") {
+            for entry in dir.filter_map(Result::ok) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if let Some(Ok(node)) = name.strip_prefix("node").map(str::parse::<u16>) {
+                    let cpulist = read(format!("/sys/devices/system/node/{name}/cpulist"));
+                    nodes.push((node, parse_cpulist(&cpulist)));
+                }
+            }
+        }
+        let mut topology = Self {
+            cpus: vec![],
+            l3_caches: vec![],
+        };
+        for id in 0..count {
+            let cpu = format!("/sys/devices/system/cpu/cpu{id}");
+            let package: u32 = read(format!("{cpu}/topology/physical_package_id"))
+                .trim()
+                .parse()
+                .unwrap_or_default();
+            let core: u32 = read(format!("{cpu}/topology/core_id"))
+                .trim()
+                .parse()
+                .unwrap_or(id as u32);
+            let node = nodes
+                .iter()
+                .find(|(_, cpus)| cpus.contains(&id))
+                .map(|(node, _)| *node)
+                .unwrap_or_default();
+            let shared = parse_cpulist(&read(format!("{cpu}/cache/index3/shared_cpu_list")));
+            let l3 = match topology.l3_caches.iter().position(|c| c.cpus == shared) {
+                Some(l3) => l3,
+                None => {
+                    topology.l3_caches.push(L3Cache {
+                        size_kb: parse_size_kb(&read(format!("{cpu}/cache/index3/size"))),
+                        cpus: shared,
+                    });
+                    topology.l3_caches.len() - 1
+                }
+            };
+            topology.cpus.push(LogicalCpu {
+                id,
+                // core ids are only unique inside a package
+                core: (package << 16) | core,
+                node,
+                l3,
+            });
+        }
+        // /sys is not readable (container...), keep the CPUs usable
+        if topology.l3_caches.iter().all(|c| c.cpus.is_empty()) {
+            return Self::flat(count, topology.l3_caches.first().map_or(0, |c| c.size_kb));
+        }
+        topology
+    }
+    pub fn nodes(&self) -> Vec<u16> {
+        let mut nodes = self.cpus.iter().map(|c| c.node).collect::<Vec<_>>();
+        nodes.sort_unstable();
+        nodes.dedup();
+        nodes
+    }
+    pub fn node_cpus(&self, node: u16) -> Vec<u16> {
+        self.cpus
+            .iter()
+            .filter(|c| c.node == node && c.id < MAX_AFFINITY_CPU)
+            .map(|c| c.id)
+            .collect()
+    }
+    // As many threads as the L3 caches can hold, placed on distinct physical cores first.
+    // If the size of the caches is unknown, one thread per physical core.
+    pub fn l3_aware_cpus(&self) -> Vec<u16> {
+        let mut selected = vec![];
+        for (index, cache) in self.l3_caches.iter().enumerate() {
+            let cpus = self
+                .cpus
+                .iter()
+                .filter(|c| c.l3 == index && c.id < MAX_AFFINITY_CPU)
+                .collect::<Vec<_>>();
+            let mut cores = cpus.iter().map(|c| c.core).collect::<Vec<_>>();
+            cores.sort_unstable();
+            cores.dedup();
+            let max = if cache.size_kb == 0 {
+                cores.len()
+            } else {
+                (cache.size_kb / L3_PER_THREAD_KB) as usize
+            };
+            // first CPU of every physical core, then their SMT siblings
+            let mut firsts = vec![];
+            let mut siblings = vec![];
+            for cpu in cpus {
+                if firsts.iter().any(|c: &&LogicalCpu| c.core == cpu.core) {
+                    siblings.push(cpu);
+                } else {
+                    firsts.push(cpu);
+                }
+            }
+            selected.extend(firsts.into_iter().chain(siblings).take(max).map(|c| c.id));
+        }
+        selected.sort_unstable();
+        selected
+    }
+}
+
+// "0-3,8,10-11" => [0, 1, 2, 3, 8, 10, 11]
+pub fn parse_cpulist(list: &str) -> Vec<u16> {
+    let mut cpus = vec![];
+    for range in list.trim().split(',').filter(|r| !r.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<u16>(), end.parse::<u16>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => {
+                if let Ok(cpu) = range.parse() {
+                    cpus.push(cpu);
+                }
+            }
+        }
+    }
+    cpus
+}
+
+// "32768K" => 32768
+fn parse_size_kb(size: &str) -> u64 {
+    let size = size.trim();
+    if let Some(mb) = size.strip_suffix('M') {
+        mb.parse::<u64>().map(|m| m * 1024).unwrap_or_default()
+    } else {
+        size.trim_end_matches('K').parse().unwrap_or_default()
+    }
+}
+
+// mask for [--cpu-affinity], CPUs beyond the 64 bits are ignored.
+pub fn affinity_mask(cpus: &[u16]) -> u64 {
+    cpus.iter()
+        .filter(|c| **c < MAX_AFFINITY_CPU)
+        .fold(0, |mask, c| mask | (1 << c))
+}
+
+//---------------------------------------------------------------------------------------------------- TEST
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_sys_files() {
+        assert_eq!(parse_cpulist("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpulist(""), Vec::<u16>::new());
+        assert_eq!(parse_size_kb("32768K\n"), 32768);
+        assert_eq!(parse_size_kb("32M"), 32768);
+        assert_eq!(affinity_mask(&[0, 1, 3, 63, 64]), 0x800000000000000b);
+    }
+
+    #[test]
+    fn l3_aware_threads() {
+        // 2 CCX of 4 cores with SMT, 8MB of L3 each: 4 threads per CCX
+        let cpus = (0..16)
+            .map(|id| LogicalCpu {
+                id,
+                core: (id % 8) as u32,
+                node: 0,
+                l3: (id % 8 / 4) as usize,
+            })
+            .collect();
+        let topology = CpuTopology {
+            cpus,
+            l3_caches: vec![
+                L3Cache {
+                    size_kb: 8192,
+                    cpus: vec![0, 1, 2, 3, 8, 9, 10, 11],
+                },
+                L3Cache {
+                    size_kb: 8192,
+                    cpus: vec![4, 5, 6, 7, 12, 13, 14, 15],
+                },
+            ],
+        };
+        assert_eq!(topology.l3_aware_cpus(), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(topology.nodes(), vec![0]);
+        // unknown size of cache
+        let topology = CpuTopology::flat(4, 0);
+        assert_eq!(topology.l3_aware_cpus(), vec![0, 1, 2, 3]);
+    }
+}